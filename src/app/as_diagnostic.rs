@@ -1,34 +1,60 @@
-use crate::lexer::LexicalError;
+use super::build::BuildError;
+use crate::ast;
+use crate::lexer::{LexicalError, Location};
 use crate::parser::ParseError;
+use crate::semantic_analyser::{diagnostic, Error as SemanticError};
+use crate::token::Token;
 use codespan::{ByteIndex, FileMap, Span};
 use codespan_reporting::{Diagnostic, Label, Severity};
 use failure::Fail;
 use std::fmt::Write;
 
 pub trait AsDiagnostic {
-    fn as_diagnostic(&self, file_map: &FileMap) -> Option<Diagnostic>;
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic>;
 }
 
 impl AsDiagnostic for failure::Error {
-    fn as_diagnostic(&self, file_map: &FileMap) -> Option<Diagnostic> {
-        self.as_fail().as_diagnostic(file_map)
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic> {
+        self.as_fail().as_diagnostics(file_map)
     }
 }
 
 impl AsDiagnostic for &dyn Fail {
-    fn as_diagnostic(&self, file_map: &FileMap) -> Option<Diagnostic> {
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic> {
         if let Some(error) = self.downcast_ref::<LexicalError>() {
-            return error.as_diagnostic(file_map);
+            return error.as_diagnostic(file_map).into_iter().collect();
         }
 
         if let Some(error) = self.downcast_ref::<ParseError>() {
-            return error.as_diagnostic(file_map);
+            return error.as_diagnostics(file_map);
         }
 
-        None
+        if let Some(error) = self.downcast_ref::<SemanticError>() {
+            return error.as_diagnostics(file_map);
+        }
+
+        if let Some(error) = self.downcast_ref::<BuildError>() {
+            return vec![Diagnostic::new(Severity::Error, error.to_string())];
+        }
+
+        Vec::new()
     }
 }
 
+impl AsDiagnostic for [diagnostic::Warning] {
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic> {
+        self.iter()
+            .map(|warning| warning.as_diagnostic(file_map))
+            .collect()
+    }
+}
+
+/// Moves a span of the AST, which starts at the beginning of the source, into the code map.
+fn code_map_span(file_map: &FileMap, span: ast::Span) -> ast::Span {
+    let offset = file_map.span().start() - ByteIndex(0);
+    Span::new(span.start() + offset, span.end() + offset)
+}
+
 impl LexicalError {
     fn as_diagnostic(&self, _file_map: &FileMap) -> Option<Diagnostic> {
         let span = Span::new(
@@ -42,10 +68,20 @@ impl LexicalError {
 }
 
 impl ParseError {
-    fn as_diagnostic(&self, file_map: &FileMap) -> Option<Diagnostic> {
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic> {
+        self.errors
+            .iter()
+            .filter_map(|error| Self::as_diagnostic(error, file_map))
+            .collect()
+    }
+
+    fn as_diagnostic(
+        error: &lalrpop_util::ParseError<Location, Token, failure::Error>,
+        file_map: &FileMap,
+    ) -> Option<Diagnostic> {
         use lalrpop_util::ParseError::*;
 
-        match &self.error {
+        match error {
             UnrecognizedToken { token, expected } => {
                 let error = Diagnostic::new(Severity::Error, "An unexpected token was observed");
 
@@ -99,8 +135,120 @@ impl ParseError {
 
                 Some(error)
             }
-            User { error } => error.as_diagnostic(file_map),
+            User { error } => error.as_diagnostics(file_map).into_iter().next(),
             _ => None,
         }
     }
 }
+
+impl SemanticError {
+    fn as_diagnostics(&self, file_map: &FileMap) -> Vec<Diagnostic> {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.as_diagnostic(file_map))
+            .collect()
+    }
+}
+
+impl diagnostic::Diagnostic {
+    fn as_diagnostic(&self, file_map: &FileMap) -> Diagnostic {
+        match self {
+            diagnostic::Diagnostic::Error(error) => error.as_diagnostic(file_map),
+            diagnostic::Diagnostic::Warning(warning) => warning.as_diagnostic(file_map),
+        }
+    }
+}
+
+impl diagnostic::Error {
+    fn as_diagnostic(&self, file_map: &FileMap) -> Diagnostic {
+        use diagnostic::Error::*;
+
+        let span = |span| code_map_span(file_map, span);
+
+        match self {
+            AlreadyDeclared { span: s, previous } => {
+                Diagnostic::new(Severity::Error, "Identifier already declared")
+                    .with_label(Label::new_primary(span(*s)).with_message("declared again here"))
+                    .with_label(
+                        Label::new_secondary(span(*previous))
+                            .with_message("previous declaration here"),
+                    )
+            }
+            Undeclared { span: s } => Diagnostic::new(Severity::Error, "Undeclared identifier")
+                .with_label(Label::new_primary(span(*s)).with_message("not found in this scope")),
+            VectorWithoutIndice {
+                span: s,
+                declaration,
+            } => Diagnostic::new(Severity::Error, "Vector used without indice")
+                .with_label(Label::new_primary(span(*s)).with_message("expected an indice"))
+                .with_label(
+                    Label::new_secondary(span(*declaration))
+                        .with_message("declared as a vector here"),
+                ),
+            ScalarWithIndice {
+                span: s,
+                declaration,
+            } => Diagnostic::new(Severity::Error, "Scalar used with an indice")
+                .with_label(Label::new_primary(span(*s)).with_message("unexpected indice"))
+                .with_label(
+                    Label::new_secondary(span(*declaration))
+                        .with_message("declared as a scalar here"),
+                ),
+            VectorExpected {
+                span: s,
+                declaration,
+            } => Diagnostic::new(Severity::Error, "Vector argument expected")
+                .with_label(Label::new_primary(span(*s)).with_message("expected a vector"))
+                .with_label(
+                    Label::new_secondary(span(*declaration)).with_message("argument declared here"),
+                ),
+            TypeConversion {
+                span: s,
+                expected,
+                found,
+            } => Diagnostic::new(Severity::Error, "Invalid type conversion").with_label(
+                Label::new_primary(span(*s))
+                    .with_message(format!("expected {}, found {}", expected, found)),
+            ),
+            InvalidFunctionArguments {
+                span: s,
+                declaration,
+                expected,
+                found,
+            } => Diagnostic::new(Severity::Error, "Invalid number of arguments")
+                .with_label(Label::new_primary(span(*s)).with_message(format!(
+                    "expected {} argument(s), found {}",
+                    expected, found
+                )))
+                .with_label(
+                    Label::new_secondary(span(*declaration)).with_message("function declared here"),
+                ),
+            MainUndeclared => Diagnostic::new(Severity::Error, "No main function declared"),
+        }
+    }
+}
+
+impl diagnostic::Warning {
+    fn as_diagnostic(&self, file_map: &FileMap) -> Diagnostic {
+        use diagnostic::Warning::*;
+
+        let span = |span| code_map_span(file_map, span);
+
+        match self {
+            VariableShadowing { span: s, previous } => {
+                Diagnostic::new(Severity::Warning, "Variable shadows another declaration")
+                    .with_label(Label::new_primary(span(*s)).with_message("shadowing declaration"))
+                    .with_label(
+                        Label::new_secondary(span(*previous))
+                            .with_message("shadowed declaration here"),
+                    )
+            }
+            UnusedVariable { span: s } => Diagnostic::new(Severity::Warning, "Unused variable")
+                .with_label(Label::new_primary(span(*s)).with_message("never used")),
+            UnusedFunction { span: s } => Diagnostic::new(Severity::Warning, "Unused function")
+                .with_label(Label::new_primary(span(*s)).with_message("never called")),
+            UnusedArgument { span: s } => Diagnostic::new(Severity::Warning, "Unused argument")
+                .with_label(Label::new_primary(span(*s)).with_message("never used")),
+        }
+    }
+}
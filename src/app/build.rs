@@ -0,0 +1,146 @@
+use crate::codegen::{elf::Elf, nasm::Nasm, Syntax, Target};
+use crate::symbol_table::SymbolTable;
+use crate::three_address_code::ThreeAddressCode;
+use failure::Fallible;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Fail)]
+pub enum BuildError {
+    #[fail(display = "could not run {}: {}", _0, _1)]
+    Spawn(&'static str, #[cause] io::Error),
+    #[fail(display = "{} failed\n{}", _0, _1)]
+    Failed(&'static str, String),
+}
+
+/// Writes the assembly code in a temporary directory, then assembles it with nasm or GNU as
+/// depending on the syntax and links it into `output`. The directory is removed afterwards
+/// unless `keep_temps` is set.
+pub fn build(
+    three_a: &ThreeAddressCode,
+    symbol_table: &SymbolTable,
+    target: Target,
+    syntax: Syntax,
+    output: &Path,
+    keep_temps: bool,
+) -> Fallible<()> {
+    let dir = temp_dir()?;
+
+    let result = assemble(three_a, symbol_table, target, syntax, &dir, output);
+
+    if keep_temps {
+        eprintln!("fichiers intermédiaires gardés dans {}", dir.display());
+        return result;
+    }
+
+    // An error of the build matters more than one of the cleanup.
+    let cleanup = fs::remove_dir_all(&dir);
+    result?;
+    cleanup?;
+
+    Ok(())
+}
+
+/// Creates a new directory in the temporary directory, never one already used by another build.
+fn temp_dir() -> io::Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    loop {
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("l-compilator-{}-{}", process::id(), count));
+
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(ref error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Writes the executable itself, without any other tool.
+pub fn write_elf(
+    three_a: &ThreeAddressCode,
+    symbol_table: &SymbolTable,
+    target: Target,
+    output: &Path,
+) -> Fallible<()> {
+    let mut f = BufWriter::new(File::create(output)?);
+    three_a.to_elf(symbol_table, target, &mut f)?;
+    f.flush()?;
+    #[cfg(unix)]
+    fs::set_permissions(output, fs::Permissions::from_mode(0o755))?;
+
+    Ok(())
+}
+
+fn assemble(
+    three_a: &ThreeAddressCode,
+    symbol_table: &SymbolTable,
+    target: Target,
+    syntax: Syntax,
+    dir: &Path,
+    output: &Path,
+) -> Fallible<()> {
+    let name = output.file_name().unwrap_or_else(|| "a.out".as_ref());
+    let extension = match syntax {
+        Syntax::Intel => "nasm",
+        Syntax::Att => "s",
+    };
+    let asm_file = dir.join(name).with_extension(extension);
+    let object = dir.join(name).with_extension("o");
+
+    let mut f = BufWriter::new(File::create(&asm_file)?);
+    match syntax {
+        Syntax::Intel => three_a.to_nasm(symbol_table, target, &mut f)?,
+        Syntax::Att => three_a.to_gas(symbol_table, target, &mut f)?,
+    }
+    f.flush()?;
+
+    let (format, mode, emulation) = match target {
+        Target::X86 => ("elf", "--32", "elf_i386"),
+        Target::X86_64 => ("elf64", "--64", "elf_x86_64"),
+    };
+
+    match syntax {
+        Syntax::Intel => run(
+            "nasm",
+            Command::new("nasm")
+                .args(["-f", format, "-o"])
+                .arg(&object)
+                .arg(&asm_file),
+        )?,
+        Syntax::Att => run(
+            "as",
+            Command::new("as")
+                .args([mode, "-o"])
+                .arg(&object)
+                .arg(&asm_file),
+        )?,
+    }
+    run(
+        "ld",
+        Command::new("ld")
+            .args(["-m", emulation, "-o"])
+            .arg(output)
+            .arg(&object),
+    )
+}
+
+/// Runs a tool, its error output becoming the error on failure.
+fn run(name: &'static str, command: &mut Command) -> Fallible<()> {
+    let output = command
+        .output()
+        .map_err(|error| BuildError::Spawn(name, error))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BuildError::Failed(name, stderr.trim_end().to_owned()).into());
+    }
+
+    Ok(())
+}
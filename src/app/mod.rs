@@ -1,32 +1,68 @@
 mod as_diagnostic;
+mod build;
 mod opt;
 
-use crate::c_code;
+use crate::ast::Program;
+use crate::bytecode::{Bytecode, GenBytecode};
+use crate::cfg::ControlFlowGraph;
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+use crate::codegen::jit::Jit;
+use crate::codegen::{c::C, llvm::Llvm, nasm::Nasm, wat::Wat, Syntax, Target};
+use crate::format::dot::Dot;
 use crate::format::three_a::ThreeA;
 use crate::gen_three_address_code::GenThreeAddressCode;
-use crate::semantic_analyser::Analyse;
+use crate::optimize::PassManager;
+use crate::run_three_address_code::RunThreeAddressCode;
+use crate::semantic_analyser::{diagnostic, Analyse, Error as SemanticError};
+use crate::symbol_table::SymbolTable;
+use crate::three_address_code::ThreeAddressCode;
 use crate::{format::asynt::Asynt, lexer::Lexer, parser::Parser};
 use as_diagnostic::AsDiagnostic;
 use codespan::CodeMap;
-use codespan_reporting::{emit, termcolor::StandardStream};
+use codespan_reporting::{emit, termcolor::StandardStream, Severity};
 use failure::{Error, Fallible, ResultExt};
-use opt::Opt;
-use structopt::StructOpt;
+use opt::{Command, Emit, Opt, Warnings};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use structopt::{clap, StructOpt};
 
 pub struct App;
 
+/// The machine code of the JIT is x86_64 code calling the memory mapping of Linux.
+#[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+#[derive(Debug, Fail)]
+#[fail(display = "jit is unsupported on this target")]
+struct UnsupportedJit;
+
 impl App {
     pub fn run() -> Result<(), Error> {
         let opt = Opt::from_args();
+        let (source_file, command) = match &opt.command {
+            Some(Command::Build { source_file, .. })
+            | Some(Command::Jit { source_file })
+            | Some(Command::Bytecode { source_file, .. }) => (source_file, true),
+            Some(Command::Vm { bytecode_file }) => return Self::vm(bytecode_file),
+            Some(Command::Disasm { bytecode_file }) => return Self::disasm(bytecode_file),
+            None => match &opt.source_file {
+                Some(source_file) => (source_file, false),
+                None => clap::Error::with_description(
+                    "The source file is required without a command",
+                    clap::ErrorKind::MissingRequiredArgument,
+                )
+                .exit(),
+            },
+        };
 
         let mut code_map = CodeMap::new();
         let file_map = code_map
-            .add_filemap_from_disk(&opt.source_file)
-            .with_context(|_| format!("could not read file {:?}", opt.source_file))?;
+            .add_filemap_from_disk(source_file)
+            .with_context(|_| format!("could not read file {:?}", source_file))?;
         let content = file_map.src();
         let mut diagnostics = Vec::new();
+        let mut warnings = Vec::new();
 
-        let res: &Fn() -> Fallible<()> = &|| {
+        let mut res = || -> Fallible<()> {
             if opt.lex {
                 Self::print_lex(&content)?;
             }
@@ -35,32 +71,138 @@ impl App {
                 Self::print_ast(&content)?;
             }
 
+            if !(opt.symbol_table
+                || opt.three_address_code
+                || opt.dump_cfg
+                || opt.nasm
+                || opt.emit.is_some()
+                || opt.run
+                || command)
+            {
+                return Ok(());
+            }
+
+            let (ast, symbol_table) = Self::analyse(content, opt.warnings, &mut warnings)?;
+
             if opt.symbol_table {
-                Self::print_tab(&content)?;
+                Self::print_tab(&symbol_table)?;
+            }
+
+            if !(opt.three_address_code
+                || opt.dump_cfg
+                || opt.nasm
+                || opt.emit.is_some()
+                || opt.run
+                || command)
+            {
+                return Ok(());
+            }
+
+            let mut passes = PassManager::new(opt.level);
+            for pass in &opt.enable {
+                passes.enable(*pass);
+            }
+            for pass in &opt.disable {
+                passes.disable(*pass);
             }
 
+            let three_a = Self::gen_three_a(&ast, &symbol_table, &passes);
+
             if opt.three_address_code {
-                Self::print_three_a(&content)?;
+                Self::print_three_a(&three_a)?;
+            }
+
+            if opt.dump_cfg {
+                Self::print_cfg(&three_a)?;
             }
 
             if opt.nasm {
-                Self::print_nasm(&content)?;
+                Self::print_nasm(&three_a, &symbol_table, opt.target, opt.syntax)?;
+            }
+
+            if let Some(emit) = opt.emit {
+                Self::print_emit(&three_a, &symbol_table, emit)?;
+            }
+
+            if opt.run {
+                Self::run_three_a(&three_a)?;
+            }
+
+            if let Some(Command::Build {
+                output,
+                keep_temps,
+                elf,
+                ..
+            }) = &opt.command
+            {
+                let output = output
+                    .clone()
+                    .unwrap_or_else(|| source_file.with_extension(""));
+                if *elf {
+                    build::write_elf(&three_a, &symbol_table, opt.target, &output)?;
+                } else {
+                    build::build(
+                        &three_a,
+                        &symbol_table,
+                        opt.target,
+                        opt.syntax,
+                        &output,
+                        *keep_temps,
+                    )?;
+                }
+            }
+
+            if let Some(Command::Jit { .. }) = &opt.command {
+                Self::jit(&three_a, &symbol_table)?;
+            }
+
+            if let Some(Command::Bytecode { output, .. }) = &opt.command {
+                let output = output
+                    .clone()
+                    .unwrap_or_else(|| source_file.with_extension("lbc"));
+                Self::write_bytecode(&three_a, &symbol_table, &output)?;
             }
 
             Ok(())
         };
 
         if let Err(error) = res() {
-            let error = error.as_diagnostic(&file_map).ok_or(error)?;
-            diagnostics.push(error);
+            let errors = error.as_diagnostics(&file_map);
+            if errors.is_empty() {
+                return Err(error);
+            }
+            diagnostics.extend(errors);
         }
 
+        diagnostics.extend(warnings.as_diagnostics(&file_map));
+
+        let diagnostics: Vec<_> = diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                if diagnostic.severity == Severity::Warning {
+                    match opt.warnings {
+                        Warnings::All => {}
+                        Warnings::Error => diagnostic.severity = Severity::Error,
+                        Warnings::None => return None,
+                    }
+                }
+                Some(diagnostic)
+            })
+            .collect();
+
         if !diagnostics.is_empty() {
             let writer = StandardStream::stderr(codespan_reporting::termcolor::ColorChoice::Auto);
             for diagnostic in &diagnostics {
-                emit(&mut writer.lock(), &code_map, &diagnostic).unwrap();
-                println!();
+                let mut writer = writer.lock();
+                emit(&mut writer, &code_map, diagnostic).unwrap();
+                writeln!(writer).unwrap();
             }
+        }
+
+        if diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+        {
             std::process::exit(1);
         }
 
@@ -80,40 +222,147 @@ impl App {
         Ok(())
     }
 
-    fn print_tab(content: &str) -> Fallible<()> {
+    /// Parses and analyses the source. With `-W error`, any warning aborts the compilation.
+    fn analyse(
+        content: &str,
+        level: Warnings,
+        warnings: &mut Vec<diagnostic::Warning>,
+    ) -> Fallible<(Program, SymbolTable)> {
         let l = Lexer::new(&content);
         let p = Parser::new();
 
-        p.parse(l)?
-            .analyse()?
-            .as_table(&mut std::io::stdout().lock())?;
+        let ast = p.parse(l)?;
+        let (symbol_table, found) = ast.analyse()?;
+
+        if level == Warnings::Error && !found.is_empty() {
+            return Err(SemanticError {
+                diagnostics: found
+                    .into_iter()
+                    .map(diagnostic::Diagnostic::Warning)
+                    .collect(),
+            }
+            .into());
+        }
+
+        *warnings = found;
+
+        Ok((ast, symbol_table))
+    }
+
+    fn print_tab(symbol_table: &SymbolTable) -> Fallible<()> {
+        symbol_table.as_table(&mut std::io::stdout().lock())?;
 
         Ok(())
     }
 
-    fn print_three_a(content: &str) -> Fallible<()> {
-        let l = Lexer::new(&content);
-        let p = Parser::new();
+    fn gen_three_a(
+        ast: &Program,
+        symbol_table: &SymbolTable,
+        passes: &PassManager,
+    ) -> ThreeAddressCode {
+        let mut three_a = ast.gen_three_address_code(symbol_table, 0);
+        passes.run(&mut three_a);
+        three_a
+    }
 
-        let ast = p.parse(l)?;
-        let symbol_table = ast.analyse()?;
-        ast.gen_three_address_code(&symbol_table, 0)
-            .three_a(&mut std::io::stdout().lock())?;
+    fn print_three_a(three_a: &ThreeAddressCode) -> Fallible<()> {
+        three_a.three_a(&mut std::io::stdout().lock())?;
 
         Ok(())
     }
 
-    fn print_nasm(content: &str) -> Fallible<()> {
-        let l = Lexer::new(&content);
-        let p = Parser::new();
+    fn print_cfg(three_a: &ThreeAddressCode) -> Fallible<()> {
+        ControlFlowGraph::new(three_a).dot(three_a, &mut std::io::stdout().lock())?;
 
-        let ast = p.parse(l)?;
-        let symbol_table = ast.analyse()?;
-        c_code::print_nasm(
-            &ast.gen_three_address_code(&symbol_table, 0),
-            &symbol_table,
-            0,
+        Ok(())
+    }
+
+    fn print_nasm(
+        three_a: &ThreeAddressCode,
+        symbol_table: &SymbolTable,
+        target: Target,
+        syntax: Syntax,
+    ) -> Fallible<()> {
+        let f = &mut std::io::stdout().lock();
+
+        match syntax {
+            Syntax::Intel => three_a.to_nasm(symbol_table, target, f)?,
+            Syntax::Att => three_a.to_gas(symbol_table, target, f)?,
+        }
+
+        Ok(())
+    }
+
+    fn print_emit(
+        three_a: &ThreeAddressCode,
+        symbol_table: &SymbolTable,
+        emit: Emit,
+    ) -> Fallible<()> {
+        let f = &mut std::io::stdout().lock();
+
+        match emit {
+            Emit::C => three_a.to_c(symbol_table, f)?,
+            Emit::Llvm => three_a.to_llvm(symbol_table, f)?,
+            Emit::Wat => three_a.to_wat(symbol_table, f)?,
+        }
+
+        Ok(())
+    }
+
+    fn run_three_a(three_a: &ThreeAddressCode) -> Fallible<()> {
+        three_a.run(&mut std::io::stdin().lock(), &mut std::io::stdout().lock())?;
+
+        Ok(())
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn jit(three_a: &ThreeAddressCode, symbol_table: &SymbolTable) -> Fallible<()> {
+        three_a.jit(
+            symbol_table,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stdout().lock(),
+        )?;
+
+        Ok(())
+    }
+
+    #[cfg(not(all(target_os = "linux", target_arch = "x86_64")))]
+    fn jit(_: &ThreeAddressCode, _: &SymbolTable) -> Fallible<()> {
+        Err(UnsupportedJit.into())
+    }
+
+    fn write_bytecode(
+        three_a: &ThreeAddressCode,
+        symbol_table: &SymbolTable,
+        output: &Path,
+    ) -> Fallible<()> {
+        let mut f = BufWriter::new(
+            File::create(output).with_context(|_| format!("could not create file {:?}", output))?,
         );
+        three_a.gen_bytecode(symbol_table).write(&mut f)?;
+        f.flush()?;
+
+        Ok(())
+    }
+
+    fn read_bytecode(bytecode_file: &Path) -> Fallible<Bytecode> {
+        let f = File::open(bytecode_file)
+            .with_context(|_| format!("could not read file {:?}", bytecode_file))?;
+        let bytecode = Bytecode::read(&mut BufReader::new(f))
+            .with_context(|_| format!("could not load file {:?}", bytecode_file))?;
+
+        Ok(bytecode)
+    }
+
+    fn vm(bytecode_file: &Path) -> Fallible<()> {
+        Self::read_bytecode(bytecode_file)?
+            .run(&mut std::io::stdin().lock(), &mut std::io::stdout().lock())?;
+
+        Ok(())
+    }
+
+    fn disasm(bytecode_file: &Path) -> Fallible<()> {
+        Self::read_bytecode(bytecode_file)?.disasm(&mut std::io::stdout().lock())?;
 
         Ok(())
     }
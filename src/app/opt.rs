@@ -1,4 +1,7 @@
+use crate::codegen::{Syntax, Target};
+use crate::optimize::{Level, Pass};
 use std::path::PathBuf;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -24,7 +27,178 @@ pub struct Opt {
     #[structopt(short = "n")]
     pub nasm: bool,
 
+    /// Affiche le graphe de flot de contrôle au format dot
+    #[structopt(long = "dump-cfg")]
+    pub dump_cfg: bool,
+
+    /// Affiche le programme traduit en C, en LLVM IR ou en WebAssembly textuel
+    #[structopt(long = "emit", raw(possible_values = r#"&["c", "llvm", "wat"]"#))]
+    pub emit: Option<Emit>,
+
+    /// Exécute le code trois adresses
+    #[structopt(short = "r", long = "run")]
+    pub run: bool,
+
+    /// Affiche les avertissements, les transforme en erreurs ou les masque
+    #[structopt(
+        short = "W",
+        default_value = "all",
+        raw(global = "true", possible_values = r#"&["all", "error", "none"]"#)
+    )]
+    pub warnings: Warnings,
+
+    /// Architecture cible du code nasm
+    #[structopt(
+        long = "target",
+        default_value = "x86",
+        raw(global = "true", possible_values = r#"&["x86", "x86_64"]"#)
+    )]
+    pub target: Target,
+
+    /// Syntaxe du code assembleur : intel pour nasm, att pour l'assembleur GNU
+    #[structopt(
+        long = "asm-syntax",
+        default_value = "intel",
+        raw(global = "true", possible_values = r#"&["intel", "att"]"#)
+    )]
+    pub syntax: Syntax,
+
+    /// Niveau d'optimisation du code trois adresses
+    #[structopt(
+        short = "O",
+        default_value = "0",
+        raw(global = "true", possible_values = r#"&["0", "1", "2"]"#)
+    )]
+    pub level: Level,
+
+    /// Active une passe d'optimisation
+    #[structopt(
+        long = "enable",
+        value_name = "pass",
+        raw(
+            global = "true",
+            possible_values = "&Pass::NAMES",
+            number_of_values = "1"
+        )
+    )]
+    pub enable: Vec<Pass>,
+
+    /// Désactive une passe d'optimisation
+    #[structopt(
+        long = "disable",
+        value_name = "pass",
+        raw(
+            global = "true",
+            possible_values = "&Pass::NAMES",
+            number_of_values = "1"
+        )
+    )]
+    pub disable: Vec<Pass>,
+
     /// Le fichier l source
     #[structopt(parse(from_os_str))]
-    pub source_file: PathBuf,
+    pub source_file: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Produit un exécutable avec nasm et ld, as et ld avec --asm-syntax att, ou directement
+    /// avec --elf
+    #[structopt(name = "build")]
+    Build {
+        /// Le fichier l source
+        #[structopt(parse(from_os_str))]
+        source_file: PathBuf,
+
+        /// L'exécutable produit, le nom du fichier source sans extension par défaut
+        #[structopt(short = "o", parse(from_os_str))]
+        output: Option<PathBuf>,
+
+        /// Garde le code assembleur et le fichier objet intermédiaires
+        #[structopt(long = "keep-temps")]
+        keep_temps: bool,
+
+        /// Écrit directement l'exécutable, sans nasm ni ld
+        #[structopt(long = "elf", conflicts_with = "keep_temps")]
+        elf: bool,
+    },
+
+    /// Exécute le programme compilé en code machine x86_64, sans écrire de fichier
+    #[structopt(name = "jit")]
+    Jit {
+        /// Le fichier l source
+        #[structopt(parse(from_os_str))]
+        source_file: PathBuf,
+    },
+
+    /// Compile en bytecode .lbc pour la machine virtuelle
+    #[structopt(name = "bytecode")]
+    Bytecode {
+        /// Le fichier l source
+        #[structopt(parse(from_os_str))]
+        source_file: PathBuf,
+
+        /// Le fichier de bytecode produit, le fichier source avec l'extension lbc par défaut
+        #[structopt(short = "o", parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
+    /// Exécute un fichier de bytecode .lbc avec la machine virtuelle
+    #[structopt(name = "vm")]
+    Vm {
+        /// Le fichier de bytecode
+        #[structopt(parse(from_os_str))]
+        bytecode_file: PathBuf,
+    },
+
+    /// Affiche un fichier de bytecode .lbc sous forme lisible
+    #[structopt(name = "disasm")]
+    Disasm {
+        /// Le fichier de bytecode
+        #[structopt(parse(from_os_str))]
+        bytecode_file: PathBuf,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Emit {
+    C,
+    Llvm,
+    Wat,
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "c" => Ok(Emit::C),
+            "llvm" => Ok(Emit::Llvm),
+            "wat" => Ok(Emit::Wat),
+            _ => Err(format!("invalid language: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Warnings {
+    All,
+    Error,
+    None,
+}
+
+impl FromStr for Warnings {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Warnings::All),
+            "error" => Ok(Warnings::Error),
+            "none" => Ok(Warnings::None),
+            _ => Err(format!("invalid warnings level: {}", s)),
+        }
+    }
 }
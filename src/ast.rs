@@ -1,12 +1,22 @@
 use std::fmt;
 
+/// Position of a node in the source, in bytes from the start of the file.
+pub type Span = codespan::ByteSpan;
+
 #[derive(Debug)]
 pub struct Program(pub Vec<Statement>);
 
+/// The span of a function declaration only covers its signature.
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
 #[derive(Debug, Clone)]
-pub enum Statement {
+pub enum StatementKind {
     DclVariable(Variable),
-    DclFunction(Id, Vec<Scalar>, Vec<Scalar>, Instructions),
+    DclFunction(Id, Vec<Argument>, Vec<Variable>, Instructions),
 }
 
 #[derive(Debug, Clone)]
@@ -15,12 +25,21 @@ pub enum Variable {
     Vector(Vector),
 }
 
-pub type Scalar = (Type, Id);
-pub type Vector = (Type, u32, Id);
+/// A vector argument is given the vector of the caller, whose size is unknown.
+#[derive(Debug, Clone)]
+pub enum Argument {
+    Scalar(Scalar),
+    Vector(Scalar),
+}
 
-#[derive(Debug, Copy, Clone)]
+pub type Scalar = (Type, Id, Span);
+pub type Vector = (Type, u32, Id, Span);
+
+/// A boolean is stored as an integer, 1 for true and 0 for false.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Type {
     Integer,
+    Boolean,
 }
 
 impl Type {
@@ -29,18 +48,37 @@ impl Type {
         use Type::*;
 
         match self {
-            Integer => size_of::<Number>(),
+            Integer | Boolean => size_of::<Number>(),
         }
     }
 }
 
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Type::*;
+
+        let t = match self {
+            Integer => "entier",
+            Boolean => "booleen",
+        };
+
+        write!(f, "{}", t)
+    }
+}
+
 pub type Id = String;
 pub type Number = i32;
 
 pub type Instructions = Vec<Instruction>;
 
 #[derive(Debug, Clone)]
-pub enum Instruction {
+pub struct Instruction {
+    pub kind: InstructionKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum InstructionKind {
     Affectation(LeftValue, Expression),
     CallFunction(CallFunction),
     Return(Expression),
@@ -54,7 +92,13 @@ pub enum Instruction {
 pub type Expressions = Vec<Expression>;
 
 #[derive(Debug, Clone)]
-pub enum Expression {
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExpressionKind {
     Value(Number),
     LeftValue(LeftValue),
     CallFunction(CallFunction),
@@ -65,9 +109,9 @@ pub enum Expression {
 
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use Expression::*;
+        use ExpressionKind::*;
 
-        match self {
+        match &self.kind {
             Value(n) => write!(f, "{}", n),
             LeftValue(lv) => write!(f, "{}", lv),
             CallFunction(cf) => write!(f, "{}", cf),
@@ -79,16 +123,31 @@ impl fmt::Display for Expression {
 }
 
 #[derive(Debug, Clone)]
-pub enum LeftValue {
+pub struct LeftValue {
+    pub kind: LeftValueKind,
+    pub span: Span,
+}
+
+impl LeftValue {
+    pub fn id(&self) -> &Id {
+        match &self.kind {
+            LeftValueKind::Variable(id) => id,
+            LeftValueKind::VariableAt(id, _) => id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LeftValueKind {
     Variable(Id),
     VariableAt(Id, Box<Expression>),
 }
 
 impl fmt::Display for LeftValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use LeftValue::*;
+        use LeftValueKind::*;
 
-        match self {
+        match &self.kind {
             Variable(id) => write!(f, "{}", id),
             VariableAt(id, indice) => write!(f, "{}[{}]", id, indice),
         }
@@ -96,7 +155,7 @@ impl fmt::Display for LeftValue {
 }
 
 #[derive(Debug, Clone)]
-pub struct CallFunction(pub Id, pub Expressions);
+pub struct CallFunction(pub Id, pub Expressions, pub Span);
 
 impl fmt::Display for CallFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -0,0 +1,333 @@
+use super::Instruction as Op;
+use super::{Bytecode, Function, Global};
+use crate::symbol_table::{Scope, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use std::collections::HashMap;
+
+/// Compiles the three address code to bytecode, a function for each function of the code.
+pub trait GenBytecode {
+    fn gen_bytecode(&self, symbol_table: &SymbolTable) -> Bytecode;
+}
+
+impl GenBytecode for ThreeAddressCode {
+    fn gen_bytecode(&self, symbol_table: &SymbolTable) -> Bytecode {
+        let instructions = &self.instructions;
+
+        let mut globals = Vec::new();
+        let mut functions = HashMap::new();
+        let mut begins = Vec::new();
+        for (i, instr) in instructions.iter().enumerate() {
+            match &instr.kind {
+                InstructionKind::Allocation {
+                    variable: Some(variable),
+                    size,
+                } if variable.scope() == Scope::Global => {
+                    let symbol = symbol_table
+                        .global_symbol(variable.id())
+                        .expect("globals are declared");
+                    let size = match symbol.kind {
+                        SymbolKind::Vector { .. } => size.constant() as u32,
+                        _ => 0,
+                    };
+                    globals.push(Global {
+                        name: variable.id()[1..].to_owned(),
+                        size,
+                    });
+                }
+                InstructionKind::FunctionBegin => {
+                    let label = instr
+                        .label
+                        .as_ref()
+                        .expect("a function begins with its label");
+                    functions.insert(label.label().as_str(), begins.len() as u32);
+                    begins.push(i);
+                }
+                _ => {}
+            }
+        }
+
+        let mut generator = Generator {
+            symbol_table,
+            globals: globals
+                .iter()
+                .enumerate()
+                .map(|(i, global)| (format!("v{}", global.name), i as u32))
+                .collect(),
+            functions,
+            slots: HashMap::new(),
+            sizes: HashMap::new(),
+            retour: 0,
+            temps: HashMap::new(),
+            code: Vec::new(),
+            labels: HashMap::new(),
+            jumps: Vec::new(),
+        };
+
+        let functions = begins
+            .iter()
+            .enumerate()
+            .map(|(n, begin)| {
+                let end = begins.get(n + 1).cloned().unwrap_or(instructions.len());
+                generator.function(&instructions[*begin..end])
+            })
+            .collect();
+
+        Bytecode {
+            main: generator.functions["fmain"],
+            globals,
+            functions,
+        }
+    }
+}
+
+struct Generator<'a> {
+    symbol_table: &'a SymbolTable,
+    globals: HashMap<String, u32>,
+    functions: HashMap<&'a str, u32>,
+    /// Slots of the arguments and of the local variables of the current function, followed
+    /// by the slot of the value returned then by the temporaries. A local vector takes a slot
+    /// for each element from its first one.
+    slots: HashMap<String, u32>,
+    /// Number of slots of the local variables.
+    sizes: HashMap<String, u32>,
+    retour: u32,
+    temps: HashMap<u32, u32>,
+    code: Vec<Op>,
+    labels: HashMap<&'a str, u32>,
+    /// Jumps to patch with the index of their label.
+    jumps: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Generator<'a> {
+    fn function(&mut self, instructions: &'a [Instruction]) -> Function {
+        let label = instructions[0]
+            .label
+            .as_ref()
+            .expect("a function begins with its label");
+        let arguments = self.arguments(label);
+
+        self.slots = arguments
+            .iter()
+            .enumerate()
+            .map(|(i, argument)| (format!("v{}", argument), i as u32))
+            .collect();
+        self.sizes = HashMap::new();
+        let mut slot = arguments.len() as u32;
+        for instr in instructions {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                self.slots.insert(variable.id().to_owned(), slot);
+                self.sizes
+                    .insert(variable.id().to_owned(), size.constant() as u32);
+                slot += size.constant() as u32;
+            }
+        }
+        self.retour = slot;
+        self.temps = HashMap::new();
+        self.code = Vec::new();
+        self.labels = HashMap::new();
+        self.jumps = Vec::new();
+
+        for instr in &instructions[1..] {
+            if let Some(label) = &instr.label {
+                self.labels
+                    .insert(label.label().as_str(), self.code.len() as u32);
+            }
+
+            self.instruction(instr);
+        }
+
+        // The function returns at the end of its code, so that it never runs past it.
+        let end = self.code.len() as u32;
+        let returns = matches!(self.code.last(), Some(Op::Return) | Some(Op::Jump(_)));
+        if !returns || self.labels.values().any(|label| *label == end) {
+            self.code.push(Op::Load(self.retour));
+            self.code.push(Op::Return);
+        }
+
+        for (pc, label) in &self.jumps {
+            let target = self.labels[label];
+            match &mut self.code[*pc] {
+                Op::Jump(jump) | Op::JumpIf(_, jump) => *jump = target,
+                _ => unreachable!("only jumps are patched"),
+            }
+        }
+
+        Function {
+            name: label.label()[1..].to_owned(),
+            arguments: arguments.len() as u32,
+            slots: self.retour + 1 + self.temps.len() as u32,
+            code: std::mem::take(&mut self.code),
+        }
+    }
+
+    fn instruction(&mut self, instr: &'a Instruction) {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => {
+                self.load(left);
+                self.load(right);
+                self.code.push(match operator {
+                    ArithmeticOperator::Addition => Op::Add,
+                    ArithmeticOperator::Subtraction => Op::Sub,
+                    ArithmeticOperator::Multiplication => Op::Mul,
+                    ArithmeticOperator::Division => Op::Div,
+                });
+                self.store(result);
+            }
+            Affectation { value, result } => {
+                self.load(value);
+                self.store(result);
+            }
+            Allocation { .. } | NOP => {}
+            ReadFunction { result } => {
+                self.code.push(Op::Read);
+                self.store(result);
+            }
+            WriteFunction { value } => {
+                self.load(value);
+                self.code.push(Op::Write);
+            }
+            FunctionCall { function, result } => {
+                let callee = self.functions[function.label().as_str()];
+                self.code.push(Op::Call(callee));
+                self.store(result);
+            }
+            // The arguments stay on the stack until the call, which pops them.
+            FunctionPushArg { arg } => self.load(arg),
+            FunctionReturn { value } => {
+                self.load(value);
+                self.code.push(Op::Store(self.retour));
+            }
+            FunctionEnd => {
+                self.code.push(Op::Load(self.retour));
+                self.code.push(Op::Return);
+            }
+            FunctionBegin => unreachable!("a function begins only once"),
+            Jump { label } => {
+                self.jumps.push((self.code.len(), label.label().as_str()));
+                self.code.push(Op::Jump(0));
+            }
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                self.load(left);
+                self.load(right);
+                self.jumps.push((self.code.len(), label.label().as_str()));
+                self.code.push(Op::JumpIf(*condition, 0));
+            }
+        }
+    }
+
+    /// The slot of a temporary, the first use giving it the next free slot.
+    fn temp(&mut self, t: &Temp) -> u32 {
+        let slot = self.retour + 1 + self.temps.len() as u32;
+        *self.temps.entry(t.temp()).or_insert(slot)
+    }
+
+    fn index(&mut self, index: &CT) {
+        match index {
+            CT::C(c) => self.code.push(Op::Push(c.constant())),
+            CT::T(t) => {
+                let slot = self.temp(t);
+                self.code.push(Op::Load(slot));
+            }
+        }
+    }
+
+    /// Pushes a constant or the value of a temporary or a variable.
+    fn load(&mut self, ctv: &CTV) {
+        let v = match ctv {
+            CTV::C(c) => return self.code.push(Op::Push(c.constant())),
+            CTV::T(t) => {
+                let slot = self.temp(t);
+                return self.code.push(Op::Load(slot));
+            }
+            CTV::V(v) => v,
+        };
+
+        match (v.scope(), v.indice()) {
+            (_, Some(index)) if v.access() == Access::Indirect => {
+                self.index(index);
+                self.code.push(Op::LoadIndirect(self.slots[v.id()]));
+            }
+            (Scope::Global, None) if v.access() == Access::AddressOf => {
+                self.code.push(Op::AddressGlobal(self.globals[v.id()]))
+            }
+            (Scope::Local, None) if v.access() == Access::AddressOf => {
+                let (slot, size) = (self.slots[v.id()], self.sizes[v.id()]);
+                self.code.push(Op::AddressLocal(slot, size));
+            }
+            (Scope::Global, Some(index)) => {
+                self.index(index);
+                self.code.push(Op::LoadElement(self.globals[v.id()]));
+            }
+            (Scope::Global, None) => self.code.push(Op::LoadGlobal(self.globals[v.id()])),
+            (_, Some(index)) => {
+                self.index(index);
+                let (slot, size) = (self.slots[v.id()], self.sizes[v.id()]);
+                self.code.push(Op::LoadLocalElement(slot, size));
+            }
+            _ => self.code.push(Op::Load(self.slots[v.id()])),
+        }
+    }
+
+    /// Pops the value on the top of the stack into a temporary or a variable.
+    fn store(&mut self, result: &TV) {
+        let v = match result {
+            TV::T(t) => {
+                let slot = self.temp(t);
+                return self.code.push(Op::Store(slot));
+            }
+            TV::V(v) => v,
+        };
+
+        match (v.scope(), v.indice()) {
+            (_, Some(index)) if v.access() == Access::Indirect => {
+                self.index(index);
+                self.code.push(Op::StoreIndirect(self.slots[v.id()]));
+            }
+            (Scope::Global, Some(index)) => {
+                self.index(index);
+                self.code.push(Op::StoreElement(self.globals[v.id()]));
+            }
+            (Scope::Global, None) => self.code.push(Op::StoreGlobal(self.globals[v.id()])),
+            (_, Some(index)) => {
+                self.index(index);
+                let (slot, size) = (self.slots[v.id()], self.sizes[v.id()]);
+                self.code.push(Op::StoreLocalElement(slot, size));
+            }
+            _ => self.code.push(Op::Store(self.slots[v.id()])),
+        }
+    }
+
+    /// The identifiers of the arguments of a function in the order of their addresses.
+    fn arguments(&self, label: &Label) -> Vec<&'a str> {
+        let symbol_table = self.symbol_table;
+        let function = symbol_table
+            .function(label.label())
+            .expect("functions are declared");
+        let table = match function.kind {
+            SymbolKind::Function { symbol_table, .. } => symbol_table,
+            _ => unreachable!(),
+        };
+
+        let arguments = symbol_table.arguments(table);
+        arguments
+            .into_iter()
+            .map(|symbol| symbol.id.as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,565 @@
+//! A compact bytecode for a stack machine, saved in `.lbc` files.
+//!
+//! Every integer of a file is written in little endian and a name is its length in bytes on
+//! 32 bits followed by its UTF-8 bytes. A file holds:
+//!
+//! - the magic number `LBC` followed by the version, the byte 1;
+//! - the index of the main function on 32 bits;
+//! - the number of global variables on 32 bits, then for each its name and its size in
+//!   integers on 32 bits, 0 for a scalar;
+//! - the number of functions on 32 bits, then for each its name, its number of arguments, its
+//!   number of slots and its number of instructions on 32 bits, followed by its instructions.
+//!
+//! An instruction is an opcode on one byte, then its operand when it has one: a signed
+//! integer, a slot, a global variable, a function or the index of an instruction of the same
+//! function, all on 32 bits. The jumps on a condition have a second byte after the opcode, the
+//! condition from 0 to 5 in the order of `JumpIfCondition`. The elements of the local vectors
+//! have two operands on 32 bits, the first slot of the vector and its size.
+//!
+//! The slots of a frame hold the arguments first, then the local variables, a vector taking a
+//! slot for each element, and the temporaries, all set to 0 on a call. Instructions pop their
+//! operands from the stack of the frame and push their result on it, and a file is verified
+//! when read so that the stack never runs out.
+//!
+//! A vector argument holds a reference to the vector given by the caller: the index of the
+//! vector in a table of the machine, where the references taken by a function are forgotten
+//! when it returns.
+
+mod gen_bytecode;
+mod vm;
+
+#[cfg(test)]
+mod tests;
+
+pub use gen_bytecode::GenBytecode;
+
+use crate::three_address_code::JumpIfCondition;
+use std::io;
+use std::io::{Read, Write};
+
+#[derive(Debug, Fail)]
+pub enum BytecodeError {
+    #[fail(display = "not a bytecode file")]
+    Magic,
+    #[fail(display = "unsupported bytecode version {}", _0)]
+    Version(u8),
+    #[fail(display = "invalid opcode {:#04x}", _0)]
+    Opcode(u8),
+    #[fail(display = "invalid condition {}", _0)]
+    Condition(u8),
+    #[fail(display = "invalid name")]
+    Name,
+    #[fail(display = "invalid bytecode in function {}: {}", _0, _1)]
+    Invalid(String, &'static str),
+    #[fail(display = "{}", _0)]
+    Io(#[cause] io::Error),
+}
+
+impl From<io::Error> for BytecodeError {
+    fn from(error: io::Error) -> Self {
+        BytecodeError::Io(error)
+    }
+}
+
+const MAGIC: &[u8; 3] = b"LBC";
+const VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bytecode {
+    pub main: u32,
+    pub globals: Vec<Global>,
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Global {
+    pub name: String,
+    /// Number of integers of a vector, 0 for a scalar.
+    pub size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    pub name: String,
+    pub arguments: u32,
+    pub slots: u32,
+    pub code: Vec<Instruction>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    /// Pushes an integer.
+    Push(i32),
+    Load(u32),
+    Store(u32),
+    LoadGlobal(u32),
+    StoreGlobal(u32),
+    /// Pops an index and pushes the element of a vector.
+    LoadElement(u32),
+    /// Pops an index then a value and stores it in the element of a vector.
+    StoreElement(u32),
+    /// Pops an index and pushes the element of a local vector, from its first slot and size.
+    LoadLocalElement(u32, u32),
+    /// Pops an index then a value and stores it in the element of a local vector.
+    StoreLocalElement(u32, u32),
+    /// Pushes a reference to a global vector.
+    AddressGlobal(u32),
+    /// Pushes a reference to a local vector, from its first slot and size.
+    AddressLocal(u32, u32),
+    /// Pops an index and pushes the element of the vector referenced by a slot.
+    LoadIndirect(u32),
+    /// Pops an index then a value and stores it in the element of the vector referenced by a
+    /// slot.
+    StoreIndirect(u32),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Jump(u32),
+    /// Pops the right then the left operand and jumps if the condition holds.
+    JumpIf(JumpIfCondition, u32),
+    /// Pops the arguments, the last on the top, and pushes the value returned.
+    Call(u32),
+    Return,
+    Read,
+    Write,
+}
+
+const CONDITIONS: [JumpIfCondition; 6] = [
+    JumpIfCondition::Less,
+    JumpIfCondition::LessOrEqual,
+    JumpIfCondition::Equal,
+    JumpIfCondition::NotEqual,
+    JumpIfCondition::Greater,
+    JumpIfCondition::GreaterOrEqual,
+];
+
+impl Instruction {
+    pub fn mnemonic(&self) -> &'static str {
+        use Instruction::*;
+
+        match self {
+            Push(_) => "push",
+            Load(_) => "load",
+            Store(_) => "store",
+            LoadGlobal(_) => "load.global",
+            StoreGlobal(_) => "store.global",
+            LoadElement(_) => "load.element",
+            StoreElement(_) => "store.element",
+            LoadLocalElement(..) => "load.local.element",
+            StoreLocalElement(..) => "store.local.element",
+            AddressGlobal(_) => "address.global",
+            AddressLocal(..) => "address.local",
+            LoadIndirect(_) => "load.indirect",
+            StoreIndirect(_) => "store.indirect",
+            Add => "add",
+            Sub => "sub",
+            Mul => "mul",
+            Div => "div",
+            Jump(_) => "jump",
+            JumpIf(JumpIfCondition::Less, _) => "jump.lt",
+            JumpIf(JumpIfCondition::LessOrEqual, _) => "jump.le",
+            JumpIf(JumpIfCondition::Equal, _) => "jump.eq",
+            JumpIf(JumpIfCondition::NotEqual, _) => "jump.ne",
+            JumpIf(JumpIfCondition::Greater, _) => "jump.gt",
+            JumpIf(JumpIfCondition::GreaterOrEqual, _) => "jump.ge",
+            Call(_) => "call",
+            Return => "return",
+            Read => "read",
+            Write => "write",
+        }
+    }
+
+    fn opcode(&self) -> u8 {
+        use Instruction::*;
+
+        match self {
+            Push(_) => 0x00,
+            Load(_) => 0x01,
+            Store(_) => 0x02,
+            LoadGlobal(_) => 0x03,
+            StoreGlobal(_) => 0x04,
+            LoadElement(_) => 0x05,
+            StoreElement(_) => 0x06,
+            LoadLocalElement(..) => 0x07,
+            StoreLocalElement(..) => 0x08,
+            AddressGlobal(_) => 0x09,
+            AddressLocal(..) => 0x0a,
+            LoadIndirect(_) => 0x0b,
+            StoreIndirect(_) => 0x0c,
+            Add => 0x10,
+            Sub => 0x11,
+            Mul => 0x12,
+            Div => 0x13,
+            Jump(_) => 0x20,
+            JumpIf(..) => 0x21,
+            Call(_) => 0x30,
+            Return => 0x31,
+            Read => 0x40,
+            Write => 0x41,
+        }
+    }
+
+    /// The operand on 32 bits, if any, the first one of the instructions on local vectors.
+    fn operand(&self) -> Option<u32> {
+        use Instruction::*;
+
+        match *self {
+            Push(value) => Some(value as u32),
+            Load(operand)
+            | Store(operand)
+            | LoadGlobal(operand)
+            | StoreGlobal(operand)
+            | LoadElement(operand)
+            | StoreElement(operand)
+            | LoadLocalElement(operand, _)
+            | StoreLocalElement(operand, _)
+            | AddressGlobal(operand)
+            | AddressLocal(operand, _)
+            | LoadIndirect(operand)
+            | StoreIndirect(operand)
+            | Jump(operand)
+            | JumpIf(_, operand)
+            | Call(operand) => Some(operand),
+            Add | Sub | Mul | Div | Return | Read | Write => None,
+        }
+    }
+
+    fn write(&self, f: &mut dyn Write) -> io::Result<()> {
+        f.write_all(&[self.opcode()])?;
+
+        if let Instruction::JumpIf(condition, _) = self {
+            let condition = CONDITIONS.iter().position(|c| c == condition).unwrap();
+            f.write_all(&[condition as u8])?;
+        }
+
+        if let Some(operand) = self.operand() {
+            write_u32(f, operand)?;
+        }
+
+        match *self {
+            Instruction::LoadLocalElement(_, size)
+            | Instruction::StoreLocalElement(_, size)
+            | Instruction::AddressLocal(_, size) => write_u32(f, size),
+            _ => Ok(()),
+        }
+    }
+
+    fn read(f: &mut dyn Read) -> Result<Self, BytecodeError> {
+        use Instruction::*;
+
+        let opcode = read_u8(f)?;
+        Ok(match opcode {
+            0x00 => Push(read_u32(f)? as i32),
+            0x01 => Load(read_u32(f)?),
+            0x02 => Store(read_u32(f)?),
+            0x03 => LoadGlobal(read_u32(f)?),
+            0x04 => StoreGlobal(read_u32(f)?),
+            0x05 => LoadElement(read_u32(f)?),
+            0x06 => StoreElement(read_u32(f)?),
+            0x07 => LoadLocalElement(read_u32(f)?, read_u32(f)?),
+            0x08 => StoreLocalElement(read_u32(f)?, read_u32(f)?),
+            0x09 => AddressGlobal(read_u32(f)?),
+            0x0a => AddressLocal(read_u32(f)?, read_u32(f)?),
+            0x0b => LoadIndirect(read_u32(f)?),
+            0x0c => StoreIndirect(read_u32(f)?),
+            0x10 => Add,
+            0x11 => Sub,
+            0x12 => Mul,
+            0x13 => Div,
+            0x20 => Jump(read_u32(f)?),
+            0x21 => {
+                let condition = read_u8(f)?;
+                let condition = *CONDITIONS
+                    .get(condition as usize)
+                    .ok_or(BytecodeError::Condition(condition))?;
+                JumpIf(condition, read_u32(f)?)
+            }
+            0x30 => Call(read_u32(f)?),
+            0x31 => Return,
+            0x40 => Read,
+            0x41 => Write,
+            _ => return Err(BytecodeError::Opcode(opcode)),
+        })
+    }
+}
+
+impl Bytecode {
+    pub fn write(&self, f: &mut dyn Write) -> io::Result<()> {
+        f.write_all(MAGIC)?;
+        f.write_all(&[VERSION])?;
+        write_u32(f, self.main)?;
+
+        write_u32(f, self.globals.len() as u32)?;
+        for global in &self.globals {
+            write_name(f, &global.name)?;
+            write_u32(f, global.size)?;
+        }
+
+        write_u32(f, self.functions.len() as u32)?;
+        for function in &self.functions {
+            write_name(f, &function.name)?;
+            write_u32(f, function.arguments)?;
+            write_u32(f, function.slots)?;
+            write_u32(f, function.code.len() as u32)?;
+            for instruction in &function.code {
+                instruction.write(f)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads and verifies a file, which the machine can then run without checking the
+    /// references of the instructions nor the size of the stack.
+    pub fn read(f: &mut dyn Read) -> Result<Self, BytecodeError> {
+        let mut magic = [0; 3];
+        f.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(BytecodeError::Magic);
+        }
+        let version = read_u8(f)?;
+        if version != VERSION {
+            return Err(BytecodeError::Version(version));
+        }
+        let main = read_u32(f)?;
+
+        let mut globals = Vec::new();
+        for _ in 0..read_u32(f)? {
+            globals.push(Global {
+                name: read_name(f)?,
+                size: read_u32(f)?,
+            });
+        }
+
+        let mut functions = Vec::new();
+        for _ in 0..read_u32(f)? {
+            let name = read_name(f)?;
+            let arguments = read_u32(f)?;
+            let slots = read_u32(f)?;
+            let mut code = Vec::new();
+            for _ in 0..read_u32(f)? {
+                code.push(Instruction::read(f)?);
+            }
+
+            functions.push(Function {
+                name,
+                arguments,
+                slots,
+                code,
+            });
+        }
+
+        let bytecode = Self {
+            main,
+            globals,
+            functions,
+        };
+        bytecode.verify()?;
+
+        Ok(bytecode)
+    }
+
+    fn verify(&self) -> Result<(), BytecodeError> {
+        match self.functions.get(self.main as usize) {
+            Some(main) if main.arguments == 0 => {}
+            _ => {
+                return Err(BytecodeError::Invalid(
+                    String::new(),
+                    "invalid main function",
+                ))
+            }
+        }
+
+        for function in &self.functions {
+            self.verify_function(function)
+                .map_err(|error| BytecodeError::Invalid(function.name.clone(), error))?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks the references of the instructions then follows every path of the code: the
+    /// stack must have the same size whatever the path to an instruction, never run out and
+    /// the code must not run past its end.
+    fn verify_function(&self, function: &Function) -> Result<(), &'static str> {
+        use Instruction::*;
+
+        if function.arguments > function.slots {
+            return Err("more arguments than slots");
+        }
+
+        let scalar = |global| self.global(global).map(|global| global.size) == Some(0);
+        let vector = |global| self.global(global).is_some_and(|global| global.size != 0);
+        for instruction in &function.code {
+            match *instruction {
+                Load(slot) | Store(slot) | LoadIndirect(slot) | StoreIndirect(slot)
+                    if slot >= function.slots =>
+                {
+                    return Err("invalid slot")
+                }
+                LoadLocalElement(slot, size)
+                | StoreLocalElement(slot, size)
+                | AddressLocal(slot, size)
+                    if size == 0
+                        || u64::from(slot) + u64::from(size) > u64::from(function.slots) =>
+                {
+                    return Err("invalid local vector")
+                }
+                LoadGlobal(global) | StoreGlobal(global) if !scalar(global) => {
+                    return Err("invalid global scalar")
+                }
+                LoadElement(global) | StoreElement(global) | AddressGlobal(global)
+                    if !vector(global) =>
+                {
+                    return Err("invalid global vector")
+                }
+                Jump(target) | JumpIf(_, target) if target as usize >= function.code.len() => {
+                    return Err("invalid jump")
+                }
+                Call(callee) if callee as usize >= self.functions.len() => {
+                    return Err("invalid function")
+                }
+                _ => {}
+            }
+        }
+
+        let mut sizes = vec![None; function.code.len()];
+        let mut paths = vec![(0, 0)];
+
+        while let Some((pc, size)) = paths.pop() {
+            let instruction = function.code.get(pc).ok_or("the code runs past its end")?;
+            match sizes[pc] {
+                Some(known) if known == size => continue,
+                Some(_) => return Err("the size of the stack depends on the path"),
+                None => sizes[pc] = Some(size),
+            }
+
+            let (pops, pushes) = match *instruction {
+                Push(_) | Load(_) | LoadGlobal(_) | AddressGlobal(_) | AddressLocal(..) | Read => {
+                    (0, 1)
+                }
+                Store(_) | StoreGlobal(_) | Write | Return => (1, 0),
+                LoadElement(_) | LoadLocalElement(..) | LoadIndirect(_) => (1, 1),
+                StoreElement(_) | StoreLocalElement(..) | StoreIndirect(_) | JumpIf(..) => (2, 0),
+                Add | Sub | Mul | Div => (2, 1),
+                Jump(_) => (0, 0),
+                Call(callee) => (self.functions[callee as usize].arguments as usize, 1),
+            };
+
+            if size < pops {
+                return Err("the stack runs out");
+            }
+            let size = size - pops + pushes;
+
+            match *instruction {
+                Jump(target) => paths.push((target as usize, size)),
+                JumpIf(_, target) => {
+                    paths.push((target as usize, size));
+                    paths.push((pc + 1, size));
+                }
+                Return => {}
+                _ => paths.push((pc + 1, size)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn global(&self, global: u32) -> Option<&Global> {
+        self.globals.get(global as usize)
+    }
+
+    /// Writes the globals then the code of each function, an instruction per line with its
+    /// index.
+    pub fn disasm(&self, f: &mut dyn Write) -> io::Result<()> {
+        for (i, global) in self.globals.iter().enumerate() {
+            match global.size {
+                0 => writeln!(f, "global {} {}", i, global.name)?,
+                size => writeln!(f, "global {} {}[{}]", i, global.name, size)?,
+            }
+        }
+
+        for (i, function) in self.functions.iter().enumerate() {
+            if i != 0 || !self.globals.is_empty() {
+                writeln!(f)?;
+            }
+            write!(f, "function {} {}", i, function.name)?;
+            if i == self.main as usize {
+                write!(f, " (main)")?;
+            }
+            writeln!(
+                f,
+                ": {} arguments, {} slots",
+                function.arguments, function.slots
+            )?;
+
+            for (pc, instruction) in function.code.iter().enumerate() {
+                write!(f, "{:04}\t{}", pc, instruction.mnemonic())?;
+
+                match *instruction {
+                    Instruction::Push(value) => write!(f, "\t{}", value)?,
+                    Instruction::LoadGlobal(global)
+                    | Instruction::StoreGlobal(global)
+                    | Instruction::LoadElement(global)
+                    | Instruction::StoreElement(global)
+                    | Instruction::AddressGlobal(global) => {
+                        write!(f, "\t{}\t; {}", global, self.globals[global as usize].name)?
+                    }
+                    Instruction::LoadLocalElement(slot, size)
+                    | Instruction::StoreLocalElement(slot, size)
+                    | Instruction::AddressLocal(slot, size) => write!(f, "\t{}\t{}", slot, size)?,
+                    Instruction::Call(callee) => write!(
+                        f,
+                        "\t{}\t; {}",
+                        callee, self.functions[callee as usize].name
+                    )?,
+                    Instruction::Jump(target) | Instruction::JumpIf(_, target) => {
+                        write!(f, "\t{:04}", target)?
+                    }
+                    _ => {
+                        if let Some(operand) = instruction.operand() {
+                            write!(f, "\t{}", operand)?;
+                        }
+                    }
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_u8(f: &mut dyn Read) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    f.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32(f: &mut dyn Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    f.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_name(f: &mut dyn Read) -> Result<String, BytecodeError> {
+    let length = read_u32(f)?;
+    let mut bytes = Vec::new();
+    f.take(u64::from(length)).read_to_end(&mut bytes)?;
+    if bytes.len() != length as usize {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    String::from_utf8(bytes).map_err(|_| BytecodeError::Name)
+}
+
+fn write_u32(f: &mut dyn Write, value: u32) -> io::Result<()> {
+    f.write_all(&value.to_le_bytes())
+}
+
+fn write_name(f: &mut dyn Write, name: &str) -> io::Result<()> {
+    write_u32(f, name.len() as u32)?;
+    f.write_all(name.as_bytes())
+}
@@ -0,0 +1,343 @@
+use super::{Bytecode, BytecodeError, GenBytecode, Instruction};
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::run_three_address_code::{RunThreeAddressCode, RuntimeError};
+use crate::semantic_analyser::Analyse;
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+
+#[test]
+fn affect() {
+    test("affect");
+}
+
+#[test]
+fn boolean_booleen() {
+    test("boolean-booleen");
+}
+
+#[test]
+fn boucle() {
+    test("boucle");
+}
+
+#[test]
+fn early_exit_and_booleen() {
+    test("early-exit-and-booleen");
+}
+
+#[test]
+fn early_exit_or_booleen() {
+    test("early-exit-or-booleen");
+}
+
+#[test]
+fn ecrire() {
+    test("ecrire");
+}
+
+#[test]
+fn expression() {
+    test("expression");
+}
+
+#[test]
+fn function_in_function_args() {
+    test("function-in-function-args");
+}
+
+#[test]
+fn global() {
+    test("global");
+}
+
+#[test]
+fn lire() {
+    test("lire");
+}
+
+#[test]
+fn local_shadowing() {
+    test("local-shadowing");
+}
+
+#[test]
+fn lire_invalide() {
+    test("lire-invalide");
+}
+
+#[test]
+fn local() {
+    test("local");
+}
+
+#[test]
+fn max() {
+    test("max");
+}
+
+#[test]
+fn operation() {
+    test("operation");
+}
+
+#[test]
+fn param_shadowing() {
+    test("param-shadowing");
+}
+
+#[test]
+fn param() {
+    test("param");
+}
+
+#[test]
+fn recursion_mutuelle() {
+    test("recursion-mutuelle");
+}
+
+#[test]
+fn return_middle() {
+    test("return-middle");
+}
+
+#[test]
+fn return_() {
+    test("return");
+}
+
+#[test]
+fn surcharge() {
+    test("surcharge");
+}
+
+#[test]
+fn si_false() {
+    test("si-false");
+}
+
+#[test]
+fn si_sinon_false() {
+    test("si-sinon-false");
+}
+
+#[test]
+fn si_sinon_true() {
+    test("si-sinon-true");
+}
+
+#[test]
+fn si_true() {
+    test("si-true");
+}
+
+#[test]
+fn tableau_acces_complexe() {
+    test("tableau-acces-complexe");
+}
+
+#[test]
+fn tableau_acces() {
+    test("tableau-acces");
+}
+
+#[test]
+fn tableau_local() {
+    test("tableau-local");
+}
+
+#[test]
+fn tri_reference() {
+    test("tri-reference");
+}
+
+#[test]
+fn tantque() {
+    test("tantque");
+}
+
+#[test]
+fn tri() {
+    test("tri");
+}
+
+#[test]
+fn division_by_zero() {
+    match run("tests/resources/division-by-zero.l", "") {
+        Err(RuntimeError::DivisionByZero) => {}
+        _ => panic!("expected a division by zero"),
+    }
+}
+
+#[test]
+fn index_out_of_bounds() {
+    match run("tests/resources/index-out-of-bounds.l", "") {
+        Err(RuntimeError::IndexOutOfBounds(..)) => {}
+        _ => panic!("expected an index out of bounds"),
+    }
+}
+
+#[test]
+fn local_index_out_of_bounds() {
+    match run("tests/resources/local-index-out-of-bounds.l", "") {
+        Err(RuntimeError::IndexOutOfBounds(_, 10)) => {}
+        _ => panic!("expected an index out of bounds"),
+    }
+}
+
+#[test]
+fn disasm_appel() {
+    disasm("appel");
+}
+
+#[test]
+fn disasm_tri() {
+    disasm("tri");
+}
+
+#[test]
+fn magic() {
+    match Bytecode::read(&mut &b"ELF\x01"[..]) {
+        Err(BytecodeError::Magic) => {}
+        _ => panic!("expected an invalid magic number"),
+    }
+}
+
+#[test]
+fn truncated() {
+    let mut bytes = Vec::new();
+    compile("testor/tri.l").write(&mut bytes).unwrap();
+    bytes.pop();
+
+    match Bytecode::read(&mut &bytes[..]) {
+        Err(BytecodeError::Io(_)) => {}
+        _ => panic!("expected the end of the file"),
+    }
+}
+
+#[test]
+fn stack_runs_out() {
+    let mut bytecode = compile("testor/tri.l");
+    let main = bytecode.main as usize;
+    bytecode.functions[main].code.insert(0, Instruction::Add);
+
+    match write_read(&bytecode) {
+        Err(BytecodeError::Invalid(function, _)) => assert_eq!("main", function),
+        _ => panic!("expected an invalid bytecode"),
+    }
+}
+
+#[test]
+fn invalid_jump() {
+    let mut bytecode = compile("testor/tri.l");
+    let main = bytecode.main as usize;
+    let end = bytecode.functions[main].code.len() as u32;
+    bytecode.functions[main]
+        .code
+        .insert(0, Instruction::Jump(end + 1));
+
+    match write_read(&bytecode) {
+        Err(BytecodeError::Invalid(..)) => {}
+        _ => panic!("expected an invalid bytecode"),
+    }
+}
+
+/// The programs of the resources which pass the analysis behave as with the interpreter, but
+/// for the value of a call without retour.
+#[test]
+fn resources() {
+    let input = "3\n14\n-15\n92\n65\n";
+
+    for entry in read_dir("tests/resources").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some("l".as_ref()) {
+            continue;
+        }
+
+        let l_file = read_to_string(&path).unwrap();
+        let program = match Parser::new().parse(Lexer::new(&l_file)) {
+            Ok(program) => program,
+            Err(_) => continue,
+        };
+        let symbol_table = match program.analyse() {
+            Ok((symbol_table, _)) => symbol_table,
+            Err(_) => continue,
+        };
+
+        let mut expected = Vec::new();
+        let interpreted = program
+            .gen_three_address_code(&symbol_table, 0)
+            .run(&mut input.as_bytes(), &mut expected);
+        // A call without retour gives 0 instead of stopping the program.
+        if let Err(RuntimeError::MissingReturn(_)) = interpreted {
+            continue;
+        }
+
+        let filename = path.to_str().unwrap();
+        println!("{}", filename);
+        match run(filename, input) {
+            Ok(output) => assert_eq!(String::from_utf8(expected).unwrap(), output),
+            Err(_) => assert!(interpreted.is_err()),
+        }
+    }
+}
+
+fn test(filename: &str) {
+    let in_file = format!("testor/{}.in", filename);
+    let input = if Path::new(&in_file).is_file() {
+        read_to_string(in_file).unwrap()
+    } else {
+        String::new()
+    };
+    let out_file = read_to_string(format!("testor/{}.out", filename)).unwrap();
+
+    let output = run(&format!("testor/{}.l", filename), &input).unwrap();
+
+    print!("{}", output);
+
+    assert_eq!(out_file.trim_end(), output.trim_end());
+}
+
+fn disasm(filename: &str) {
+    let bytecode = compile(&format!("tests/resources/{}.l", filename));
+    let mut output = Vec::new();
+    bytecode.disasm(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    print!("{}", output);
+
+    let dis_file = read_to_string(format!("tests/resources/{}.dis", filename)).unwrap();
+    assert_eq!(dis_file, output);
+}
+
+fn compile(filename: &str) -> Bytecode {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .gen_bytecode(&symbol_table)
+}
+
+/// Writes then reads a bytecode, which must not change it when it is valid.
+fn write_read(bytecode: &Bytecode) -> Result<Bytecode, BytecodeError> {
+    let mut bytes = Vec::new();
+    bytecode.write(&mut bytes).unwrap();
+
+    let read = Bytecode::read(&mut &bytes[..])?;
+    assert_eq!(bytecode, &read);
+    Ok(read)
+}
+
+/// Compiles a program to bytecode and runs it once written and read back.
+fn run(filename: &str, input: &str) -> Result<String, RuntimeError> {
+    let bytecode = write_read(&compile(filename)).unwrap();
+
+    let mut output = Vec::new();
+    bytecode.run(&mut input.as_bytes(), &mut output)?;
+
+    Ok(String::from_utf8(output).unwrap())
+}
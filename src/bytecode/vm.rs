@@ -0,0 +1,226 @@
+use super::{Bytecode, Instruction};
+use crate::run_three_address_code::{read, RuntimeError};
+use crate::three_address_code::ArithmeticOperator;
+use std::io::{BufRead, Write};
+
+struct Frame {
+    function: usize,
+    pc: usize,
+    slots: Vec<i32>,
+    /// Size of the stack when the frame was created, after popping the arguments.
+    base: usize,
+    /// Number of references when the frame was created.
+    references: usize,
+}
+
+/// A vector given to a vector argument, which gets the index of the reference.
+#[derive(PartialEq)]
+enum Reference {
+    Global(u32),
+    Local { frame: usize, slot: u32, size: u32 },
+}
+
+impl Bytecode {
+    /// Runs a verified bytecode from its main function, `read` reading lines of `input` and
+    /// `write` writing to `output`.
+    pub fn run(&self, input: &mut dyn BufRead, output: &mut dyn Write) -> Result<(), RuntimeError> {
+        use Instruction::*;
+
+        let mut globals: Vec<Vec<i32>> = self
+            .globals
+            .iter()
+            .map(|global| vec![0; global.size.max(1) as usize])
+            .collect();
+        let mut stack: Vec<i32> = Vec::new();
+        let mut references: Vec<Reference> = Vec::new();
+        let mut frames = vec![self.frame(self.main as usize, &mut stack, 0)];
+
+        while let Some(frame) = frames.last_mut() {
+            let instruction = self.functions[frame.function].code[frame.pc];
+            frame.pc += 1;
+
+            match instruction {
+                Push(value) => stack.push(value),
+                Load(slot) => stack.push(frame.slots[slot as usize]),
+                Store(slot) => frame.slots[slot as usize] = pop(&mut stack),
+                LoadGlobal(global) => stack.push(globals[global as usize][0]),
+                StoreGlobal(global) => globals[global as usize][0] = pop(&mut stack),
+                LoadElement(global) => {
+                    let index = pop(&mut stack);
+                    let element = self.element(&mut globals, global, index)?;
+                    stack.push(*element);
+                }
+                StoreElement(global) => {
+                    let index = pop(&mut stack);
+                    let value = pop(&mut stack);
+                    *self.element(&mut globals, global, index)? = value;
+                }
+                LoadLocalElement(slot, size) => {
+                    let index = pop(&mut stack);
+                    let slot = self.local_element(frame, slot, size, index)?;
+                    stack.push(frame.slots[slot]);
+                }
+                StoreLocalElement(slot, size) => {
+                    let index = pop(&mut stack);
+                    let value = pop(&mut stack);
+                    let slot = self.local_element(frame, slot, size, index)?;
+                    frame.slots[slot] = value;
+                }
+                AddressGlobal(global) => {
+                    stack.push(reference(&mut references, Reference::Global(global)));
+                }
+                AddressLocal(slot, size) => {
+                    let frame = frames.len() - 1;
+                    let local = Reference::Local { frame, slot, size };
+                    stack.push(reference(&mut references, local));
+                }
+                LoadIndirect(slot) => {
+                    let index = pop(&mut stack);
+                    let reference = frame.slots[slot as usize];
+                    let element =
+                        self.referenced(&mut frames, &mut globals, &references, reference, index)?;
+                    stack.push(*element);
+                }
+                StoreIndirect(slot) => {
+                    let index = pop(&mut stack);
+                    let value = pop(&mut stack);
+                    let reference = frame.slots[slot as usize];
+                    *self.referenced(&mut frames, &mut globals, &references, reference, index)? =
+                        value;
+                }
+                Add | Sub | Mul | Div => {
+                    let right = pop(&mut stack);
+                    let left = pop(&mut stack);
+                    let operator = match instruction {
+                        Add => ArithmeticOperator::Addition,
+                        Sub => ArithmeticOperator::Subtraction,
+                        Mul => ArithmeticOperator::Multiplication,
+                        _ => ArithmeticOperator::Division,
+                    };
+                    let value = operator
+                        .compute(left, right)
+                        .ok_or(RuntimeError::DivisionByZero)?;
+                    stack.push(value);
+                }
+                Jump(target) => frame.pc = target as usize,
+                JumpIf(condition, target) => {
+                    let right = pop(&mut stack);
+                    let left = pop(&mut stack);
+                    if condition.holds(left, right) {
+                        frame.pc = target as usize;
+                    }
+                }
+                Call(callee) => {
+                    let frame = self.frame(callee as usize, &mut stack, references.len());
+                    frames.push(frame);
+                }
+                // The references taken by the frame are forgotten with it.
+                Return => {
+                    let value = pop(&mut stack);
+                    stack.truncate(frame.base);
+                    stack.push(value);
+                    references.truncate(frame.references);
+                    frames.pop();
+                }
+                Read => stack.push(read(input)?),
+                Write => writeln!(output, "{}", pop(&mut stack))?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A frame for a call, which pops the arguments from the stack.
+    fn frame(&self, function: usize, stack: &mut Vec<i32>, references: usize) -> Frame {
+        let arguments = self.functions[function].arguments as usize;
+        let base = stack.len() - arguments;
+
+        let mut slots: Vec<i32> = stack.drain(base..).collect();
+        slots.resize(self.functions[function].slots as usize, 0);
+
+        Frame {
+            function,
+            pc: 0,
+            slots,
+            base,
+            references,
+        }
+    }
+
+    fn element<'a>(
+        &self,
+        globals: &'a mut [Vec<i32>],
+        global: u32,
+        index: i32,
+    ) -> Result<&'a mut i32, RuntimeError> {
+        let out_of_bounds =
+            || RuntimeError::IndexOutOfBounds(self.globals[global as usize].name.clone(), index);
+
+        if index < 0 {
+            return Err(out_of_bounds());
+        }
+
+        globals[global as usize]
+            .get_mut(index as usize)
+            .ok_or_else(out_of_bounds)
+    }
+}
+
+impl Bytecode {
+    /// The slot of an element of a local vector. As the local variables have no name in the
+    /// bytecode, the vector is named after its first slot and its function.
+    fn local_element(
+        &self,
+        frame: &Frame,
+        slot: u32,
+        size: u32,
+        index: i32,
+    ) -> Result<usize, RuntimeError> {
+        if index < 0 || index as u32 >= size {
+            let name = format!("slot {} of {}", slot, self.functions[frame.function].name);
+            return Err(RuntimeError::IndexOutOfBounds(name, index));
+        }
+
+        Ok((slot + index as u32) as usize)
+    }
+}
+
+impl Bytecode {
+    /// The element of the vector referenced by a vector argument.
+    fn referenced<'a>(
+        &self,
+        frames: &'a mut [Frame],
+        globals: &'a mut [Vec<i32>],
+        references: &[Reference],
+        reference: i32,
+        index: i32,
+    ) -> Result<&'a mut i32, RuntimeError> {
+        match references.get(reference as usize) {
+            Some(Reference::Global(global)) => self.element(globals, *global, index),
+            Some(Reference::Local { frame, slot, size }) => {
+                let frame = &mut frames[*frame];
+                let slot = self.local_element(frame, *slot, *size, index)?;
+                Ok(&mut frame.slots[slot])
+            }
+            None => Err(RuntimeError::InvalidReference(reference)),
+        }
+    }
+}
+
+/// The index of a reference, the same vector of a frame keeping its index.
+fn reference(references: &mut Vec<Reference>, reference: Reference) -> i32 {
+    match references.iter().position(|r| *r == reference) {
+        Some(index) => index as i32,
+        None => {
+            references.push(reference);
+            references.len() as i32 - 1
+        }
+    }
+}
+
+/// Pops a value, which the verification of the bytecode guarantees.
+fn pop(stack: &mut Vec<i32>) -> i32 {
+    stack
+        .pop()
+        .expect("the stack of a verified bytecode never runs out")
+}
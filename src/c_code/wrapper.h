@@ -1,3 +0,0 @@
-#include "code3a.h"
-#include "c3a2nasm.h"
-#include "tabsymboles.h"
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests;
+
+use crate::three_address_code::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The functions of a three-address code split into basic blocks. Blocks refer to the
+/// instructions of the code they were built from by their indices.
+#[derive(Debug)]
+pub struct ControlFlowGraph {
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub label: Label,
+    /// The first block is the entry of the function.
+    pub blocks: Vec<BasicBlock>,
+    /// The immediate dominator of each block, `None` for the entry and unreachable blocks.
+    pub dominators: Vec<Option<usize>>,
+}
+
+#[derive(Debug)]
+pub struct BasicBlock {
+    pub instructions: Range<usize>,
+    pub successors: Vec<usize>,
+    pub predecessors: Vec<usize>,
+}
+
+impl ControlFlowGraph {
+    /// The global allocations before the first function are left out.
+    pub fn new(three_address_code: &ThreeAddressCode) -> Self {
+        let instructions = &three_address_code.instructions;
+
+        let mut begins: Vec<usize> = instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, instr)| match instr.kind {
+                InstructionKind::FunctionBegin => Some(i),
+                _ => None,
+            })
+            .collect();
+        begins.push(instructions.len());
+
+        let functions = begins
+            .windows(2)
+            .map(|range| Function::new(instructions, range[0]..range[1]))
+            .collect();
+
+        Self { functions }
+    }
+}
+
+impl Function {
+    fn new(instructions: &[Instruction], range: Range<usize>) -> Self {
+        let label = instructions[range.start]
+            .label
+            .clone()
+            .expect("a function begins with its label");
+
+        let mut leaders = vec![range.start];
+        for i in range.clone().skip(1) {
+            let follows_jump = matches!(
+                instructions[i - 1].kind,
+                InstructionKind::Jump { .. }
+                    | InstructionKind::JumpIf { .. }
+                    | InstructionKind::FunctionEnd
+            );
+
+            if follows_jump || instructions[i].label.is_some() {
+                leaders.push(i);
+            }
+        }
+        leaders.push(range.end);
+
+        let mut blocks: Vec<BasicBlock> = leaders
+            .windows(2)
+            .map(|range| BasicBlock {
+                instructions: range[0]..range[1],
+                successors: Vec::new(),
+                predecessors: Vec::new(),
+            })
+            .collect();
+
+        let labels: HashMap<&Label, usize> = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(b, block)| {
+                instructions[block.instructions.start]
+                    .label
+                    .as_ref()
+                    .map(|label| (label, b))
+            })
+            .collect();
+
+        for b in 0..blocks.len() {
+            let next = Some(b + 1).filter(|next| *next < blocks.len());
+
+            let successors = match &instructions[blocks[b].instructions.end - 1].kind {
+                InstructionKind::Jump { label } => vec![labels[label]],
+                InstructionKind::JumpIf { label, .. } => {
+                    next.into_iter().chain(Some(labels[label])).collect()
+                }
+                InstructionKind::FunctionEnd => Vec::new(),
+                _ => next.into_iter().collect(),
+            };
+
+            for successor in successors {
+                if !blocks[b].successors.contains(&successor) {
+                    blocks[b].successors.push(successor);
+                    blocks[successor].predecessors.push(b);
+                }
+            }
+        }
+
+        let dominators = dominators(&blocks);
+
+        Self {
+            label,
+            blocks,
+            dominators,
+        }
+    }
+}
+
+/// The blocks reachable from the entry, each one before its successors except along loops.
+fn reverse_postorder(blocks: &[BasicBlock]) -> Vec<usize> {
+    let mut visited = vec![false; blocks.len()];
+    let mut postorder = Vec::with_capacity(blocks.len());
+    let mut stack = vec![(0, 0)];
+    visited[0] = true;
+
+    while let Some((b, successor)) = stack.pop() {
+        match blocks[b].successors.get(successor) {
+            Some(&next) => {
+                stack.push((b, successor + 1));
+                if !visited[next] {
+                    visited[next] = true;
+                    stack.push((next, 0));
+                }
+            }
+            None => postorder.push(b),
+        }
+    }
+
+    postorder.reverse();
+    postorder
+}
+
+/// "A Simple, Fast Dominance Algorithm" by Cooper, Harvey and Kennedy.
+fn dominators(blocks: &[BasicBlock]) -> Vec<Option<usize>> {
+    let order = reverse_postorder(blocks);
+    let mut position = vec![usize::MAX; blocks.len()];
+    for (i, b) in order.iter().enumerate() {
+        position[*b] = i;
+    }
+
+    let mut dominators = vec![None; blocks.len()];
+    dominators[0] = Some(0);
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for &b in order.iter().skip(1) {
+            let mut processed = blocks[b]
+                .predecessors
+                .iter()
+                .filter(|p| dominators[**p].is_some());
+
+            let first = match processed.next() {
+                Some(first) => *first,
+                None => continue,
+            };
+            let dominator = processed.fold(first, |mut a, &p| {
+                let mut p = p;
+                while a != p {
+                    while position[a] > position[p] {
+                        a = dominators[a].unwrap();
+                    }
+                    while position[p] > position[a] {
+                        p = dominators[p].unwrap();
+                    }
+                }
+                a
+            });
+
+            if dominators[b] != Some(dominator) {
+                dominators[b] = Some(dominator);
+                changed = true;
+            }
+        }
+    }
+
+    dominators[0] = None;
+    dominators
+}
@@ -0,0 +1,117 @@
+use super::{reverse_postorder, ControlFlowGraph};
+use crate::format::dot::Dot;
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use crate::three_address_code::ThreeAddressCode;
+use std::fs::read_to_string;
+
+#[test]
+fn tantque() {
+    let cfg = ControlFlowGraph::new(&gen("testor/tantque.l"));
+    assert_eq!(cfg.functions.len(), 1);
+
+    let main = &cfg.functions[0];
+    assert_eq!(main.label.label(), "fmain");
+
+    let ranges: Vec<_> = main
+        .blocks
+        .iter()
+        .map(|block| (block.instructions.start, block.instructions.end))
+        .collect();
+    assert_eq!(ranges, [(1, 3), (3, 6), (6, 7), (7, 9), (9, 13), (13, 16)]);
+
+    let successors: Vec<_> = main
+        .blocks
+        .iter()
+        .map(|block| block.successors.clone())
+        .collect();
+    assert_eq!(
+        successors,
+        [vec![1], vec![2, 3], vec![3], vec![4, 5], vec![1], vec![]]
+    );
+    assert_eq!(main.blocks[1].predecessors, [0, 4]);
+
+    assert_eq!(
+        main.dominators,
+        [None, Some(0), Some(1), Some(1), Some(3), Some(3)]
+    );
+    assert_eq!(reverse_postorder(&main.blocks), [0, 1, 2, 3, 5, 4]);
+}
+
+#[test]
+fn diamond() {
+    let cfg = ControlFlowGraph::new(&gen("testor/si-sinon-true.l"));
+    let main = &cfg.functions[0];
+
+    let successors: Vec<_> = main
+        .blocks
+        .iter()
+        .map(|block| block.successors.clone())
+        .collect();
+    assert_eq!(
+        successors,
+        [vec![1, 2], vec![2], vec![3, 4], vec![5], vec![5], vec![]]
+    );
+    assert_eq!(main.blocks[5].predecessors, [3, 4]);
+
+    assert_eq!(
+        main.dominators,
+        [None, Some(0), Some(0), Some(2), Some(2), Some(2)]
+    );
+    assert_eq!(reverse_postorder(&main.blocks), [0, 1, 2, 4, 3, 5]);
+}
+
+#[test]
+fn return_middle() {
+    let cfg = ControlFlowGraph::new(&gen("testor/return-middle.l"));
+    let main = &cfg.functions[0];
+
+    assert_eq!(main.blocks.len(), 2);
+    assert!(main.blocks[0].successors.is_empty());
+    assert!(main.blocks[1].predecessors.is_empty());
+    assert_eq!(main.dominators, [None, None]);
+    assert_eq!(reverse_postorder(&main.blocks), [0]);
+}
+
+#[test]
+fn functions() {
+    let cfg = ControlFlowGraph::new(&gen("testor/tri.l"));
+
+    let labels: Vec<_> = cfg
+        .functions
+        .iter()
+        .map(|function| function.label.label().as_str())
+        .collect();
+    assert_eq!(
+        labels,
+        ["finitialiser", "fafficher", "fechanger", "ftrier", "fmain"]
+    );
+}
+
+#[test]
+fn dot() {
+    let three_a = gen("testor/si-sinon-true.l");
+    let mut dot = Vec::new();
+    ControlFlowGraph::new(&three_a)
+        .dot(&three_a, &mut dot)
+        .unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+
+    print!("{}", dot);
+
+    assert!(dot.starts_with("digraph cfg {"));
+    assert!(dot.contains("subgraph cluster_fmain {"));
+    assert!(dot.contains("fmain_0 -> fmain_1;"));
+    assert!(dot.contains("fmain_0 -> fmain_1 [style=dashed, color=gray];"));
+}
+
+fn gen(filename: &str) -> ThreeAddressCode {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program.gen_three_address_code(&symbol_table, 0)
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests;
+
+use crate::liveness::Liveness;
+use crate::three_address_code::*;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Where a temporary is kept during its whole function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// The index of one of the allocatable registers.
+    Register(usize),
+    /// The index of a stack slot of the function.
+    Slot(usize),
+}
+
+/// Registers and stack slots of the temporaries, found by a linear scan over their live
+/// intervals in each function.
+#[derive(Debug)]
+pub struct Allocation {
+    pub locations: HashMap<u32, Location>,
+    /// Number of stack slots needed by each function, by label.
+    pub slots: HashMap<String, usize>,
+}
+
+/// The positions covered by a temporary. Instruction `i` reads its operands at `2 * i` and
+/// writes its result at `2 * i + 1`, so a result may take the register of an operand.
+#[derive(Debug)]
+struct Interval {
+    temp: Temp,
+    start: usize,
+    end: usize,
+}
+
+impl Allocation {
+    pub fn new(
+        three_address_code: &ThreeAddressCode,
+        liveness: &Liveness,
+        registers: usize,
+    ) -> Self {
+        let instructions = &three_address_code.instructions;
+        let mut allocation = Self {
+            locations: HashMap::new(),
+            slots: HashMap::new(),
+        };
+
+        let begins: Vec<usize> = instructions
+            .iter()
+            .enumerate()
+            .filter(|(_, instr)| matches!(instr.kind, InstructionKind::FunctionBegin))
+            .map(|(i, _)| i)
+            .collect();
+
+        for (n, begin) in begins.iter().enumerate() {
+            let end = begins.get(n + 1).cloned().unwrap_or(instructions.len());
+            let label = instructions[*begin]
+                .label
+                .as_ref()
+                .expect("a function begins with its label");
+
+            let intervals = intervals(instructions, liveness, *begin..end);
+            let slots = allocation.scan(intervals, registers);
+            allocation.slots.insert(label.label().clone(), slots);
+        }
+
+        allocation
+    }
+
+    pub fn location(&self, t: &Temp) -> Location {
+        *self
+            .locations
+            .get(&t.temp())
+            .unwrap_or_else(|| panic!("unallocated temporary t{}", t.temp()))
+    }
+
+    /// Gives a register or a stack slot to each interval and returns the number of slots used.
+    /// When all registers are taken, the interval ending last goes to the stack.
+    fn scan(&mut self, mut intervals: Vec<Interval>, registers: usize) -> usize {
+        intervals.sort_by_key(|interval| (interval.start, interval.temp.temp()));
+
+        let mut active: Vec<(Interval, usize)> = Vec::new();
+        let mut free = vec![true; registers];
+        // The end of the last interval kept in each slot.
+        let mut slots: Vec<usize> = Vec::new();
+
+        for interval in intervals {
+            active.retain(|(active, register)| {
+                if active.end < interval.start {
+                    free[*register] = true;
+                }
+                active.end >= interval.start
+            });
+
+            if let Some(register) = free.iter().position(|free| *free) {
+                free[register] = false;
+                self.locations
+                    .insert(interval.temp.temp(), Location::Register(register));
+                active.push((interval, register));
+                continue;
+            }
+
+            let last = (0..active.len())
+                .max_by_key(|i| active[*i].0.end)
+                .filter(|i| active[*i].0.end > interval.end);
+
+            let spilled = match last {
+                Some(i) => {
+                    let (spilled, register) = active.swap_remove(i);
+                    self.locations
+                        .insert(interval.temp.temp(), Location::Register(register));
+                    active.push((interval, register));
+                    spilled
+                }
+                None => interval,
+            };
+
+            let slot = match slots.iter().position(|end| *end < spilled.start) {
+                Some(slot) => slot,
+                None => {
+                    slots.push(0);
+                    slots.len() - 1
+                }
+            };
+            slots[slot] = spilled.end;
+            self.locations
+                .insert(spilled.temp.temp(), Location::Slot(slot));
+        }
+
+        slots.len()
+    }
+}
+
+/// The live intervals of the temporaries of the instructions in `range`.
+fn intervals(
+    instructions: &[Instruction],
+    liveness: &Liveness,
+    range: Range<usize>,
+) -> Vec<Interval> {
+    let mut bounds: HashMap<Temp, (usize, usize)> = HashMap::new();
+    let mut extend = |t: &Temp, position: usize| {
+        let bound = bounds.entry(t.clone()).or_insert((position, position));
+        bound.0 = bound.0.min(position);
+        bound.1 = bound.1.max(position);
+    };
+
+    for i in range {
+        for t in instructions[i]
+            .uses()
+            .into_iter()
+            .chain(&liveness.live_in[i])
+        {
+            extend(t, 2 * i);
+        }
+        if let Some(TV::T(t)) = instructions[i].result() {
+            extend(t, 2 * i + 1);
+        }
+        for t in &liveness.live_out[i] {
+            extend(t, 2 * i + 1);
+        }
+    }
+
+    bounds
+        .into_iter()
+        .map(|(temp, (start, end))| Interval { temp, start, end })
+        .collect()
+}
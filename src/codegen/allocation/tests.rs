@@ -0,0 +1,55 @@
+use super::{Allocation, Location};
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::liveness::Liveness;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use crate::three_address_code::ThreeAddressCode;
+use std::collections::HashSet;
+use std::fs::read_to_string;
+
+#[test]
+fn registers() {
+    let three_a = gen("testor/tantque.l");
+    let liveness = Liveness::new(&three_a);
+    let allocation = Allocation::new(&three_a, &liveness, 4);
+
+    assert_eq!(allocation.slots["fmain"], 0);
+    assert!(allocation
+        .locations
+        .values()
+        .all(|location| *location == Location::Register(0)));
+}
+
+/// Six call results are live at once in the first sum.
+#[test]
+fn spill() {
+    let three_a = gen("tests/resources/spill.l");
+    let liveness = Liveness::new(&three_a);
+    let allocation = Allocation::new(&three_a, &liveness, 4);
+
+    assert_eq!(allocation.slots["ff"], 0);
+    assert!(allocation.slots["fmain"] > 0);
+
+    // Temporaries live at the same time never share a location.
+    for live in liveness.live_in.iter().chain(&liveness.live_out) {
+        let locations: HashSet<Location> = live.iter().map(|t| allocation.location(t)).collect();
+        assert_eq!(locations.len(), live.len());
+    }
+
+    for location in allocation.locations.values() {
+        match location {
+            Location::Register(register) => assert!(*register < 4),
+            Location::Slot(slot) => assert!(*slot < allocation.slots["fmain"]),
+        }
+    }
+}
+
+fn gen(filename: &str) -> ThreeAddressCode {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program.gen_three_address_code(&symbol_table, 0)
+}
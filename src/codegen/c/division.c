@@ -0,0 +1,13 @@
+/* La division par zéro arrête le programme avec l'erreur de l'interpréteur, -2147483648 / -1
+ * donne -2147483648. */
+static int division(int gauche, int droite)
+{
+	if (droite == 0) {
+		fputs("Error: division by zero\n", stderr);
+		exit(1);
+	}
+	if (droite == -1)
+		return (int)(0u - (unsigned)gauche);
+
+	return gauche / droite;
+}
@@ -0,0 +1,4 @@
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
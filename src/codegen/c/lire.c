@@ -0,0 +1,25 @@
+/* Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+ * blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+ * une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32. */
+static int lire(void)
+{
+	char ligne[255] = "";
+	const char *c = ligne;
+	unsigned valeur = 0;
+	int negatif = 0;
+
+	if (scanf("%254[^\n]", ligne) != 1)
+		ligne[0] = '\0';
+	/* La fin d'une ligne trop longue est sautée, puis le saut de ligne. */
+	if (scanf("%*[^\n]") != EOF)
+		getchar();
+
+	while (*c == ' ')
+		c++;
+	if (*c == '-' || *c == '+')
+		negatif = *c++ == '-';
+	while (*c >= '0' && *c <= '9')
+		valeur = valeur * 10 + (unsigned)(*c++ - '0');
+
+	return (int)(negatif ? 0u - valeur : valeur);
+}
@@ -0,0 +1,485 @@
+#[cfg(test)]
+mod tests;
+
+use crate::symbol_table::{Scope, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::Write;
+
+/// Translates to a C99 file which needs nothing but the standard library.
+pub trait C {
+    fn to_c(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()>;
+}
+
+impl C for ThreeAddressCode {
+    fn to_c(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()> {
+        Generator::new(symbol_table, f).generate(self)
+    }
+}
+
+/// `lire`, `ecrire` and the division, which behave as the nasm runtime. Only those the program
+/// uses are written, as C compilers warn about unused static functions.
+const LIRE: &str = include_str!("lire.c");
+const ECRIRE: &str = include_str!("ecrire.c");
+const DIVISION: &str = include_str!("division.c");
+
+struct Generator<'a> {
+    f: &'a mut dyn Write,
+    symbol_table: &'a SymbolTable,
+    /// Arguments of the calls being prepared, the innermost last.
+    calls: Vec<Vec<String>>,
+    /// Number of arguments copied in the current function.
+    arguments: usize,
+    /// Temporaries read in the current function, the others are not declared.
+    read: BTreeSet<u32>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbol_table: &'a SymbolTable, f: &'a mut dyn Write) -> Self {
+        Self {
+            f,
+            symbol_table,
+            calls: Vec::new(),
+            arguments: 0,
+            read: BTreeSet::new(),
+        }
+    }
+
+    fn generate(&mut self, three_address_code: &ThreeAddressCode) -> io::Result<()> {
+        let instructions = &three_address_code.instructions;
+
+        writeln!(self.f, "#include <stdio.h>")?;
+        writeln!(self.f, "#include <stdlib.h>")?;
+
+        let globals = instructions
+            .iter()
+            .take_while(|instr| matches!(instr.kind, InstructionKind::Allocation { .. }))
+            .count();
+
+        if globals != 0 {
+            writeln!(self.f)?;
+        }
+        for instr in &instructions[..globals] {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                match self.global(variable.id()) {
+                    SymbolKind::Vector { .. } => writeln!(
+                        self.f,
+                        "static int {}[{}];",
+                        name('g', variable.id()),
+                        size.constant()
+                    )?,
+                    _ => writeln!(self.f, "static int {};", name('g', variable.id()))?,
+                }
+            }
+        }
+
+        let functions: Vec<usize> = (globals..instructions.len())
+            .filter(|i| matches!(instructions[*i].kind, InstructionKind::FunctionBegin))
+            .collect();
+
+        writeln!(self.f)?;
+        for begin in &functions {
+            let signature = self.signature(&instructions[*begin]);
+            writeln!(self.f, "{};", signature)?;
+        }
+
+        let uses =
+            |used: fn(&InstructionKind) -> bool| instructions.iter().any(|instr| used(&instr.kind));
+        if uses(|kind| matches!(kind, InstructionKind::ReadFunction { .. })) {
+            write!(self.f, "\n{}", LIRE)?;
+        }
+        if uses(|kind| matches!(kind, InstructionKind::WriteFunction { .. })) {
+            write!(self.f, "\n{}", ECRIRE)?;
+        }
+        if uses(|kind| {
+            matches!(
+                kind,
+                InstructionKind::Arithmetic {
+                    operator: ArithmeticOperator::Division,
+                    ..
+                }
+            )
+        }) {
+            write!(self.f, "\n{}", DIVISION)?;
+        }
+
+        for (n, begin) in functions.iter().enumerate() {
+            let end = functions.get(n + 1).cloned().unwrap_or(instructions.len());
+            self.function(&instructions[*begin..end])?;
+        }
+
+        // The functions never called and the global variables never used are marked as used,
+        // as C compilers warn about them.
+        let called: BTreeSet<&str> = instructions
+            .iter()
+            .filter_map(|instr| match &instr.kind {
+                InstructionKind::FunctionCall { function, .. } => Some(&function.label()[1..]),
+                _ => None,
+            })
+            .collect();
+        let used: BTreeSet<&str> = instructions
+            .iter()
+            .flat_map(variables)
+            .filter(|v| v.scope() == Scope::Global)
+            .map(|v| v.id().as_str())
+            .collect();
+
+        writeln!(self.f, "\nint main(void)\n{{")?;
+        for instr in &instructions[..globals] {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                ..
+            } = &instr.kind
+            {
+                if !used.contains(variable.id().as_str()) {
+                    writeln!(self.f, "\t(void){};", name('g', variable.id()))?;
+                }
+            }
+        }
+        for begin in &functions {
+            let id = function_id(&instructions[*begin]);
+            if id != "main" && !called.contains(id) {
+                writeln!(self.f, "\t(void){};", name('f', id))?;
+            }
+        }
+        writeln!(self.f, "\t{}();", name('f', "main"))?;
+        writeln!(self.f, "\treturn 0;\n}}")
+    }
+
+    /// The table of the symbols of a function.
+    fn table(&self, begin: &Instruction) -> usize {
+        let function = self
+            .symbol_table
+            .function(&format!("f{}", function_id(begin)))
+            .expect("functions are declared");
+
+        match function.kind {
+            SymbolKind::Function { symbol_table, .. } => symbol_table,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The prototype of a function, its arguments in the order of their addresses. A vector
+    /// argument is a pointer to the first element of the vector of the caller.
+    fn signature(&self, begin: &Instruction) -> String {
+        let id = function_id(begin);
+        let table = self.table(begin);
+
+        let arguments: Vec<String> = self
+            .symbol_table
+            .arguments(table)
+            .iter()
+            .map(|symbol| {
+                let name = name('a', &format!("v{}", symbol.id));
+                match symbol.kind {
+                    SymbolKind::Vector { .. } => format!("int *{}", name),
+                    _ => format!("int {}", name),
+                }
+            })
+            .collect();
+        let arguments = if arguments.is_empty() {
+            "void".to_owned()
+        } else {
+            arguments.join(", ")
+        };
+
+        format!("static int {}({})", name('f', id), arguments)
+    }
+
+    /// Writes a function. Its temporaries and local variables are declared first, the labels
+    /// become the targets of `goto` and each end of the function a `return`.
+    fn function(&mut self, instructions: &[Instruction]) -> io::Result<()> {
+        let signature = self.signature(&instructions[0]);
+        let table = &self.symbol_table.tables[self.table(&instructions[0])];
+        writeln!(self.f, "\n{}\n{{", signature)?;
+
+        let mut temps = BTreeSet::new();
+        let mut read = BTreeSet::new();
+        let mut locals = Vec::new();
+        let mut arguments = 0;
+        for instr in instructions {
+            temps.extend(instr.uses().into_iter().map(Temp::temp));
+            read.extend(
+                reads(instr)
+                    .into_iter()
+                    .filter(|v| v.scope() == Scope::Local)
+                    .map(Variable::id),
+            );
+            match &instr.kind {
+                InstructionKind::Allocation {
+                    variable: Some(variable),
+                    size,
+                } => {
+                    let vector = table.symbols.iter().any(|symbol| match symbol.kind {
+                        SymbolKind::Vector { .. } => format!("v{}", symbol.id) == *variable.id(),
+                        _ => false,
+                    });
+                    locals.push(variable.id());
+                    if vector {
+                        writeln!(
+                            self.f,
+                            "\tint {}[{}] = {{0}};",
+                            name('v', variable.id()),
+                            size.constant()
+                        )?
+                    } else {
+                        writeln!(self.f, "\tint {} = 0;", name('v', variable.id()))?
+                    }
+                }
+                InstructionKind::FunctionPushArg { arg } if !is_address(arg) => arguments += 1,
+                _ => {}
+            }
+        }
+
+        let mut declarations: Vec<String> = temps.iter().map(|t| format!("t{}", t)).collect();
+        self.read = temps;
+        declarations.extend((0..arguments).map(|p| format!("p{}", p)));
+        if !declarations.is_empty() {
+            writeln!(self.f, "\tint {};", declarations.join(", "))?;
+        }
+        writeln!(self.f, "\tint retour = 0;")?;
+        // C compilers warn about the local variables which are never read.
+        for local in locals.into_iter().filter(|local| !read.contains(local)) {
+            writeln!(self.f, "\t(void){};", name('v', local))?;
+        }
+        writeln!(self.f)?;
+
+        self.arguments = 0;
+        for instr in &instructions[1..] {
+            if let Some(label) = &instr.label {
+                writeln!(self.f, "{}:;", label.label())?;
+            }
+
+            self.instruction(instr)?;
+        }
+
+        writeln!(self.f, "}}")
+    }
+
+    fn instruction(&mut self, instr: &Instruction) -> io::Result<()> {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => {
+                let left = operand(left);
+                let right = operand(right);
+                let value = match operator {
+                    ArithmeticOperator::Addition => wrapping(&left, "+", &right),
+                    ArithmeticOperator::Subtraction => wrapping(&left, "-", &right),
+                    ArithmeticOperator::Multiplication => wrapping(&left, "*", &right),
+                    ArithmeticOperator::Division => {
+                        let value = format!("division({}, {})", left, right);
+                        return self.assign(result, &value, true);
+                    }
+                };
+                self.assign(result, &value, false)
+            }
+            Affectation { value, result } => self.assign(result, &operand(value), false),
+            Allocation { variable: None, .. } => {
+                self.calls.push(Vec::new());
+                Ok(())
+            }
+            Allocation { .. } => Ok(()),
+            ReadFunction { result } => self.assign(result, "lire()", true),
+            WriteFunction { value } => writeln!(self.f, "\tecrire({});", operand(value)),
+            FunctionCall { function, result } => {
+                let arguments = self
+                    .calls
+                    .pop()
+                    .expect("a call begins with the allocation of its result");
+                let call = format!(
+                    "{}({})",
+                    name('f', &function.label()[1..]),
+                    arguments.join(", ")
+                );
+                self.assign(result, &call, true)
+            }
+            // The arguments are copied when pushed, as a call in a later argument may change
+            // the variables. A vector is given as it is.
+            FunctionPushArg { arg } if is_address(arg) => {
+                self.calls
+                    .last_mut()
+                    .expect("an argument is pushed during a call")
+                    .push(operand(arg));
+                Ok(())
+            }
+            FunctionPushArg { arg } => {
+                let copy = format!("p{}", self.arguments);
+                self.arguments += 1;
+                writeln!(self.f, "\t{} = {};", copy, operand(arg))?;
+                self.calls
+                    .last_mut()
+                    .expect("an argument is pushed during a call")
+                    .push(copy);
+                Ok(())
+            }
+            FunctionReturn { value } => writeln!(self.f, "\tretour = {};", operand(value)),
+            FunctionEnd => writeln!(self.f, "\treturn retour;"),
+            FunctionBegin => unreachable!("a function begins only once"),
+            Jump { label } => writeln!(self.f, "\tgoto {};", label.label()),
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                let condition = match condition {
+                    JumpIfCondition::Less => "<",
+                    JumpIfCondition::LessOrEqual => "<=",
+                    JumpIfCondition::Equal => "==",
+                    JumpIfCondition::NotEqual => "!=",
+                    JumpIfCondition::Greater => ">",
+                    JumpIfCondition::GreaterOrEqual => ">=",
+                };
+                writeln!(
+                    self.f,
+                    "\tif ({} {} {})\n\t\tgoto {};",
+                    operand(left),
+                    condition,
+                    operand(right),
+                    label.label()
+                )
+            }
+            NOP => Ok(()),
+        }
+    }
+
+    /// Writes the affectation of a value to a result. A temporary which is never read is left
+    /// out, and the value is only computed for its side `effect`.
+    fn assign(&mut self, result: &TV, value: &str, effect: bool) -> io::Result<()> {
+        match result {
+            TV::T(t) if !self.read.contains(&t.temp()) => {
+                if effect {
+                    writeln!(self.f, "\t{};", value)?;
+                }
+                Ok(())
+            }
+            _ => writeln!(self.f, "\t{} = {};", result_operand(result), value),
+        }
+    }
+
+    fn global(&self, id: &str) -> &'a SymbolKind {
+        &self
+            .symbol_table
+            .global_symbol(id)
+            .expect("globals are declared")
+            .kind
+    }
+}
+
+/// The variables read by an instruction, a vector given as an argument included.
+fn reads(instr: &Instruction) -> Vec<&Variable> {
+    use InstructionKind::*;
+
+    let operands = match &instr.kind {
+        Arithmetic { left, right, .. } | JumpIf { left, right, .. } => vec![left, right],
+        Affectation { value, .. }
+        | WriteFunction { value }
+        | FunctionReturn { value }
+        | FunctionPushArg { arg: value } => vec![value],
+        _ => Vec::new(),
+    };
+
+    operands
+        .into_iter()
+        .filter_map(|operand| match operand {
+            CTV::V(v) => Some(v),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The variables read or written by an instruction.
+fn variables(instr: &Instruction) -> Vec<&Variable> {
+    let mut variables = reads(instr);
+    if let Some(TV::V(v)) = instr.result() {
+        variables.push(v);
+    }
+    variables
+}
+
+fn function_id(begin: &Instruction) -> &str {
+    &begin
+        .label
+        .as_ref()
+        .expect("a function begins with its label")
+        .label()[1..]
+}
+
+/// A C identifier of an l identifier, which starts with the kind of the identifier: `f` for a
+/// function, `g` for a global variable, `a` for an argument and `v` for a local variable, in
+/// upper case if the l identifier starts with `$`. Then comes `_` and the l identifier where
+/// `_` is written `_u` and `$` is written `_d`, so no C keyword nor two l identifiers give the
+/// same C identifier. The `$` ending the identifier of an overloaded function, before its
+/// number of arguments, is written `_d` as well.
+fn name(kind: char, id: &str) -> String {
+    let id = if kind == 'f' { id } else { &id[1..] };
+    let (kind, id) = match id.chars().next() {
+        Some('$') => (kind.to_ascii_uppercase(), &id[1..]),
+        _ => (kind, id),
+    };
+
+    let mut name = format!("{}_", kind);
+    for c in id.chars() {
+        match c {
+            '_' => name.push_str("_u"),
+            '$' => name.push_str("_d"),
+            c => name.push(c),
+        }
+    }
+    name
+}
+
+fn variable(v: &Variable) -> String {
+    let kind = match v.scope() {
+        Scope::Global => 'g',
+        Scope::Local => 'v',
+        Scope::Argument => 'a',
+    };
+    let name = name(kind, v.id());
+
+    match v.indice() {
+        Some(CT::C(c)) => format!("{}[{}]", name, c.constant()),
+        Some(CT::T(t)) => format!("{}[t{}]", name, t.temp()),
+        None => name,
+    }
+}
+
+fn operand(ctv: &CTV) -> String {
+    match ctv {
+        // -2147483648 would be the negation of a constant too large for an int.
+        CTV::C(c) if c.constant() == i32::MIN => "(-2147483647 - 1)".to_owned(),
+        CTV::C(c) => c.constant().to_string(),
+        CTV::T(t) => format!("t{}", t.temp()),
+        CTV::V(v) => variable(v),
+    }
+}
+
+fn is_address(ctv: &CTV) -> bool {
+    match ctv {
+        CTV::V(v) => v.access() == Access::AddressOf,
+        _ => false,
+    }
+}
+
+fn result_operand(tv: &TV) -> String {
+    match tv {
+        TV::T(t) => format!("t{}", t.temp()),
+        TV::V(v) => variable(v),
+    }
+}
+
+/// An operation computed on unsigned integers, which wrap around instead of overflowing.
+fn wrapping(left: &str, operator: &str, right: &str) -> String {
+    format!("(int)((unsigned){} {} (unsigned){})", left, operator, right)
+}
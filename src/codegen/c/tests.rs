@@ -0,0 +1,166 @@
+use super::{name, C};
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use std::fs::{read, read_to_string, remove_file};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+#[test]
+fn appel() {
+    test("appel");
+}
+
+#[test]
+fn factorielle() {
+    test("factorielle");
+}
+
+#[test]
+fn pgcd() {
+    test("pgcd");
+}
+
+#[test]
+fn tableau_argument() {
+    test("tableau-argument");
+}
+
+#[test]
+fn tri() {
+    test("tri");
+}
+
+#[test]
+fn expression_compiled() {
+    test_compiled("expression");
+}
+
+#[test]
+fn operation_compiled() {
+    test_compiled("operation");
+}
+
+#[test]
+fn tableau_acces_complexe_compiled() {
+    test_compiled("tableau-acces-complexe");
+}
+
+#[test]
+fn tri_compiled() {
+    test_compiled("tri");
+}
+
+#[test]
+fn division_by_zero_compiled() {
+    let output = match run("tests/resources/division-by-zero.l", "") {
+        Some(output) => output,
+        None => return,
+    };
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Error: division by zero\n"
+    );
+}
+
+#[test]
+fn names() {
+    assert_eq!(name('f', "main"), "f_main");
+    assert_eq!(name('g', "v$tab"), "G_tab");
+    assert_eq!(name('a', "vauto"), "a_auto");
+    assert_eq!(name('v', "va_b"), "v_a_ub");
+    assert_eq!(name('v', "va$b"), "v_a_db");
+}
+
+fn test(filename: &str) {
+    let c_file = read(format!("tests/resources/{}.c", filename)).unwrap();
+    let mut generated_c = Vec::with_capacity(c_file.capacity());
+
+    generate(filename, &mut generated_c);
+
+    print!("{}", String::from_utf8_lossy(&generated_c));
+
+    assert!(c_file == generated_c);
+}
+
+fn generate(filename: &str, f: &mut Vec<u8>) {
+    let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .to_c(&symbol_table, f)
+        .unwrap();
+}
+
+/// The program of testor compiled by the C compiler, when there is one, writes as expected.
+fn test_compiled(filename: &str) {
+    let in_file = format!("testor/{}.in", filename);
+    let input = if Path::new(&in_file).is_file() {
+        read_to_string(in_file).unwrap()
+    } else {
+        String::new()
+    };
+    let out_file = read_to_string(format!("testor/{}.out", filename)).unwrap();
+
+    let output = match run(&format!("testor/{}.l", filename), &input) {
+        Some(output) => output,
+        None => return,
+    };
+    let output = String::from_utf8(output.stdout).unwrap();
+    print!("{}", output);
+
+    assert_eq!(out_file.trim_end(), output.trim_end());
+}
+
+/// Compiles the C of a program with `cc -std=c99 -Wall -Werror` and runs it, `None` if there
+/// is no C compiler.
+fn run(path: &str, input: &str) -> Option<Output> {
+    if Command::new("cc").arg("--version").output().is_err() {
+        println!("no C compiler, the test is skipped");
+        return None;
+    }
+
+    let l_file = read_to_string(path).unwrap();
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+    let mut c_file = Vec::new();
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .to_c(&symbol_table, &mut c_file)
+        .unwrap();
+
+    let name = Path::new(path).file_stem().unwrap().to_str().unwrap();
+    let executable: PathBuf = std::env::temp_dir().join(format!("l-compilator-c-{}", name));
+    let mut cc = Command::new("cc")
+        .args(["-std=c99", "-Wall", "-Werror", "-x", "c", "-", "-o"])
+        .arg(&executable)
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+    cc.stdin.take().unwrap().write_all(&c_file).unwrap();
+    assert!(cc.wait().unwrap().success(), "{} does not compile", path);
+
+    let mut child = Command::new(&executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    remove_file(&executable).unwrap();
+
+    Some(output)
+}
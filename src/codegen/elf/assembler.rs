@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+/// Number of a register in the encoding of the instructions.
+pub type Register = u8;
+
+pub const EAX: Register = 0;
+pub const ECX: Register = 1;
+pub const EDX: Register = 2;
+pub const EBX: Register = 3;
+pub const ESP: Register = 4;
+pub const EBP: Register = 5;
+pub const ESI: Register = 6;
+pub const EDI: Register = 7;
+
+/// A memory operand of 32 bits: `[base + disp]`, `[base + index * scale + disp]` for the
+/// elements of the local vectors, or `[symbol + index * 4 + disp]` for the zero-filled data of
+/// the program.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    base: Option<Register>,
+    index: Option<Register>,
+    scale: u8,
+    symbol: Option<String>,
+    disp: i32,
+}
+
+impl Memory {
+    pub fn base(base: Register, disp: i32) -> Self {
+        Self {
+            base: Some(base),
+            index: None,
+            scale: 1,
+            symbol: None,
+            disp,
+        }
+    }
+
+    /// The scale is 4 or 8.
+    pub fn indexed(base: Register, index: Register, scale: u8, disp: i32) -> Self {
+        Self {
+            base: Some(base),
+            index: Some(index),
+            scale,
+            symbol: None,
+            disp,
+        }
+    }
+
+    pub fn symbol(symbol: &str, index: Option<Register>, disp: i32) -> Self {
+        Self {
+            base: None,
+            index,
+            scale: 4,
+            symbol: Some(symbol.to_owned()),
+            disp,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Operand {
+    Register(Register),
+    Immediate(i32),
+    Memory(Memory),
+}
+
+/// The arithmetic instructions sharing their encoding, by their opcode extension.
+#[derive(Debug, Copy, Clone)]
+pub enum Alu {
+    Add = 0,
+    And = 4,
+    Sub = 5,
+    Cmp = 7,
+}
+
+/// Conditions of the conditional jumps, by their opcode.
+#[derive(Debug, Copy, Clone)]
+pub enum Condition {
+    Equal = 0x84,
+    NotEqual = 0x85,
+    Less = 0x8c,
+    GreaterOrEqual = 0x8d,
+    LessOrEqual = 0x8e,
+    Greater = 0x8f,
+}
+
+/// Encodes x86 instructions. Jumps and calls go to labels and memory operands may refer to
+/// data symbols, both are resolved by `link`. In 64 bits mode, the values stay on 32 bits and
+/// only the stack pointer, the base pointer and the pushed registers take 64 bits.
+pub struct Assembler {
+    code: Vec<u8>,
+    wide: bool,
+    labels: HashMap<String, usize>,
+    /// Positions of the relative addresses of the jumps and calls, with their label.
+    jumps: Vec<(usize, String)>,
+    /// Positions of the absolute addresses in memory operands, with their symbol.
+    addresses: Vec<(usize, String)>,
+}
+
+impl Assembler {
+    pub fn new(wide: bool) -> Self {
+        Self {
+            code: Vec::new(),
+            wide,
+            labels: HashMap::new(),
+            jumps: Vec::new(),
+            addresses: Vec::new(),
+        }
+    }
+
+    pub fn label(&mut self, label: &str) {
+        let previous = self.labels.insert(label.to_owned(), self.code.len());
+        assert!(previous.is_none(), "label {} defined twice", label);
+    }
+
+    /// Position of a label from the beginning of the code.
+    pub fn position(&self, label: &str) -> usize {
+        self.labels[label]
+    }
+
+    /// Size of the code, which linking does not change.
+    pub fn size(&self) -> usize {
+        self.code.len()
+    }
+
+    /// Resolves the labels, then the symbols to their address in `symbols`.
+    pub fn link(mut self, symbols: &HashMap<String, u32>) -> Vec<u8> {
+        for (position, label) in &self.jumps {
+            let target = *self
+                .labels
+                .get(label)
+                .unwrap_or_else(|| panic!("undefined label {}", label));
+            let relative = target as i32 - (*position as i32 + 4);
+            self.code[*position..*position + 4].copy_from_slice(&relative.to_le_bytes());
+        }
+
+        for (position, symbol) in &self.addresses {
+            let disp = i32::from_le_bytes([
+                self.code[*position],
+                self.code[*position + 1],
+                self.code[*position + 2],
+                self.code[*position + 3],
+            ]);
+            let absolute = symbols[symbol].wrapping_add(disp as u32);
+            self.code[*position..*position + 4].copy_from_slice(&absolute.to_le_bytes());
+        }
+
+        self.code
+    }
+
+    /// `mov dst, src`, one of them at most being in memory.
+    pub fn mov(&mut self, dst: &Operand, src: &Operand) {
+        match (dst, src) {
+            (Operand::Register(dst), Operand::Immediate(imm)) => {
+                self.code.push(0xb8 + dst);
+                self.imm32(*imm);
+            }
+            (Operand::Register(dst), src) => {
+                self.code.push(0x8b);
+                self.modrm(*dst, src);
+            }
+            (dst, Operand::Register(src)) => {
+                self.code.push(0x89);
+                self.modrm(*src, dst);
+            }
+            (dst @ Operand::Memory(_), Operand::Immediate(imm)) => {
+                self.code.push(0xc7);
+                self.modrm(0, dst);
+                self.imm32(*imm);
+            }
+            _ => panic!("mov {:?}, {:?} can not be encoded", dst, src),
+        }
+    }
+
+    /// `add`, `sub` or `cmp` of a register and an operand.
+    pub fn alu(&mut self, alu: Alu, dst: Register, src: &Operand) {
+        match src {
+            Operand::Immediate(imm) => {
+                self.code.push(0x81);
+                self.modrm(alu as u8, &Operand::Register(dst));
+                self.imm32(*imm);
+            }
+            src => {
+                self.code.push(((alu as u8) << 3) | 0x03);
+                self.modrm(dst, src);
+            }
+        }
+    }
+
+    /// `add`, `and` or `sub` of the stack pointer, on 64 bits in 64 bits mode.
+    pub fn stack(&mut self, alu: Alu, size: i32) {
+        self.rex_w();
+        self.alu(alu, ESP, &Operand::Immediate(size));
+    }
+
+    /// `mov ebp, esp`, on 64 bits in 64 bits mode.
+    pub fn frame(&mut self) {
+        self.mov_wide(&Operand::Register(EBP), &Operand::Register(ESP));
+    }
+
+    /// `mov` of a pointer between registers and memory, on 64 bits in 64 bits mode.
+    pub fn mov_wide(&mut self, dst: &Operand, src: &Operand) {
+        self.rex_w();
+        self.mov(dst, src);
+    }
+
+    /// `mov dst, imm64`, only in 64 bits mode.
+    pub fn movabs(&mut self, dst: Register, imm: u64) {
+        assert!(self.wide);
+        self.code.extend_from_slice(&[0x48, 0xb8 + dst]);
+        self.code.extend_from_slice(&imm.to_le_bytes());
+    }
+
+    pub fn imul(&mut self, dst: Register, src: &Operand) {
+        match src {
+            Operand::Immediate(imm) => {
+                self.code.push(0x69);
+                self.modrm(dst, &Operand::Register(dst));
+                self.imm32(*imm);
+            }
+            src => {
+                self.code.extend_from_slice(&[0x0f, 0xaf]);
+                self.modrm(dst, src);
+            }
+        }
+    }
+
+    pub fn cdq(&mut self) {
+        self.code.push(0x99);
+    }
+
+    pub fn idiv(&mut self, divisor: Register) {
+        self.code.push(0xf7);
+        self.modrm(7, &Operand::Register(divisor));
+    }
+
+    pub fn div(&mut self, divisor: Register) {
+        self.code.push(0xf7);
+        self.modrm(6, &Operand::Register(divisor));
+    }
+
+    pub fn neg(&mut self, register: Register) {
+        self.code.push(0xf7);
+        self.modrm(3, &Operand::Register(register));
+    }
+
+    /// `lea dst, src`, the way to get the address of a symbol.
+    pub fn lea(&mut self, dst: Register, src: &Memory) {
+        self.code.push(0x8d);
+        self.modrm(dst, &Operand::Memory(src.clone()));
+    }
+
+    /// `lea` of a pointer, on 64 bits in 64 bits mode.
+    pub fn lea_wide(&mut self, dst: Register, src: &Memory) {
+        self.rex_w();
+        self.lea(dst, src);
+    }
+
+    /// `movzx dst, byte src`.
+    pub fn load_byte(&mut self, dst: Register, src: &Memory) {
+        self.code.extend_from_slice(&[0x0f, 0xb6]);
+        self.modrm(dst, &Operand::Memory(src.clone()));
+    }
+
+    /// `mov byte dst, src` of the low byte of `eax` to `edx` or of a constant.
+    pub fn store_byte(&mut self, dst: &Memory, src: &Operand) {
+        let dst = Operand::Memory(dst.clone());
+        match src {
+            Operand::Register(src) if *src <= EBX => {
+                self.code.push(0x88);
+                self.modrm(*src, &dst);
+            }
+            Operand::Immediate(imm) => {
+                self.code.push(0xc6);
+                self.modrm(0, &dst);
+                self.code.push(*imm as u8);
+            }
+            _ => panic!("mov byte {:?} can not be encoded", src),
+        }
+    }
+
+    /// Pushes a whole register, on 64 bits in 64 bits mode.
+    pub fn push(&mut self, register: Register) {
+        self.code.push(0x50 + register);
+    }
+
+    pub fn pop(&mut self, register: Register) {
+        self.code.push(0x58 + register);
+    }
+
+    pub fn call(&mut self, label: &str) {
+        self.code.push(0xe8);
+        self.rel32(label);
+    }
+
+    /// Calls the address held by a register.
+    pub fn call_register(&mut self, register: Register) {
+        self.code.push(0xff);
+        self.modrm(2, &Operand::Register(register));
+    }
+
+    pub fn jmp(&mut self, label: &str) {
+        self.code.push(0xe9);
+        self.rel32(label);
+    }
+
+    pub fn jcc(&mut self, condition: Condition, label: &str) {
+        self.code.extend_from_slice(&[0x0f, condition as u8]);
+        self.rel32(label);
+    }
+
+    pub fn ret(&mut self) {
+        self.code.push(0xc3);
+    }
+
+    /// The system call of the target, `int 0x80` or `syscall`.
+    pub fn system_call(&mut self) {
+        if self.wide {
+            self.code.extend_from_slice(&[0x0f, 0x05]);
+        } else {
+            self.code.extend_from_slice(&[0xcd, 0x80]);
+        }
+    }
+
+    fn rex_w(&mut self) {
+        if self.wide {
+            self.code.push(0x48);
+        }
+    }
+
+    fn imm32(&mut self, imm: i32) {
+        self.code.extend_from_slice(&imm.to_le_bytes());
+    }
+
+    fn rel32(&mut self, label: &str) {
+        self.jumps.push((self.code.len(), label.to_owned()));
+        self.imm32(0);
+    }
+
+    /// Encodes the ModR/M byte of an instruction, with its SIB byte and displacement. The
+    /// displacements always take 32 bits and a symbol is an absolute address, which is not
+    /// relative to the instruction even in 64 bits mode.
+    fn modrm(&mut self, reg: u8, operand: &Operand) {
+        match operand {
+            Operand::Register(register) => self.code.push(0xc0 | reg << 3 | register),
+            Operand::Memory(Memory {
+                base: Some(base),
+                index: None,
+                symbol: None,
+                disp,
+                ..
+            }) if *base != ESP => {
+                self.code.push(0x80 | reg << 3 | base);
+                self.imm32(*disp);
+            }
+            Operand::Memory(Memory {
+                base: Some(base),
+                index: Some(index),
+                scale,
+                symbol: None,
+                disp,
+            }) => {
+                let sib = ss(*scale) | index << 3 | base;
+                self.code.extend_from_slice(&[0x80 | reg << 3 | ESP, sib]);
+                self.imm32(*disp);
+            }
+            Operand::Memory(Memory {
+                base: None,
+                index,
+                scale,
+                symbol,
+                disp,
+            }) => {
+                let sib = match index {
+                    Some(index) => ss(*scale) | index << 3 | EBP,
+                    None => ESP << 3 | EBP,
+                };
+                self.code.extend_from_slice(&[reg << 3 | ESP, sib]);
+                if let Some(symbol) = symbol {
+                    self.addresses.push((self.code.len(), symbol.clone()));
+                }
+                self.imm32(*disp);
+            }
+            _ => panic!("{:?} can not be encoded", operand),
+        }
+    }
+}
+
+/// The bits of a scale in the SIB byte.
+fn ss(scale: u8) -> u8 {
+    match scale {
+        4 => 0x80,
+        8 => 0xc0,
+        _ => panic!("scale {} can not be encoded", scale),
+    }
+}
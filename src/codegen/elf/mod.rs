@@ -0,0 +1,634 @@
+// The tests run the executables.
+#[cfg(all(
+    test,
+    target_os = "linux",
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+mod tests;
+
+// Some instructions are only encoded by the JIT.
+#[cfg_attr(
+    not(all(target_os = "linux", target_arch = "x86_64")),
+    allow(dead_code)
+)]
+pub(super) mod assembler;
+mod runtime;
+
+use super::allocation::{Allocation, Location};
+use super::{round_up, Target};
+use crate::liveness::Liveness;
+use crate::symbol_table::{Scope, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use assembler::*;
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+/// Writes a static executable without an assembler nor a linker, its runtime included.
+pub trait Elf {
+    fn to_elf(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()>;
+}
+
+impl Elf for ThreeAddressCode {
+    fn to_elf(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()> {
+        // The temporaries all go to the stack, the registers only hold values for a line.
+        let liveness = Liveness::new(self);
+        let allocation = Allocation::new(self, &liveness, 0);
+
+        let mut generator = Generator::new(symbol_table, allocation, target);
+        generator.generate(self);
+        generator.write(self, f)
+    }
+}
+
+const START: &str = "_start";
+
+/// Where the executable is loaded, the code following its headers.
+const BASE_X86: u32 = 0x0804_8000;
+const BASE_X86_64: u32 = 0x40_0000;
+const PAGE: u32 = 0x1000;
+
+/// Generates the machine code of the functions, keeping the frames and calling convention of
+/// the nasm code.
+pub(super) struct Generator<'a> {
+    pub(super) asm: Assembler,
+    /// Label jumped to on a division by zero, which otherwise faults like the nasm code.
+    pub(super) trap: Option<&'static str>,
+    divisions: usize,
+    target: Target,
+    symbol_table: &'a SymbolTable,
+    allocation: Allocation,
+    current_table: usize,
+    arguments: usize,
+    local_variables: usize,
+    /// Number of stack slots of each local variable of the current function.
+    sizes: HashMap<String, usize>,
+    /// Stack slots of the temporaries of the current function, reserved after its local
+    /// variables.
+    slots: usize,
+    slots_reserved: bool,
+    slots_base: usize,
+}
+
+impl<'a> Generator<'a> {
+    pub(super) fn new(
+        symbol_table: &'a SymbolTable,
+        allocation: Allocation,
+        target: Target,
+    ) -> Self {
+        Self {
+            asm: Assembler::new(target == Target::X86_64),
+            trap: None,
+            divisions: 0,
+            target,
+            symbol_table,
+            allocation,
+            current_table: 0,
+            arguments: 0,
+            local_variables: 0,
+            sizes: HashMap::new(),
+            slots: 0,
+            slots_reserved: true,
+            slots_base: 0,
+        }
+    }
+
+    fn generate(&mut self, three_address_code: &ThreeAddressCode) {
+        self.asm.label(START);
+        self.asm.call("fmain");
+        match self.target {
+            Target::X86 => {
+                self.asm
+                    .mov(&Operand::Register(EAX), &Operand::Immediate(1));
+                self.asm
+                    .mov(&Operand::Register(EBX), &Operand::Immediate(0));
+            }
+            Target::X86_64 => {
+                self.asm
+                    .mov(&Operand::Register(EAX), &Operand::Immediate(60));
+                self.asm
+                    .mov(&Operand::Register(EDI), &Operand::Immediate(0));
+            }
+        }
+        self.asm.system_call();
+
+        self.functions(three_address_code);
+        runtime::runtime(&mut self.asm, self.target);
+    }
+
+    pub(super) fn functions(&mut self, three_address_code: &ThreeAddressCode) {
+        for instr in three_address_code
+            .instructions
+            .iter()
+            .skip_while(|instr| is_allocation(instr))
+        {
+            // As in the nasm code, the slots are reserved before the label of the first
+            // instruction after the local variables, which a loop may jump back to.
+            let local_variable = matches!(
+                instr.kind,
+                InstructionKind::Allocation {
+                    variable: Some(_),
+                    ..
+                }
+            );
+            if !self.slots_reserved && !local_variable {
+                self.reserve_slots();
+            }
+
+            if let Some(label) = &instr.label {
+                self.asm.label(label.label());
+            }
+
+            self.instruction(instr);
+        }
+    }
+
+    /// Writes the headers and the code. The code and its headers are in a segment, the zero
+    /// filled buffers and global variables in a second one on the next page.
+    fn write(self, three_address_code: &ThreeAddressCode, f: &mut dyn Write) -> io::Result<()> {
+        let mut bss = vec![
+            (runtime::INPUT.to_owned(), runtime::INPUT_SIZE),
+            (runtime::OUTPUT.to_owned(), runtime::OUTPUT_SIZE),
+        ];
+        bss.extend(globals(three_address_code));
+
+        let (base, header_size) = match self.target {
+            Target::X86 => (BASE_X86, 52 + 2 * 32),
+            Target::X86_64 => (BASE_X86_64, 64 + 2 * 56),
+        };
+        let entry = base + header_size + self.asm.position(START) as u32;
+
+        // The code size does not depend on the addresses, it is known before linking.
+        let code_size = header_size + self.asm.size() as u32;
+        let bss_address = base + round_up(code_size, PAGE);
+
+        let mut symbols = HashMap::new();
+        let mut bss_size = 0;
+        for (symbol, size) in bss {
+            symbols.insert(symbol, bss_address + bss_size);
+            bss_size += round_up(size, 4);
+        }
+
+        let code = self.asm.link(&symbols);
+
+        let segments = [
+            Segment {
+                flags: 5,
+                offset: 0,
+                address: base,
+                file_size: code_size,
+                memory_size: code_size,
+            },
+            Segment {
+                flags: 6,
+                offset: 0,
+                address: bss_address,
+                file_size: 0,
+                memory_size: bss_size,
+            },
+        ];
+
+        match self.target {
+            Target::X86 => elf32(f, entry, &segments)?,
+            Target::X86_64 => elf64(f, entry, &segments)?,
+        }
+        f.write_all(&code)
+    }
+
+    fn instruction(&mut self, instr: &Instruction) {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => self.arithmetic(*operator, left, right, result),
+            Affectation { value, result } => {
+                self.load(value, EAX);
+                let result = self.tv_operand(result);
+                self.asm.mov(&result, &Operand::Register(EAX));
+            }
+            Allocation {
+                variable: Some(variable),
+                size,
+            } => {
+                let size = size.constant() as usize;
+                self.sizes.insert(variable.id().to_owned(), size);
+                self.local_variables += size * self.word();
+                self.asm.stack(Alu::Sub, (size * self.word()) as i32);
+            }
+            Allocation { variable: None, .. } => self.asm.stack(Alu::Sub, self.word() as i32),
+            ReadFunction { result } => {
+                self.asm.call(runtime::READ);
+                let result = self.tv_operand(result);
+                self.asm.mov(&result, &Operand::Register(EAX));
+            }
+            WriteFunction { value } => {
+                self.load(value, EAX);
+                self.asm.call(runtime::WRITE);
+            }
+            FunctionCall { function, result } => self.call(function, result),
+            FunctionBegin => {
+                let label = instr
+                    .label
+                    .as_ref()
+                    .expect("a function begins with its label");
+                self.function_begin(label)
+            }
+            FunctionEnd => self.function_end(),
+            FunctionPushArg { arg: CTV::V(v) } if v.access() == Access::AddressOf => {
+                self.address_of(v, EAX);
+                self.asm.push(EAX);
+            }
+            FunctionPushArg { arg } => {
+                self.load(arg, EAX);
+                self.asm.push(EAX);
+            }
+            FunctionReturn { value } => {
+                self.load(value, EAX);
+                let word = self.word();
+                let result = Memory::base(EBP, ((self.arguments + 2) * word) as i32);
+                self.asm
+                    .mov(&Operand::Memory(result), &Operand::Register(EAX));
+            }
+            Jump { label } => self.asm.jmp(label.label()),
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                let condition = match condition {
+                    JumpIfCondition::Less => Condition::Less,
+                    JumpIfCondition::LessOrEqual => Condition::LessOrEqual,
+                    JumpIfCondition::Equal => Condition::Equal,
+                    JumpIfCondition::NotEqual => Condition::NotEqual,
+                    JumpIfCondition::Greater => Condition::Greater,
+                    JumpIfCondition::GreaterOrEqual => Condition::GreaterOrEqual,
+                };
+                self.load(left, EAX);
+                let right = self.operand(right, ECX);
+                self.asm.alu(Alu::Cmp, EAX, &right);
+                self.asm.jcc(condition, label.label());
+            }
+            NOP => {}
+        }
+    }
+
+    /// Size of a stack slot.
+    fn word(&self) -> usize {
+        match self.target {
+            Target::X86 => 4,
+            Target::X86_64 => 8,
+        }
+    }
+
+    fn temp_operand(&self, t: &Temp) -> Operand {
+        match self.allocation.location(t) {
+            Location::Slot(slot) => {
+                let word = self.word();
+                let offset = word + 4 * word + self.slots_base + slot * word;
+                Operand::Memory(Memory::base(EBP, -(offset as i32)))
+            }
+            Location::Register(_) => unreachable!("temporaries are kept on the stack"),
+        }
+    }
+
+    /// Returns the memory operand of a variable, an index held by a temporary being copied in
+    /// `scratch`. Integers take 4 bytes but each local variable or argument takes a whole stack
+    /// slot. The elements of a local vector go up by 4 bytes from the lowest slot of the vector
+    /// as in the nasm code, so that a vector given as an argument is always an array of integers.
+    fn variable(&mut self, v: &Variable, scratch: Register) -> Operand {
+        if v.access() == Access::Indirect {
+            return self.indirect(v, scratch);
+        }
+
+        Operand::Memory(match v.scope() {
+            Scope::Global => match v.indice() {
+                Some(CT::C(c)) => Memory::symbol(v.id(), None, c.constant() * 4),
+                Some(CT::T(t)) => {
+                    let index = self.temp_operand(t);
+                    self.asm.mov(&Operand::Register(scratch), &index);
+                    Memory::symbol(v.id(), Some(scratch), 0)
+                }
+                None => Memory::symbol(v.id(), None, 0),
+            },
+            Scope::Local => {
+                let first = self.first_slot(v);
+                match v.indice() {
+                    Some(CT::C(c)) => Memory::base(EBP, first + c.constant() * 4),
+                    Some(CT::T(t)) => {
+                        let index = self.temp_operand(t);
+                        self.asm.mov(&Operand::Register(scratch), &index);
+                        Memory::indexed(EBP, scratch, 4, first)
+                    }
+                    None => Memory::base(EBP, first),
+                }
+            }
+            Scope::Argument => self.argument(v),
+        })
+    }
+
+    /// Returns the memory operand of an element of a vector argument, its address being copied
+    /// in `edx` and its index in `scratch`.
+    fn indirect(&mut self, v: &Variable, scratch: Register) -> Operand {
+        let address = self.argument(v);
+        self.asm
+            .mov_wide(&Operand::Register(EDX), &Operand::Memory(address));
+
+        Operand::Memory(match v.indice() {
+            Some(CT::C(c)) => Memory::base(EDX, c.constant() * 4),
+            Some(CT::T(t)) => {
+                let index = self.temp_operand(t);
+                self.asm.mov(&Operand::Register(scratch), &index);
+                Memory::indexed(EDX, scratch, 4, 0)
+            }
+            None => unreachable!("only the elements of a vector argument are accessed"),
+        })
+    }
+
+    /// Copies the address of a vector given as an argument in a register.
+    fn address_of(&mut self, v: &Variable, register: Register) {
+        match v.scope() {
+            Scope::Global => self.asm.lea(register, &Memory::symbol(v.id(), None, 0)),
+            Scope::Local => {
+                let first = Memory::base(EBP, self.first_slot(v));
+                self.asm.lea_wide(register, &first);
+            }
+            Scope::Argument => {
+                let address = self.argument(v);
+                self.asm
+                    .mov_wide(&Operand::Register(register), &Operand::Memory(address));
+            }
+        }
+    }
+
+    /// The offset from the base pointer of the lowest slot of a local variable.
+    fn first_slot(&self, v: &Variable) -> i32 {
+        let word = self.word();
+        let slot = v.address() / 4 * word;
+
+        -((word + 4 * word + slot + (self.sizes[v.id()] - 1) * word) as i32)
+    }
+
+    /// The slot of an argument.
+    fn argument(&self, v: &Variable) -> Memory {
+        let word = self.word();
+        let slot = v.address() / 4 * word;
+
+        Memory::base(EBP, (word + word * self.arguments - slot) as i32)
+    }
+
+    fn operand(&mut self, ctv: &CTV, scratch: Register) -> Operand {
+        match ctv {
+            CTV::C(c) => Operand::Immediate(c.constant()),
+            CTV::T(t) => self.temp_operand(t),
+            CTV::V(v) => self.variable(v, scratch),
+        }
+    }
+
+    fn tv_operand(&mut self, tv: &TV) -> Operand {
+        match tv {
+            TV::T(t) => self.temp_operand(t),
+            TV::V(v) => self.variable(v, ECX),
+        }
+    }
+
+    fn load(&mut self, ctv: &CTV, register: Register) {
+        let operand = self.operand(ctv, ECX);
+        self.asm.mov(&Operand::Register(register), &operand);
+    }
+
+    fn arithmetic(&mut self, operator: ArithmeticOperator, left: &CTV, right: &CTV, result: &TV) {
+        self.load(left, EAX);
+        match operator {
+            ArithmeticOperator::Addition => {
+                let right = self.operand(right, ECX);
+                self.asm.alu(Alu::Add, EAX, &right);
+            }
+            ArithmeticOperator::Subtraction => {
+                let right = self.operand(right, ECX);
+                self.asm.alu(Alu::Sub, EAX, &right);
+            }
+            ArithmeticOperator::Multiplication => {
+                let right = self.operand(right, ECX);
+                self.asm.imul(EAX, &right);
+            }
+            ArithmeticOperator::Division => self.division(right),
+        }
+        let result = self.tv_operand(result);
+        self.asm.mov(&result, &Operand::Register(EAX));
+    }
+
+    /// Divides `eax`. With a trap, a division by zero goes to the trap and a division by -1
+    /// wraps around as in the interpreter instead of faulting.
+    fn division(&mut self, right: &CTV) {
+        self.load(right, ECX);
+
+        let trap = match self.trap {
+            Some(trap) => trap,
+            None => {
+                self.asm.cdq();
+                self.asm.idiv(ECX);
+                return;
+            }
+        };
+
+        let divide = format!("division{}", self.divisions);
+        let end = format!("division{}_fin", self.divisions);
+        self.divisions += 1;
+
+        self.asm.alu(Alu::Cmp, ECX, &Operand::Immediate(0));
+        self.asm.jcc(Condition::Equal, trap);
+        self.asm.alu(Alu::Cmp, ECX, &Operand::Immediate(-1));
+        self.asm.jcc(Condition::NotEqual, &divide);
+        self.asm.neg(EAX);
+        self.asm.jmp(&end);
+        self.asm.label(&divide);
+        self.asm.cdq();
+        self.asm.idiv(ECX);
+        self.asm.label(&end);
+    }
+
+    fn reserve_slots(&mut self) {
+        self.slots_reserved = true;
+        self.slots_base = self.local_variables;
+
+        if self.slots != 0 {
+            let size = self.slots * self.word();
+            self.local_variables += size;
+            self.asm.stack(Alu::Sub, size as i32);
+        }
+    }
+
+    fn call(&mut self, function: &Label, result: &TV) {
+        self.asm.call(function.label());
+
+        let nb_arguments = self.nb_arguments(function.label());
+        if nb_arguments != 0 {
+            self.asm
+                .stack(Alu::Add, (self.word() * nb_arguments) as i32);
+        }
+
+        self.asm.pop(EAX);
+        let result = self.tv_operand(result);
+        self.asm.mov(&result, &Operand::Register(EAX));
+    }
+
+    fn function_begin(&mut self, label: &Label) {
+        self.current_table = match self.function(label.label()) {
+            SymbolKind::Function { symbol_table, .. } => *symbol_table,
+            _ => unreachable!(),
+        };
+        self.local_variables = 0;
+        self.sizes.clear();
+        self.arguments = self.nb_arguments(label.label());
+        self.slots = self.allocation.slots[label.label()];
+        self.slots_reserved = false;
+
+        // The frame is the one of the nasm code, so that the offsets are the same.
+        self.asm.push(EBP);
+        self.asm.frame();
+        for register in &[EAX, EBX, ECX, EDX] {
+            self.asm.push(*register);
+        }
+    }
+
+    fn function_end(&mut self) {
+        if self.local_variables != 0 {
+            self.asm.stack(Alu::Add, self.local_variables as i32);
+        }
+
+        for register in &[EDX, ECX, EBX, EAX] {
+            self.asm.pop(*register);
+        }
+        self.asm.pop(EBP);
+        self.asm.ret();
+
+        if let Some(parent) = self.symbol_table.tables[self.current_table].parent {
+            self.current_table = parent;
+        }
+    }
+
+    /// The function of a label of the three address code.
+    fn function(&self, label: &str) -> &'a SymbolKind {
+        self.symbol_table
+            .function(label)
+            .map(|symbol| &symbol.kind)
+            .expect("functions are declared")
+    }
+
+    fn nb_arguments(&self, label: &str) -> usize {
+        match self.function(label) {
+            SymbolKind::Function { nb_arguments, .. } => *nb_arguments,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The global variables with their size in bytes.
+pub(super) fn globals(three_address_code: &ThreeAddressCode) -> Vec<(String, u32)> {
+    three_address_code
+        .instructions
+        .iter()
+        .take_while(|instr| is_allocation(instr))
+        .filter_map(|instr| match &instr.kind {
+            InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } => Some((variable.id().clone(), size.constant() as u32 * 4)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_allocation(instr: &Instruction) -> bool {
+    matches!(instr.kind, InstructionKind::Allocation { .. })
+}
+
+/// A loadable segment of the executable.
+struct Segment {
+    /// Readable 4, writable 2 and executable 1.
+    flags: u32,
+    offset: u32,
+    address: u32,
+    file_size: u32,
+    memory_size: u32,
+}
+
+/// The ELF header of an executable for Linux.
+fn identification(f: &mut dyn Write, class: u8, machine: u16) -> io::Result<()> {
+    f.write_all(&[0x7f, b'E', b'L', b'F', class, 1, 1, 0])?;
+    f.write_all(&[0; 8])?;
+    // An executable file of the version 1.
+    f.write_all(&2u16.to_le_bytes())?;
+    f.write_all(&machine.to_le_bytes())?;
+    f.write_all(&1u32.to_le_bytes())
+}
+
+fn elf32(f: &mut dyn Write, entry: u32, segments: &[Segment]) -> io::Result<()> {
+    identification(f, 1, 3)?;
+    for field in &[entry, 52, 0, 0] {
+        f.write_all(&field.to_le_bytes())?;
+    }
+    for field in &[52u16, 32, segments.len() as u16, 40, 0, 0] {
+        f.write_all(&field.to_le_bytes())?;
+    }
+
+    for segment in segments {
+        for field in &[
+            1,
+            segment.offset,
+            segment.address,
+            segment.address,
+            segment.file_size,
+            segment.memory_size,
+            segment.flags,
+            PAGE,
+        ] {
+            f.write_all(&field.to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn elf64(f: &mut dyn Write, entry: u32, segments: &[Segment]) -> io::Result<()> {
+    identification(f, 2, 62)?;
+    for field in &[u64::from(entry), 64, 0] {
+        f.write_all(&field.to_le_bytes())?;
+    }
+    f.write_all(&0u32.to_le_bytes())?;
+    for field in &[64u16, 56, segments.len() as u16, 64, 0, 0] {
+        f.write_all(&field.to_le_bytes())?;
+    }
+
+    for segment in segments {
+        f.write_all(&1u32.to_le_bytes())?;
+        f.write_all(&segment.flags.to_le_bytes())?;
+        for field in &[
+            segment.offset,
+            segment.address,
+            segment.address,
+            segment.file_size,
+            segment.memory_size,
+            PAGE,
+        ] {
+            f.write_all(&u64::from(*field).to_le_bytes())?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+//! Reading and writing of integers, with the behaviour of `io.asm`. The routines take and
+//! return their integer in `eax` and may overwrite any other register but the stack and base
+//! pointers.
+
+use super::assembler::*;
+use crate::codegen::Target;
+
+/// The buffer of the line read, which keeps at most 254 characters.
+pub const INPUT: &str = "sinput";
+pub const INPUT_SIZE: u32 = 255;
+/// The buffer of the integer written: a sign, 10 digits and a line feed.
+pub const OUTPUT: &str = "soutput";
+pub const OUTPUT_SIZE: u32 = 12;
+
+pub const READ: &str = "lire";
+pub const WRITE: &str = "ecrire";
+
+pub fn runtime(asm: &mut Assembler, target: Target) {
+    read(asm, target);
+    write(asm, target);
+}
+
+/// Reads a line in the input buffer, then converts it to an integer in `eax`.
+fn read(asm: &mut Assembler, target: Target) {
+    let input = Memory::symbol(INPUT, None, 0);
+    let pointer = Memory::base(ESI, 0);
+
+    asm.label(READ);
+    asm.lea(ESI, &input);
+
+    asm.label("lire_octet");
+    match target {
+        Target::X86 => {
+            asm.mov(&Operand::Register(EAX), &Operand::Immediate(3));
+            asm.mov(&Operand::Register(EBX), &Operand::Immediate(0));
+            asm.mov(&Operand::Register(ECX), &Operand::Register(ESI));
+        }
+        Target::X86_64 => {
+            asm.mov(&Operand::Register(EAX), &Operand::Immediate(0));
+            asm.mov(&Operand::Register(EDI), &Operand::Immediate(0));
+        }
+    }
+    asm.mov(&Operand::Register(EDX), &Operand::Immediate(1));
+    asm.system_call();
+    // The end of the input or an error ends the line.
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(0));
+    asm.jcc(Condition::LessOrEqual, "lire_fin");
+    asm.load_byte(EAX, &pointer);
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b'\n')));
+    asm.jcc(Condition::Equal, "lire_fin");
+    // Once the buffer is full, its last byte is overwritten until the end of the line.
+    asm.lea(EDI, &Memory::symbol(INPUT, None, INPUT_SIZE as i32 - 1));
+    asm.alu(Alu::Cmp, ESI, &Operand::Register(EDI));
+    asm.jcc(Condition::Equal, "lire_octet");
+    asm.alu(Alu::Add, ESI, &Operand::Immediate(1));
+    asm.jmp("lire_octet");
+
+    asm.label("lire_fin");
+    asm.store_byte(&pointer, &Operand::Immediate(0));
+
+    // Leading blanks are skipped, then an optional sign and digits are read until any other
+    // character. The number is built in `ecx` and `ebx` tells whether it is negative.
+    asm.lea(ESI, &input);
+    asm.mov(&Operand::Register(ECX), &Operand::Immediate(0));
+    asm.mov(&Operand::Register(EBX), &Operand::Immediate(0));
+    asm.label("lire_blanc");
+    asm.load_byte(EAX, &pointer);
+    asm.alu(Alu::Add, ESI, &Operand::Immediate(1));
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b' ')));
+    asm.jcc(Condition::Equal, "lire_blanc");
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b'+')));
+    asm.jcc(Condition::Equal, "lire_chiffre");
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b'-')));
+    asm.jcc(Condition::NotEqual, "lire_test");
+    asm.mov(&Operand::Register(EBX), &Operand::Immediate(1));
+
+    asm.label("lire_chiffre");
+    asm.load_byte(EAX, &pointer);
+    asm.alu(Alu::Add, ESI, &Operand::Immediate(1));
+    asm.label("lire_test");
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b'0')));
+    asm.jcc(Condition::Less, "lire_nombre");
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(i32::from(b'9')));
+    asm.jcc(Condition::Greater, "lire_nombre");
+    asm.alu(Alu::Sub, EAX, &Operand::Immediate(i32::from(b'0')));
+    asm.imul(ECX, &Operand::Immediate(10));
+    asm.alu(Alu::Add, ECX, &Operand::Register(EAX));
+    asm.jmp("lire_chiffre");
+
+    asm.label("lire_nombre");
+    asm.mov(&Operand::Register(EAX), &Operand::Register(ECX));
+    asm.alu(Alu::Cmp, EBX, &Operand::Immediate(0));
+    asm.jcc(Condition::Equal, "lire_retour");
+    asm.neg(EAX);
+    asm.label("lire_retour");
+    asm.ret();
+}
+
+/// Writes the integer of `eax` and a line feed. The digits are put from the end of the output
+/// buffer, the absolute value being divided as unsigned so that -2147483648 is written.
+fn write(asm: &mut Assembler, target: Target) {
+    let pointer = Memory::base(ESI, 0);
+
+    asm.label(WRITE);
+    asm.mov(&Operand::Register(ECX), &Operand::Register(EAX));
+    asm.lea(ESI, &Memory::symbol(OUTPUT, None, OUTPUT_SIZE as i32 - 1));
+    asm.store_byte(&pointer, &Operand::Immediate(i32::from(b'\n')));
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(0));
+    asm.jcc(Condition::GreaterOrEqual, "ecrire_chiffre");
+    asm.neg(EAX);
+
+    asm.label("ecrire_chiffre");
+    asm.mov(&Operand::Register(EDI), &Operand::Immediate(10));
+    asm.mov(&Operand::Register(EDX), &Operand::Immediate(0));
+    asm.div(EDI);
+    asm.alu(Alu::Add, EDX, &Operand::Immediate(i32::from(b'0')));
+    asm.alu(Alu::Sub, ESI, &Operand::Immediate(1));
+    asm.store_byte(&pointer, &Operand::Register(EDX));
+    asm.alu(Alu::Cmp, EAX, &Operand::Immediate(0));
+    asm.jcc(Condition::NotEqual, "ecrire_chiffre");
+
+    asm.alu(Alu::Cmp, ECX, &Operand::Immediate(0));
+    asm.jcc(Condition::GreaterOrEqual, "ecrire_sortie");
+    asm.alu(Alu::Sub, ESI, &Operand::Immediate(1));
+    asm.store_byte(&pointer, &Operand::Immediate(i32::from(b'-')));
+
+    asm.label("ecrire_sortie");
+    asm.lea(EDI, &Memory::symbol(OUTPUT, None, OUTPUT_SIZE as i32));
+    asm.mov(&Operand::Register(EDX), &Operand::Register(EDI));
+    asm.alu(Alu::Sub, EDX, &Operand::Register(ESI));
+    match target {
+        Target::X86 => {
+            asm.mov(&Operand::Register(EAX), &Operand::Immediate(4));
+            asm.mov(&Operand::Register(EBX), &Operand::Immediate(1));
+            asm.mov(&Operand::Register(ECX), &Operand::Register(ESI));
+        }
+        Target::X86_64 => {
+            asm.mov(&Operand::Register(EAX), &Operand::Immediate(1));
+            asm.mov(&Operand::Register(EDI), &Operand::Immediate(1));
+        }
+    }
+    asm.system_call();
+    asm.ret();
+}
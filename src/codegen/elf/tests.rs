@@ -0,0 +1,186 @@
+use super::assembler::*;
+use super::Elf;
+use crate::codegen::Target;
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use std::collections::HashMap;
+use std::fs::{read_to_string, remove_file, File};
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn affect() {
+    test("affect", Target::X86);
+}
+
+#[test]
+fn boolean_booleen() {
+    test("boolean-booleen", Target::X86);
+}
+
+#[test]
+fn expression() {
+    test("expression", Target::X86);
+}
+
+#[test]
+fn function_in_function_args() {
+    test("function-in-function-args", Target::X86);
+}
+
+#[test]
+fn lire() {
+    test("lire", Target::X86);
+}
+
+#[test]
+fn lire_invalide() {
+    test("lire-invalide", Target::X86);
+}
+
+#[test]
+fn max() {
+    test("max", Target::X86);
+}
+
+#[test]
+fn param() {
+    test("param", Target::X86);
+}
+
+#[test]
+fn return_middle() {
+    test("return-middle", Target::X86);
+}
+
+#[test]
+fn tableau_acces_complexe() {
+    test("tableau-acces-complexe", Target::X86);
+}
+
+#[test]
+fn tableau_local() {
+    test("tableau-local", Target::X86);
+}
+
+#[test]
+fn tri() {
+    test("tri", Target::X86);
+}
+
+#[test]
+fn tri_reference() {
+    test("tri-reference", Target::X86);
+}
+
+#[test]
+fn lire_invalide_x86_64() {
+    test("lire-invalide", Target::X86_64);
+}
+
+#[test]
+fn param_x86_64() {
+    test("param", Target::X86_64);
+}
+
+#[test]
+fn tableau_local_x86_64() {
+    test("tableau-local", Target::X86_64);
+}
+
+#[test]
+fn tri_x86_64() {
+    test("tri", Target::X86_64);
+}
+
+#[test]
+fn tri_reference_x86_64() {
+    test("tri-reference", Target::X86_64);
+}
+
+#[test]
+fn encoding() {
+    let mut asm = Assembler::new(false);
+    asm.label("debut");
+    asm.mov(&Operand::Register(EAX), &Operand::Immediate(1));
+    asm.mov(
+        &Operand::Memory(Memory::base(EBP, -20)),
+        &Operand::Register(ECX),
+    );
+    asm.alu(
+        Alu::Cmp,
+        EAX,
+        &Operand::Memory(Memory::symbol("v$a", Some(ECX), 8)),
+    );
+    asm.mov(
+        &Operand::Register(EAX),
+        &Operand::Memory(Memory::indexed(EBP, ECX, 4, -40)),
+    );
+    asm.imul(EAX, &Operand::Immediate(10));
+    asm.jcc(Condition::Less, "debut");
+
+    let mut symbols = HashMap::new();
+    symbols.insert("v$a".to_owned(), 0x0804_9000);
+
+    assert_eq!(
+        asm.link(&symbols),
+        vec![
+            0xb8, 0x01, 0x00, 0x00, 0x00, // mov eax, 1
+            0x89, 0x8d, 0xec, 0xff, 0xff, 0xff, // mov dword [ebp - 20], ecx
+            0x3b, 0x04, 0x8d, 0x08, 0x90, 0x04, 0x08, // cmp eax, [v$a+ecx*4+8]
+            0x8b, 0x84, 0x8d, 0xd8, 0xff, 0xff, 0xff, // mov eax, [ebp+ecx*4-40]
+            0x69, 0xc0, 0x0a, 0x00, 0x00, 0x00, // imul eax, eax, 10
+            0x0f, 0x8c, 0xdb, 0xff, 0xff, 0xff, // jl debut
+        ]
+    );
+}
+
+fn test(filename: &str, target: Target) {
+    let in_file = format!("testor/{}.in", filename);
+    let input = if Path::new(&in_file).is_file() {
+        read_to_string(in_file).unwrap()
+    } else {
+        String::new()
+    };
+    let out_file = read_to_string(format!("testor/{}.out", filename)).unwrap();
+
+    let executable = std::env::temp_dir().join(format!("l-compilator-{}-{:?}", filename, target));
+    generate(&format!("testor/{}.l", filename), target, &executable);
+
+    let mut child = Command::new(&executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    remove_file(&executable).unwrap();
+
+    let output = String::from_utf8(output.stdout).unwrap();
+    print!("{}", output);
+
+    assert_eq!(out_file.trim_end(), output.trim_end());
+}
+
+fn generate(filename: &str, target: Target, executable: &Path) {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    let mut f = File::create(executable).unwrap();
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .to_elf(&symbol_table, target, &mut f)
+        .unwrap();
+    f.set_permissions(PermissionsExt::from_mode(0o755)).unwrap();
+}
@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests;
+
+use super::allocation::Allocation;
+use super::elf::assembler::*;
+use super::elf::{globals, Generator};
+use super::{round_up, Target};
+use crate::liveness::Liveness;
+use crate::run_three_address_code::{self, RuntimeError};
+use crate::symbol_table::SymbolTable;
+use crate::three_address_code::ThreeAddressCode;
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+use std::mem;
+use std::ptr;
+
+/// Compiles the code to x86_64 machine code in memory and executes it from `fmain`, `lire`
+/// reading lines of `input` and `ecrire` writing to `output` as the interpreter does.
+pub trait Jit {
+    fn jit(
+        &self,
+        symbol_table: &SymbolTable,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Result<(), RuntimeError>;
+}
+
+impl Jit for ThreeAddressCode {
+    fn jit(
+        &self,
+        symbol_table: &SymbolTable,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Result<(), RuntimeError> {
+        let mut io = Io {
+            input,
+            output,
+            error: None,
+        };
+
+        let liveness = Liveness::new(self);
+        let allocation = Allocation::new(self, &liveness, 0);
+        let mut generator = Generator::new(symbol_table, allocation, Target::X86_64);
+        generator.trap = Some(DIVISION_BY_ZERO);
+
+        entry(&mut generator.asm);
+        generator.functions(self);
+        runtime(&mut generator.asm, &mut io as *mut Io as u64);
+
+        // The data follows the code on the next page, the pointer of the stack at the entry
+        // being kept first.
+        let asm = generator.asm;
+        let code_size = round_up(asm.size(), PAGE);
+        let mut data = vec![(STACK.to_owned(), 8)];
+        data.extend(globals(self));
+        let data_size: u32 = data.iter().map(|(_, size)| round_up(*size, 8)).sum();
+
+        let mapping = Mapping::new(code_size + data_size as usize)?;
+        let mut symbols = HashMap::new();
+        let mut address = mapping.address() as u32 + code_size as u32;
+        for (symbol, size) in data {
+            symbols.insert(symbol, address);
+            address += round_up(size, 8);
+        }
+
+        let position = asm.position(ENTRY);
+        let code = asm.link(&symbols);
+        mapping.load(&code, code_size)?;
+
+        let status = unsafe {
+            let entry: extern "C" fn() -> i32 = mem::transmute(mapping.pointer.add(position));
+            entry()
+        };
+
+        if let Some(error) = io.error {
+            return Err(error.into());
+        }
+
+        match status {
+            0 => Ok(()),
+            _ => Err(RuntimeError::DivisionByZero),
+        }
+    }
+}
+
+const ENTRY: &str = "entree";
+const EXIT: &str = "sortie";
+const DIVISION_BY_ZERO: &str = "division_par_zero";
+const READ: &str = "lire";
+const WRITE: &str = "ecrire";
+/// The pointer of the stack at the entry, restored to leave from any depth.
+const STACK: &str = "pile";
+
+/// Calls `fmain` and returns 0, or 1 after a division by zero. The registers which the
+/// functions of the code may not keep are saved.
+fn entry(asm: &mut Assembler) {
+    asm.label(ENTRY);
+    asm.push(EBX);
+    asm.push(EBP);
+    asm.mov_wide(
+        &Operand::Memory(Memory::symbol(STACK, None, 0)),
+        &Operand::Register(ESP),
+    );
+    asm.call("fmain");
+    asm.mov(&Operand::Register(EAX), &Operand::Immediate(0));
+    asm.label(EXIT);
+    asm.pop(EBP);
+    asm.pop(EBX);
+    asm.ret();
+
+    asm.label(DIVISION_BY_ZERO);
+    asm.mov_wide(
+        &Operand::Register(ESP),
+        &Operand::Memory(Memory::symbol(STACK, None, 0)),
+    );
+    asm.mov(&Operand::Register(EAX), &Operand::Immediate(1));
+    asm.jmp(EXIT);
+}
+
+/// `lire` and `ecrire` call back `read` and `write` with the stack aligned on 16 bytes, as the
+/// System V calling convention wants.
+fn runtime(asm: &mut Assembler, io: u64) {
+    let callback = |asm: &mut Assembler, label: &str, function: u64| {
+        asm.label(label);
+        asm.push(EBP);
+        asm.frame();
+        asm.stack(Alu::And, -16);
+        asm.mov(&Operand::Register(ESI), &Operand::Register(EAX));
+        asm.movabs(EDI, io);
+        asm.movabs(EAX, function);
+        asm.call_register(EAX);
+        asm.mov_wide(&Operand::Register(ESP), &Operand::Register(EBP));
+        asm.pop(EBP);
+        asm.ret();
+    };
+
+    callback(asm, READ, read as *const () as u64);
+    callback(asm, WRITE, write as *const () as u64);
+}
+
+struct Io<'a> {
+    input: &'a mut dyn BufRead,
+    output: &'a mut dyn Write,
+    /// The first error, after which `lire` gives 0 and `ecrire` does nothing.
+    error: Option<io::Error>,
+}
+
+extern "C" fn read(io: *mut Io) -> i32 {
+    let io = unsafe { &mut *io };
+    if io.error.is_some() {
+        return 0;
+    }
+
+    match run_three_address_code::read(io.input) {
+        Ok(value) => value,
+        Err(error) => {
+            io.error = Some(error);
+            0
+        }
+    }
+}
+
+extern "C" fn write(io: *mut Io, value: i32) {
+    let io = unsafe { &mut *io };
+    if io.error.is_some() {
+        return;
+    }
+
+    if let Err(error) = writeln!(io.output, "{}", value) {
+        io.error = Some(error);
+    }
+}
+
+const PAGE: usize = 0x1000;
+const PROT_READ: i32 = 1;
+const PROT_WRITE: i32 = 2;
+const PROT_EXEC: i32 = 4;
+const MAP_PRIVATE: i32 = 0x02;
+const MAP_ANONYMOUS: i32 = 0x20;
+/// In the first 2 GiB, where the absolute addresses of 32 bits of the code can reach.
+const MAP_32BIT: i32 = 0x40;
+
+extern "C" {
+    fn mmap(addr: *mut u8, len: usize, prot: i32, flags: i32, fd: i32, offset: i64) -> *mut u8;
+    fn mprotect(addr: *mut u8, len: usize, prot: i32) -> i32;
+    fn munmap(addr: *mut u8, len: usize) -> i32;
+}
+
+/// Zero filled pages of memory, unmapped when dropped.
+struct Mapping {
+    pointer: *mut u8,
+    size: usize,
+}
+
+impl Mapping {
+    fn new(size: usize) -> io::Result<Self> {
+        let size = round_up(size, PAGE);
+        let pointer = unsafe {
+            mmap(
+                ptr::null_mut(),
+                size,
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS | MAP_32BIT,
+                -1,
+                0,
+            )
+        };
+
+        if pointer as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self { pointer, size })
+    }
+
+    fn address(&self) -> usize {
+        self.pointer as usize
+    }
+
+    /// Copies the code at the beginning and makes its `size` bytes executable and read-only.
+    fn load(&self, code: &[u8], size: usize) -> io::Result<()> {
+        unsafe {
+            ptr::copy_nonoverlapping(code.as_ptr(), self.pointer, code.len());
+            if mprotect(self.pointer, size, PROT_READ | PROT_EXEC) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe {
+            munmap(self.pointer, self.size);
+        }
+    }
+}
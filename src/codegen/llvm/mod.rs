@@ -0,0 +1,454 @@
+#[cfg(test)]
+mod tests;
+
+use crate::symbol_table::{Scope, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use std::collections::BTreeSet;
+use std::io;
+use std::io::Write;
+
+/// Translates to a module of LLVM IR in text, which `clang` compiles and links with the C
+/// library.
+pub trait Llvm {
+    fn to_llvm(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()>;
+}
+
+impl Llvm for ThreeAddressCode {
+    fn to_llvm(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()> {
+        Generator::new(symbol_table, f).generate(self)
+    }
+}
+
+/// `lire`, `ecrire` and the division, which behave as the nasm runtime.
+const RUNTIME: &str = include_str!("runtime.ll");
+
+struct Generator<'a> {
+    f: &'a mut dyn Write,
+    symbol_table: &'a SymbolTable,
+    /// Table of the symbols of the current function.
+    table: usize,
+    /// Typed arguments of the calls being prepared, the innermost last.
+    calls: Vec<Vec<String>>,
+    /// Number of values and of unnamed blocks of the current function.
+    values: usize,
+    blocks: usize,
+    /// Whether the current block has ended with a branch or a return.
+    terminated: bool,
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbol_table: &'a SymbolTable, f: &'a mut dyn Write) -> Self {
+        Self {
+            f,
+            symbol_table,
+            table: 0,
+            calls: Vec::new(),
+            values: 0,
+            blocks: 0,
+            terminated: false,
+        }
+    }
+
+    fn generate(&mut self, three_address_code: &ThreeAddressCode) -> io::Result<()> {
+        let instructions = &three_address_code.instructions;
+
+        let globals = instructions
+            .iter()
+            .take_while(|instr| matches!(instr.kind, InstructionKind::Allocation { .. }))
+            .count();
+
+        for instr in &instructions[..globals] {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                match self.global(variable.id()) {
+                    SymbolKind::Vector { .. } => writeln!(
+                        self.f,
+                        "@{} = internal global [{} x i32] zeroinitializer",
+                        variable.id(),
+                        size.constant()
+                    )?,
+                    _ => writeln!(self.f, "@{} = internal global i32 0", variable.id())?,
+                }
+            }
+        }
+        if globals != 0 {
+            writeln!(self.f)?;
+        }
+
+        write!(self.f, "{}", RUNTIME)?;
+
+        let functions: Vec<usize> = (globals..instructions.len())
+            .filter(|i| matches!(instructions[*i].kind, InstructionKind::FunctionBegin))
+            .collect();
+        for (n, begin) in functions.iter().enumerate() {
+            let end = functions.get(n + 1).cloned().unwrap_or(instructions.len());
+            self.function(&instructions[*begin..end])?;
+        }
+
+        writeln!(self.f, "\ndefine i32 @main() {{")?;
+        writeln!(self.f, "  %retour = call i32 @fmain()")?;
+        writeln!(self.f, "  ret i32 0\n}}")
+    }
+
+    /// Writes a function. The temporaries, local variables and arguments are kept in memory
+    /// allocated on entry, which the `mem2reg` pass of LLVM turns back into registers.
+    fn function(&mut self, instructions: &[Instruction]) -> io::Result<()> {
+        let label = instructions[0]
+            .label
+            .as_ref()
+            .expect("a function begins with its label");
+        self.table = match self.global(label.label()) {
+            SymbolKind::Function { symbol_table, .. } => *symbol_table,
+            _ => unreachable!(),
+        };
+        let symbol_table = self.symbol_table;
+        let arguments = symbol_table.arguments(self.table);
+
+        // A vector argument is a pointer to the first element of the vector of the caller.
+        let types: Vec<&str> = arguments
+            .iter()
+            .map(|argument| match argument.kind {
+                SymbolKind::Vector { .. } => "i32*",
+                _ => "i32",
+            })
+            .collect();
+        let parameters: Vec<String> = arguments
+            .iter()
+            .zip(&types)
+            .map(|(argument, ty)| format!("{} %v{}.param", ty, argument.id))
+            .collect();
+        writeln!(
+            self.f,
+            "\ndefine internal i32 @{}({}) {{",
+            label.label(),
+            parameters.join(", ")
+        )?;
+
+        self.values = 0;
+        self.blocks = 0;
+        self.terminated = false;
+
+        writeln!(self.f, "  %retour = alloca i32")?;
+        writeln!(self.f, "  store i32 0, i32* %retour")?;
+        for (argument, ty) in arguments.iter().zip(types) {
+            writeln!(self.f, "  %v{}.arg = alloca {}", argument.id, ty)?;
+            writeln!(
+                self.f,
+                "  store {1} %v{0}.param, {1}* %v{0}.arg",
+                argument.id, ty
+            )?;
+        }
+
+        let mut temps = BTreeSet::new();
+        for instr in instructions {
+            temps.extend(instr.uses().into_iter().map(Temp::temp));
+            if let Some(TV::T(t)) = instr.result() {
+                temps.insert(t.temp());
+            }
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                let ty = match self.local(variable.id()) {
+                    SymbolKind::Vector { .. } => format!("[{} x i32]", size.constant()),
+                    _ => "i32".to_owned(),
+                };
+                let zero = if ty == "i32" { "0" } else { "zeroinitializer" };
+                writeln!(self.f, "  %{} = alloca {}", variable.id(), ty)?;
+                writeln!(
+                    self.f,
+                    "  store {0} {1}, {0}* %{2}",
+                    ty,
+                    zero,
+                    variable.id()
+                )?;
+            }
+        }
+        for t in temps {
+            writeln!(self.f, "  %t{} = alloca i32", t)?;
+        }
+
+        for instr in &instructions[1..] {
+            if let Some(label) = &instr.label {
+                if !self.terminated {
+                    writeln!(self.f, "  br label %{}", label.label())?;
+                }
+                writeln!(self.f, "\n{}:", label.label())?;
+                self.terminated = false;
+            }
+
+            self.instruction(instr)?;
+        }
+
+        writeln!(self.f, "}}")
+    }
+
+    fn instruction(&mut self, instr: &Instruction) -> io::Result<()> {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Allocation { variable: None, .. } => {
+                self.calls.push(Vec::new());
+                return Ok(());
+            }
+            Allocation { .. } | NOP => return Ok(()),
+            _ => {}
+        }
+
+        // An instruction after a branch or a return starts a block, which is never reached.
+        if self.terminated {
+            writeln!(self.f, "\nb{}:", self.blocks)?;
+            self.blocks += 1;
+            self.terminated = false;
+        }
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => {
+                let left = self.load(left)?;
+                let right = self.load(right)?;
+                let value = self.value();
+                match operator {
+                    ArithmeticOperator::Addition => {
+                        writeln!(self.f, "  {} = add i32 {}, {}", value, left, right)?
+                    }
+                    ArithmeticOperator::Subtraction => {
+                        writeln!(self.f, "  {} = sub i32 {}, {}", value, left, right)?
+                    }
+                    ArithmeticOperator::Multiplication => {
+                        writeln!(self.f, "  {} = mul i32 {}, {}", value, left, right)?
+                    }
+                    ArithmeticOperator::Division => writeln!(
+                        self.f,
+                        "  {} = call i32 @division(i32 {}, i32 {})",
+                        value, left, right
+                    )?,
+                }
+                self.store(&value, result)
+            }
+            Affectation { value, result } => {
+                let value = self.load(value)?;
+                self.store(&value, result)
+            }
+            ReadFunction { result } => {
+                let value = self.value();
+                writeln!(self.f, "  {} = call i32 @lire()", value)?;
+                self.store(&value, result)
+            }
+            WriteFunction { value } => {
+                let value = self.load(value)?;
+                writeln!(self.f, "  call void @ecrire(i32 {})", value)
+            }
+            FunctionCall { function, result } => {
+                let arguments = self
+                    .calls
+                    .pop()
+                    .expect("a call begins with the allocation of its result");
+                let value = self.value();
+                writeln!(
+                    self.f,
+                    "  {} = call i32 @{}({})",
+                    value,
+                    function.label(),
+                    arguments.join(", ")
+                )?;
+                self.store(&value, result)
+            }
+            // The arguments are read when pushed, as a call in a later argument may change the
+            // variables.
+            FunctionPushArg { arg } => {
+                let value = match arg {
+                    CTV::V(v) if v.access() == Access::AddressOf => {
+                        format!("i32* {}", self.address(v)?)
+                    }
+                    _ => format!("i32 {}", self.load(arg)?),
+                };
+                self.calls
+                    .last_mut()
+                    .expect("an argument is pushed during a call")
+                    .push(value);
+                Ok(())
+            }
+            FunctionReturn { value } => {
+                let value = self.load(value)?;
+                writeln!(self.f, "  store i32 {}, i32* %retour", value)
+            }
+            FunctionEnd => {
+                let value = self.value();
+                writeln!(self.f, "  {} = load i32, i32* %retour", value)?;
+                writeln!(self.f, "  ret i32 {}", value)?;
+                self.terminated = true;
+                Ok(())
+            }
+            Jump { label } => {
+                writeln!(self.f, "  br label %{}", label.label())?;
+                self.terminated = true;
+                Ok(())
+            }
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                let condition = match condition {
+                    JumpIfCondition::Less => "slt",
+                    JumpIfCondition::LessOrEqual => "sle",
+                    JumpIfCondition::Equal => "eq",
+                    JumpIfCondition::NotEqual => "ne",
+                    JumpIfCondition::Greater => "sgt",
+                    JumpIfCondition::GreaterOrEqual => "sge",
+                };
+                let left = self.load(left)?;
+                let right = self.load(right)?;
+                let value = self.value();
+                writeln!(
+                    self.f,
+                    "  {} = icmp {} i32 {}, {}",
+                    value, condition, left, right
+                )?;
+
+                let next = format!("b{}", self.blocks);
+                self.blocks += 1;
+                writeln!(
+                    self.f,
+                    "  br i1 {}, label %{}, label %{}",
+                    value,
+                    label.label(),
+                    next
+                )?;
+                writeln!(self.f, "\n{}:", next)
+            }
+            FunctionBegin => unreachable!("a function begins only once"),
+            Allocation { .. } | NOP => unreachable!(),
+        }
+    }
+
+    /// A new value of the function.
+    fn value(&mut self) -> String {
+        self.values += 1;
+        format!("%r{}", self.values - 1)
+    }
+
+    /// The pointer to a temporary or a variable, an element of a vector being computed first.
+    fn pointer(&mut self, tv: &TV) -> io::Result<String> {
+        let v = match tv {
+            TV::T(t) => return Ok(format!("%t{}", t.temp())),
+            TV::V(v) => v,
+        };
+
+        let (pointer, size) = match v.scope() {
+            Scope::Global => match self.global(v.id()) {
+                SymbolKind::Vector { size, .. } => (format!("@{}", v.id()), *size),
+                _ => (format!("@{}", v.id()), None),
+            },
+            Scope::Local => match self.local(v.id()) {
+                SymbolKind::Vector { size, .. } => (format!("%{}", v.id()), *size),
+                _ => (format!("%{}", v.id()), None),
+            },
+            Scope::Argument => (format!("%{}.arg", v.id()), None),
+        };
+
+        let index = match v.indice() {
+            Some(CT::C(c)) => c.constant().to_string(),
+            Some(CT::T(t)) => self.load(&CTV::T(t.clone()))?,
+            None => return Ok(pointer),
+        };
+
+        let element = if v.access() == Access::Indirect {
+            let base = self.value();
+            writeln!(self.f, "  {} = load i32*, i32** {}", base, pointer)?;
+            let element = self.value();
+            writeln!(
+                self.f,
+                "  {} = getelementptr inbounds i32, i32* {}, i32 {}",
+                element, base, index
+            )?;
+            element
+        } else {
+            let size = size.expect("only vectors are indexed");
+            let element = self.value();
+            writeln!(
+                self.f,
+                "  {} = getelementptr inbounds [{1} x i32], [{1} x i32]* {2}, i32 0, i32 {3}",
+                element, size, pointer, index
+            )?;
+            element
+        };
+        Ok(element)
+    }
+
+    /// The pointer to the first element of a vector given as an argument.
+    fn address(&mut self, v: &Variable) -> io::Result<String> {
+        let (pointer, size) = match v.scope() {
+            Scope::Global => (format!("@{}", v.id()), self.global(v.id())),
+            Scope::Local => (format!("%{}", v.id()), self.local(v.id())),
+            Scope::Argument => {
+                let value = self.value();
+                writeln!(self.f, "  {} = load i32*, i32** %{}.arg", value, v.id())?;
+                return Ok(value);
+            }
+        };
+        let size = match size {
+            SymbolKind::Vector {
+                size: Some(size), ..
+            } => size,
+            _ => unreachable!("only vectors are given by address"),
+        };
+
+        let value = self.value();
+        writeln!(
+            self.f,
+            "  {} = getelementptr inbounds [{1} x i32], [{1} x i32]* {2}, i32 0, i32 0",
+            value, size, pointer
+        )?;
+        Ok(value)
+    }
+
+    /// Returns a constant or the value loaded from a temporary or a variable.
+    fn load(&mut self, ctv: &CTV) -> io::Result<String> {
+        let tv = match ctv {
+            CTV::C(c) => return Ok(c.constant().to_string()),
+            CTV::T(t) => TV::T(t.clone()),
+            CTV::V(v) => TV::V(v.clone()),
+        };
+
+        let pointer = self.pointer(&tv)?;
+        let value = self.value();
+        writeln!(self.f, "  {} = load i32, i32* {}", value, pointer)?;
+        Ok(value)
+    }
+
+    fn store(&mut self, value: &str, result: &TV) -> io::Result<()> {
+        let pointer = self.pointer(result)?;
+        writeln!(self.f, "  store i32 {}, i32* {}", value, pointer)
+    }
+
+    /// The local variable of the current function of an identifier of the three address code.
+    fn local(&self, id: &str) -> &'a SymbolKind {
+        self.symbol_table.tables[self.table]
+            .symbols
+            .iter()
+            .find(|symbol| symbol.id == id[1..])
+            .map(|symbol| &symbol.kind)
+            .expect("local variables are declared")
+    }
+
+    /// The global symbol of an identifier of the three address code, the label of a function
+    /// or `v` followed by the identifier of a variable.
+    fn global(&self, id: &str) -> &'a SymbolKind {
+        &self
+            .symbol_table
+            .global_symbol(id)
+            .expect("globals are declared")
+            .kind
+    }
+}
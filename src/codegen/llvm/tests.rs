@@ -0,0 +1,49 @@
+use super::Llvm;
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use std::fs::{read, read_to_string};
+
+#[test]
+fn appel() {
+    test("appel");
+}
+
+#[test]
+fn factorielle() {
+    test("factorielle");
+}
+
+#[test]
+fn tableau_argument() {
+    test("tableau-argument");
+}
+
+#[test]
+fn tri() {
+    test("tri");
+}
+
+fn test(filename: &str) {
+    let ll_file = read(format!("tests/resources/{}.ll", filename)).unwrap();
+    let mut generated_ll = Vec::with_capacity(ll_file.capacity());
+
+    generate(filename, &mut generated_ll);
+
+    print!("{}", String::from_utf8_lossy(&generated_ll));
+
+    assert!(ll_file == generated_ll);
+}
+
+fn generate(filename: &str, f: &mut Vec<u8>) {
+    let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .to_llvm(&symbol_table, f)
+        .unwrap();
+}
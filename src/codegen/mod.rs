@@ -0,0 +1,56 @@
+mod allocation;
+pub mod c;
+pub mod elf;
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+pub mod jit;
+pub mod llvm;
+pub mod nasm;
+pub mod wat;
+
+use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Target {
+    X86,
+    X86_64,
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x86" => Ok(Target::X86),
+            "x86_64" => Ok(Target::X86_64),
+            _ => Err(format!("invalid target: {}", s)),
+        }
+    }
+}
+
+/// Syntax of the assembly code: Intel for nasm, AT&T for the GNU assembler.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Syntax {
+    Intel,
+    Att,
+}
+
+impl FromStr for Syntax {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "intel" => Ok(Syntax::Intel),
+            "att" => Ok(Syntax::Att),
+            _ => Err(format!("invalid syntax: {}", s)),
+        }
+    }
+}
+
+/// Rounds `n` up to a multiple of `alignment`.
+pub(crate) fn round_up<T>(n: T, alignment: T) -> T
+where
+    T: Copy + From<u8> + Add<Output = T> + Sub<Output = T> + Div<Output = T> + Mul<Output = T>,
+{
+    (n + alignment - T::from(1)) / alignment * alignment
+}
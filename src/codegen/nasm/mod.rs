@@ -0,0 +1,1078 @@
+#[cfg(test)]
+mod tests;
+
+use super::allocation::{Allocation, Location};
+use super::{Syntax, Target};
+use crate::liveness::Liveness;
+use crate::symbol_table::{Scope, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+pub trait Nasm {
+    fn to_nasm(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()>;
+
+    /// The same code in the AT&T syntax of the GNU assembler.
+    fn to_gas(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()>;
+}
+
+impl Nasm for ThreeAddressCode {
+    fn to_nasm(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()> {
+        generate(self, symbol_table, target, Syntax::Intel, f)
+    }
+
+    fn to_gas(
+        &self,
+        symbol_table: &SymbolTable,
+        target: Target,
+        f: &mut dyn Write,
+    ) -> io::Result<()> {
+        generate(self, symbol_table, target, Syntax::Att, f)
+    }
+}
+
+fn generate(
+    three_address_code: &ThreeAddressCode,
+    symbol_table: &SymbolTable,
+    target: Target,
+    syntax: Syntax,
+    f: &mut dyn Write,
+) -> io::Result<()> {
+    let liveness = Liveness::new(three_address_code);
+    let allocation = Allocation::new(three_address_code, &liveness, ALLOCATABLE);
+
+    Generator::new(symbol_table, &liveness, allocation, target, syntax, f)
+        .generate(three_address_code)
+}
+
+/// Reading and writing of integers, appended to the code so that it assembles on its own.
+const RUNTIME: &str = include_str!("runtime/io.asm");
+const RUNTIME_64: &str = include_str!("runtime/io64.asm");
+const RUNTIME_GAS: &str = include_str!("runtime/io.s");
+const RUNTIME_GAS_64: &str = include_str!("runtime/io64.s");
+
+type Register = usize;
+
+const EAX: Register = 0;
+const EDX: Register = 3;
+const ESI: Register = 4;
+const EDI: Register = 5;
+const REGISTERS: [&str; 6] = ["eax", "ebx", "ecx", "edx", "esi", "edi"];
+const REGISTERS_64: [&str; 6] = ["rax", "rbx", "rcx", "rdx", "rsi", "rdi"];
+/// Temporaries are kept in `eax` to `edx`, `esi` and `edi` hold values for a single line.
+const ALLOCATABLE: usize = 4;
+
+struct Generator<'a> {
+    f: &'a mut dyn Write,
+    target: Target,
+    syntax: Syntax,
+    symbol_table: &'a SymbolTable,
+    liveness: &'a Liveness,
+    allocation: Allocation,
+    current_table: usize,
+    line: usize,
+    arguments: usize,
+    local_variables: usize,
+    /// Number of stack slots of each local variable of the current function.
+    sizes: HashMap<String, usize>,
+    /// Stack slots of the spilled temporaries of the current function, reserved after its
+    /// local variables.
+    slots: usize,
+    slots_reserved: bool,
+    slots_base: usize,
+}
+
+impl<'a> Generator<'a> {
+    fn new(
+        symbol_table: &'a SymbolTable,
+        liveness: &'a Liveness,
+        allocation: Allocation,
+        target: Target,
+        syntax: Syntax,
+        f: &'a mut dyn Write,
+    ) -> Self {
+        Self {
+            f,
+            target,
+            syntax,
+            symbol_table,
+            liveness,
+            allocation,
+            current_table: 0,
+            line: 0,
+            arguments: 0,
+            local_variables: 0,
+            sizes: HashMap::new(),
+            slots: 0,
+            slots_reserved: true,
+            slots_base: 0,
+        }
+    }
+
+    fn generate(&mut self, three_address_code: &ThreeAddressCode) -> io::Result<()> {
+        let instructions = &three_address_code.instructions;
+
+        match self.syntax {
+            Syntax::Intel => {
+                writeln!(self.f, "section\t.bss")?;
+                writeln!(
+                    self.f,
+                    "sinput:\tresb\t255\t;reserve a 255 byte space in memory for the users input string"
+                )?;
+            }
+            Syntax::Att => {
+                writeln!(self.f, "\t.bss")?;
+                writeln!(
+                    self.f,
+                    "sinput:\t.skip\t255\t# reserve a 255 byte space in memory for the users input string"
+                )?;
+            }
+        }
+
+        let globals = instructions
+            .iter()
+            .take_while(|instr| matches!(instr.kind, InstructionKind::Allocation { .. }))
+            .count();
+
+        for instr in &instructions[..globals] {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                match self.syntax {
+                    Syntax::Intel => {
+                        writeln!(self.f, "{}:\tresd\t{}", variable.id(), size.constant())?
+                    }
+                    Syntax::Att => {
+                        writeln!(self.f, "{}:\t.skip\t{}", variable.id(), size.constant() * 4)?
+                    }
+                }
+            }
+        }
+
+        match self.syntax {
+            Syntax::Intel => {
+                writeln!(self.f, "\nsection\t.text")?;
+                writeln!(self.f, "global _start")?;
+            }
+            Syntax::Att => {
+                writeln!(self.f, "\n\t.text")?;
+                writeln!(self.f, "\t.globl\t_start")?;
+            }
+        }
+        writeln!(self.f, "_start:")?;
+        self.instr("call", &[Operand::label("fmain")])?;
+        match self.target {
+            Target::X86 => {
+                self.instr("mov", &[Operand::Register("eax"), Operand::immediate(1)])?;
+                self.instr("int", &[Operand::immediate("0x80")])?;
+            }
+            Target::X86_64 => {
+                self.instr("mov", &[Operand::Register("eax"), Operand::immediate(60)])?;
+                self.instr("mov", &[Operand::Register("edi"), Operand::immediate(0)])?;
+                self.instr("syscall", &[])?;
+            }
+        }
+
+        for (line, instr) in instructions.iter().enumerate().skip(globals) {
+            self.line = line;
+
+            // The slots are reserved before the label of the first instruction after the local
+            // variables, which a loop may jump back to.
+            let local_variable = matches!(
+                instr.kind,
+                InstructionKind::Allocation {
+                    variable: Some(_),
+                    ..
+                }
+            );
+            if !self.slots_reserved && !local_variable {
+                self.reserve_slots()?;
+            }
+
+            if let Some(label) = &instr.label {
+                writeln!(self.f, "{}:", label.label())?;
+            }
+
+            self.instruction(instr)?;
+        }
+
+        let runtime = match (self.syntax, self.target) {
+            (Syntax::Intel, Target::X86) => RUNTIME,
+            (Syntax::Intel, Target::X86_64) => RUNTIME_64,
+            (Syntax::Att, Target::X86) => RUNTIME_GAS,
+            (Syntax::Att, Target::X86_64) => RUNTIME_GAS_64,
+        };
+        write!(self.f, "\n{}", runtime)
+    }
+
+    fn instruction(&mut self, instr: &Instruction) -> io::Result<()> {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => match operator {
+                ArithmeticOperator::Addition => self.arithmetic("add", left, right, result),
+                ArithmeticOperator::Subtraction => self.arithmetic("sub", left, right, result),
+                ArithmeticOperator::Multiplication => self.arithmetic("imul", left, right, result),
+                ArithmeticOperator::Division => self.division(left, right, result),
+            },
+            Affectation { value, result } => self.affectation(value, result),
+            Allocation { variable, size } => self.allocation(variable, size),
+            ReadFunction { result } => self.read(result),
+            WriteFunction { value } => self.write(value),
+            FunctionCall { function, result } => self.call(function, result),
+            FunctionBegin => {
+                let label = instr
+                    .label
+                    .as_ref()
+                    .expect("a function begins with its label");
+                self.function_begin(label)
+            }
+            FunctionEnd => self.function_end(),
+            FunctionPushArg { arg } => self.push_arg(arg),
+            FunctionReturn { value } => self.function_return(value),
+            Jump { label } => self.instr("jmp", &[Operand::label(label.label())]),
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                let opcode = match condition {
+                    JumpIfCondition::Less => "jl",
+                    JumpIfCondition::LessOrEqual => "jle",
+                    JumpIfCondition::Equal => "je",
+                    JumpIfCondition::NotEqual => "jne",
+                    JumpIfCondition::Greater => "jg",
+                    JumpIfCondition::GreaterOrEqual => "jge",
+                };
+                self.jump_if(opcode, left, right, label)
+            }
+            NOP => Ok(()),
+        }
+    }
+
+    fn instr(&mut self, opcode: &str, operands: &[Operand]) -> io::Result<()> {
+        self.write_instr(opcode, operands)?;
+        writeln!(self.f)
+    }
+
+    /// Writes an instruction followed by a comment, after `padding`.
+    fn instr_comment(
+        &mut self,
+        opcode: &str,
+        operands: &[Operand],
+        padding: &str,
+        comment: &str,
+    ) -> io::Result<()> {
+        self.write_instr(opcode, operands)?;
+
+        let marker = match self.syntax {
+            Syntax::Intel => ';',
+            Syntax::Att => '#',
+        };
+        writeln!(self.f, "{}{} {}", padding, marker, comment)
+    }
+
+    /// Writes an instruction, its operands being given in the Intel order. In the AT&T syntax
+    /// they come in the reverse order and the size of the operation is added to the opcode when
+    /// an operand is an integer in memory.
+    fn write_instr(&mut self, opcode: &str, operands: &[Operand]) -> io::Result<()> {
+        let syntax = self.syntax;
+        let (opcode, operands): (String, Vec<String>) = match syntax {
+            Syntax::Intel => (
+                opcode.to_owned(),
+                operands
+                    .iter()
+                    .map(|operand| operand.write(syntax))
+                    .collect(),
+            ),
+            Syntax::Att => {
+                let suffix = if operands.iter().any(Operand::is_dword) {
+                    "l"
+                } else {
+                    ""
+                };
+                let opcode = match opcode {
+                    "cdq" => "cltd",
+                    opcode => opcode,
+                };
+
+                (
+                    format!("{}{}", opcode, suffix),
+                    operands
+                        .iter()
+                        .rev()
+                        .map(|operand| operand.write(syntax))
+                        .collect(),
+                )
+            }
+        };
+
+        write!(self.f, "\t{}", opcode)?;
+
+        for (i, operand) in operands.iter().enumerate() {
+            if i == 0 {
+                write!(self.f, "\t{}", operand)?;
+            } else {
+                write!(self.f, ", {}", operand)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Size of a stack slot.
+    fn word(&self) -> usize {
+        match self.target {
+            Target::X86 => 4,
+            Target::X86_64 => 8,
+        }
+    }
+
+    /// Name of a register when used as an address or pushed on the stack.
+    fn wide(&self, register: Register) -> &'static str {
+        match self.target {
+            Target::X86 => REGISTERS[register],
+            Target::X86_64 => REGISTERS_64[register],
+        }
+    }
+
+    fn stack_pointer(&self) -> &'static str {
+        match self.target {
+            Target::X86 => "esp",
+            Target::X86_64 => "rsp",
+        }
+    }
+
+    fn base_pointer(&self) -> &'static str {
+        match self.target {
+            Target::X86 => "ebp",
+            Target::X86_64 => "rbp",
+        }
+    }
+
+    fn location(&self, t: &Temp) -> Location {
+        self.allocation.location(t)
+    }
+
+    /// The register holding the operand, if it is a temporary kept in a register.
+    fn register(&self, ctv: &CTV) -> Option<Register> {
+        match ctv {
+            CTV::T(t) => match self.location(t) {
+                Location::Register(register) => Some(register),
+                Location::Slot(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Whether reading the operand, its index included, reads the register.
+    fn reads(&self, ctv: &CTV, register: Register) -> bool {
+        match ctv {
+            CTV::T(_) => self.register(ctv) == Some(register),
+            CTV::V(v) => match v.indice() {
+                Some(CT::T(t)) => self.location(t) == Location::Register(register),
+                _ => false,
+            },
+            CTV::C(_) => false,
+        }
+    }
+
+    /// Whether the operand is in memory, which an instruction can read only once.
+    fn in_memory(&self, ctv: &CTV) -> bool {
+        match ctv {
+            CTV::C(_) => false,
+            CTV::T(_) => self.register(ctv).is_none(),
+            CTV::V(_) => true,
+        }
+    }
+
+    /// Whether a temporary other than `except` still needed after the current line is kept in
+    /// the register.
+    fn is_occupied(&self, register: Register, except: &Temp) -> bool {
+        self.liveness.live_out[self.line]
+            .iter()
+            .any(|t| t != except && self.location(t) == Location::Register(register))
+    }
+
+    /// Returns the operand of a temporary, a stack slot if it was spilled.
+    fn temp_operand(&self, t: &Temp) -> Operand {
+        match self.location(t) {
+            Location::Register(register) => Operand::Register(REGISTERS[register]),
+            Location::Slot(slot) => {
+                let word = self.word();
+                let offset = word + 4 * word + self.slots_base + slot * word;
+                Operand::dword(Address::relative(self.base_pointer(), -(offset as i32)))
+            }
+        }
+    }
+
+    /// Returns the memory operand of a variable. An index held by a temporary is first copied
+    /// in `scratch`. Integers take 4 bytes but each local variable, element of a local vector
+    /// or argument takes a whole stack slot. The elements of a local vector go up from its
+    /// lowest slot 4 bytes apart, as the ones of a global vector, so that a vector argument
+    /// holds the address of either.
+    fn variable(&mut self, v: &Variable, scratch: Register) -> io::Result<Operand> {
+        if v.access() == Access::Indirect {
+            return self.indirect(v, scratch);
+        }
+
+        let address = match v.scope() {
+            Scope::Global => match v.indice() {
+                Some(CT::C(c)) => Address::Symbol {
+                    symbol: v.id().to_owned(),
+                    index: None,
+                    displacement: Some(c.constant() * 4),
+                },
+                Some(CT::T(t)) => {
+                    let index = self.temp_operand(t);
+                    self.instr("mov", &[Operand::Register(REGISTERS[scratch]), index])?;
+                    Address::Symbol {
+                        symbol: v.id().to_owned(),
+                        index: Some(self.wide(scratch)),
+                        displacement: None,
+                    }
+                }
+                None => Address::Symbol {
+                    symbol: v.id().to_owned(),
+                    index: None,
+                    displacement: None,
+                },
+            },
+            Scope::Local => {
+                let first = self.first_slot(v) as i32;
+                match v.indice() {
+                    Some(CT::C(c)) => {
+                        Address::relative(self.base_pointer(), c.constant() * 4 - first)
+                    }
+                    Some(CT::T(t)) => {
+                        let index = self.temp_operand(t);
+                        self.instr("mov", &[Operand::Register(REGISTERS[scratch]), index])?;
+                        Address::Register {
+                            base: self.base_pointer(),
+                            index: Some(self.wide(scratch)),
+                            displacement: Some(-first),
+                        }
+                    }
+                    None => Address::relative(self.base_pointer(), -first),
+                }
+            }
+            Scope::Argument => self.argument(v),
+        };
+
+        Ok(Operand::dword(address))
+    }
+
+    /// Returns the memory operand of an element of the vector whose address is held by a
+    /// vector argument, the address being copied in `scratch`.
+    fn indirect(&mut self, v: &Variable, scratch: Register) -> io::Result<Operand> {
+        let address = Operand::Memory {
+            address: self.argument(v),
+            dword: false,
+        };
+        let wide = self.wide(scratch);
+
+        Ok(match v.indice() {
+            Some(CT::C(c)) => {
+                self.instr("mov", &[Operand::Register(wide), address])?;
+                Operand::dword(Address::relative(wide, c.constant() * 4))
+            }
+            Some(CT::T(t)) => {
+                let index = self.temp_operand(t);
+                self.instr("mov", &[Operand::Register(REGISTERS[scratch]), index])?;
+                self.instr("shl", &[Operand::Register(wide), Operand::immediate(2)])?;
+                self.instr("add", &[Operand::Register(wide), address])?;
+                Operand::dword(Address::Register {
+                    base: wide,
+                    index: None,
+                    displacement: None,
+                })
+            }
+            None => unreachable!("only the elements of a vector argument are accessed"),
+        })
+    }
+
+    /// Copies the address of a vector given as an argument in a register.
+    fn address_of(&mut self, v: &Variable, register: Register) -> io::Result<()> {
+        let wide = self.wide(register);
+
+        match v.scope() {
+            Scope::Global => self.instr(
+                "mov",
+                &[
+                    Operand::Register(REGISTERS[register]),
+                    Operand::immediate(v.id()),
+                ],
+            ),
+            Scope::Local => {
+                let first = Address::relative(self.base_pointer(), -(self.first_slot(v) as i32));
+                self.instr(
+                    "lea",
+                    &[
+                        Operand::Register(wide),
+                        Operand::Memory {
+                            address: first,
+                            dword: false,
+                        },
+                    ],
+                )
+            }
+            Scope::Argument => {
+                let address = Operand::Memory {
+                    address: self.argument(v),
+                    dword: false,
+                };
+                self.instr("mov", &[Operand::Register(wide), address])
+            }
+        }
+    }
+
+    /// The offset from the base pointer of the lowest slot of a local variable.
+    fn first_slot(&self, v: &Variable) -> usize {
+        let word = self.word();
+        let slot = v.address() / 4 * word;
+
+        word + 4 * word + slot + (self.sizes[v.id()] - 1) * word
+    }
+
+    /// The slot of an argument.
+    fn argument(&self, v: &Variable) -> Address {
+        let word = self.word();
+        let slot = v.address() / 4 * word;
+
+        Address::relative(
+            self.base_pointer(),
+            (word + word * self.arguments - slot) as i32,
+        )
+    }
+
+    /// Returns an operand, an index being copied in `scratch`.
+    fn operand(&mut self, ctv: &CTV, scratch: Register) -> io::Result<Operand> {
+        match ctv {
+            CTV::C(c) => Ok(Operand::immediate(c.constant())),
+            CTV::T(t) => Ok(self.temp_operand(t)),
+            CTV::V(v) => self.variable(v, scratch),
+        }
+    }
+
+    /// Copies an operand in a register.
+    fn load(&mut self, ctv: &CTV, register: Register) -> io::Result<()> {
+        if self.register(ctv) != Some(register) {
+            let operand = self.operand(ctv, ESI)?;
+            self.instr("mov", &[Operand::Register(REGISTERS[register]), operand])?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies a value computed in a register to the location of its temporary.
+    fn store(&mut self, t: &Temp, register: Register) -> io::Result<()> {
+        if self.location(t) != Location::Register(register) {
+            let operand = self.temp_operand(t);
+            self.instr("mov", &[operand, Operand::Register(REGISTERS[register])])?;
+        }
+
+        Ok(())
+    }
+
+    fn arithmetic(&mut self, opcode: &str, left: &CTV, right: &CTV, result: &TV) -> io::Result<()> {
+        let result = temp(result);
+        let same = match (left, right) {
+            (CTV::T(l), CTV::T(r)) => l == r,
+            _ => false,
+        };
+
+        // The result may take the register of the right operand which is read last: the
+        // operands of an addition or a multiplication are then swapped.
+        let (register, left, right) = match self.location(result) {
+            Location::Register(register) if same || !self.reads(right, register) => {
+                (register, left, right)
+            }
+            Location::Register(register) if opcode != "sub" && !self.reads(left, register) => {
+                (register, right, left)
+            }
+            _ => (EDI, left, right),
+        };
+
+        self.load(left, register)?;
+        let right = self.operand(right, ESI)?;
+        self.instr(opcode, &[Operand::Register(REGISTERS[register]), right])?;
+        self.store(result, register)
+    }
+
+    fn division(&mut self, left: &CTV, right: &CTV, result: &TV) -> io::Result<()> {
+        let result = temp(result);
+
+        let saved: Vec<Register> = [EAX, EDX]
+            .iter()
+            .cloned()
+            .filter(|register| self.is_occupied(*register, result))
+            .collect();
+        for register in &saved {
+            self.instr("push", &[Operand::Register(self.wide(*register))])?;
+        }
+
+        let divisor = match self.register(right) {
+            Some(register) if register != EAX && register != EDX => {
+                Operand::Register(REGISTERS[register])
+            }
+            None if right.is_temp() => self.operand(right, ESI)?,
+            _ => {
+                self.load(right, EDI)?;
+                Operand::Register(REGISTERS[EDI])
+            }
+        };
+        self.load(left, EAX)?;
+        self.instr("cdq", &[])?;
+        self.instr("idiv", &[divisor])?;
+        self.store(result, EAX)?;
+
+        for register in saved.iter().rev() {
+            self.instr("pop", &[Operand::Register(self.wide(*register))])?;
+        }
+
+        Ok(())
+    }
+
+    fn affectation(&mut self, value: &CTV, result: &TV) -> io::Result<()> {
+        if let TV::T(t) = result {
+            if let Location::Register(register) = self.location(t) {
+                return self.load(value, register);
+            }
+        }
+
+        // There is no move from memory to memory.
+        let value = if self.in_memory(value) {
+            self.load(value, EDI)?;
+            Operand::Register(REGISTERS[EDI])
+        } else {
+            self.operand(value, ESI)?
+        };
+        let result = match result {
+            TV::T(t) => self.temp_operand(t),
+            TV::V(v) => self.variable(v, ESI)?,
+        };
+
+        self.instr("mov", &[result, value])
+    }
+
+    fn allocation(&mut self, variable: &Option<Variable>, size: &Constant) -> io::Result<()> {
+        let word = self.word();
+
+        match variable {
+            Some(variable) => {
+                let size = size.constant() as usize;
+                self.sizes.insert(variable.id().to_owned(), size);
+                self.local_variables += size * word;
+                self.instr_comment(
+                    "sub",
+                    &[
+                        Operand::Register(self.stack_pointer()),
+                        Operand::immediate(size * word),
+                    ],
+                    "\t",
+                    &format!("allocation variable locale {}", variable.id()),
+                )
+            }
+            None => self.instr(
+                "sub",
+                &[
+                    Operand::Register(self.stack_pointer()),
+                    Operand::immediate(word),
+                ],
+            ),
+        }
+    }
+
+    fn reserve_slots(&mut self) -> io::Result<()> {
+        self.slots_reserved = true;
+        self.slots_base = self.local_variables;
+
+        if self.slots == 0 {
+            return Ok(());
+        }
+
+        let size = self.slots * self.word();
+        self.local_variables += size;
+        self.instr_comment(
+            "sub",
+            &[
+                Operand::Register(self.stack_pointer()),
+                Operand::immediate(size),
+            ],
+            "\t",
+            "allocation temporaires",
+        )
+    }
+
+    fn read(&mut self, result: &TV) -> io::Result<()> {
+        let result = temp(result);
+        let save_eax = self.is_occupied(EAX, result);
+        let eax = self.wide(EAX);
+        if save_eax {
+            self.instr("push", &[Operand::Register(eax)])?;
+        }
+
+        self.instr(
+            "mov",
+            &[Operand::Register(eax), Operand::immediate("sinput")],
+        )?;
+        self.instr("call", &[Operand::label("readline")])?;
+        self.instr(
+            "mov",
+            &[Operand::Register(eax), Operand::immediate("sinput")],
+        )?;
+        self.instr("call", &[Operand::label("atoi")])?;
+        self.store(result, EAX)?;
+
+        if save_eax {
+            self.instr("pop", &[Operand::Register(eax)])?;
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, value: &CTV) -> io::Result<()> {
+        if self.register(value) == Some(EAX) {
+            return self.instr("call", &[Operand::label("iprintLF")]);
+        }
+
+        let save_eax = match value {
+            CTV::T(t) => self.is_occupied(EAX, t),
+            _ => self.liveness.live_out[self.line]
+                .iter()
+                .any(|t| self.location(t) == Location::Register(EAX)),
+        };
+        let eax = self.wide(EAX);
+        if save_eax {
+            self.instr("push", &[Operand::Register(eax)])?;
+        }
+
+        self.load(value, EAX)?;
+        self.instr("call", &[Operand::label("iprintLF")])?;
+
+        if save_eax {
+            self.instr("pop", &[Operand::Register(eax)])?;
+        }
+
+        Ok(())
+    }
+
+    fn jump_if(&mut self, opcode: &str, left: &CTV, right: &CTV, label: &Label) -> io::Result<()> {
+        let left = if left.is_constant() || (self.in_memory(left) && self.in_memory(right)) {
+            self.load(left, EDI)?;
+            Operand::Register(REGISTERS[EDI])
+        } else {
+            self.operand(left, ESI)?
+        };
+        let right = self.operand(right, ESI)?;
+
+        self.instr("cmp", &[left, right])?;
+        self.instr(opcode, &[Operand::label(label.label())])
+    }
+
+    fn call(&mut self, function: &Label, result: &TV) -> io::Result<()> {
+        self.instr("call", &[Operand::label(function.label())])?;
+
+        let nb_arguments = self.nb_arguments(function.label());
+        if nb_arguments != 0 {
+            self.instr_comment(
+                "add",
+                &[
+                    Operand::Register(self.stack_pointer()),
+                    Operand::immediate(self.word() * nb_arguments),
+                ],
+                "\t\t",
+                "desallocation parametres",
+            )?;
+        }
+
+        let result = temp(result);
+        match self.location(result) {
+            Location::Register(register) => {
+                self.instr("pop", &[Operand::Register(self.wide(register))])
+            }
+            Location::Slot(_) => {
+                self.instr("pop", &[Operand::Register(self.wide(EDI))])?;
+                self.store(result, EDI)
+            }
+        }
+    }
+
+    fn push_arg(&mut self, arg: &CTV) -> io::Result<()> {
+        if let CTV::V(v) = arg {
+            if v.access() == Access::AddressOf {
+                self.address_of(v, ESI)?;
+                return self.instr("push", &[Operand::Register(self.wide(ESI))]);
+            }
+        }
+
+        let operand = match self.register(arg) {
+            Some(register) => Operand::Register(self.wide(register)),
+            None if self.in_memory(arg) && self.target == Target::X86_64 => {
+                // There is no 32 bits push in 64 bits mode.
+                let operand = self.operand(arg, ESI)?;
+                self.instr("mov", &[Operand::Register("r11d"), operand])?;
+                Operand::Register("r11")
+            }
+            None => self.operand(arg, ESI)?,
+        };
+
+        self.instr("push", &[operand])
+    }
+
+    fn function_return(&mut self, value: &CTV) -> io::Result<()> {
+        let operand = if self.in_memory(value) {
+            self.load(value, EDI)?;
+            Operand::Register(REGISTERS[EDI])
+        } else {
+            self.operand(value, ESI)?
+        };
+
+        let result = Operand::dword(Address::relative(
+            self.base_pointer(),
+            ((self.arguments + 2) * self.word()) as i32,
+        ));
+        self.instr("mov", &[result, operand])
+    }
+
+    fn function_begin(&mut self, label: &Label) -> io::Result<()> {
+        self.current_table = match self.function(label.label()) {
+            SymbolKind::Function { symbol_table, .. } => *symbol_table,
+            _ => unreachable!(),
+        };
+        self.local_variables = 0;
+        self.sizes.clear();
+        self.arguments = self.nb_arguments(label.label());
+        self.slots = self.allocation.slots[label.label()];
+        self.slots_reserved = false;
+
+        self.instr("push", &[Operand::Register(self.base_pointer())])?;
+        self.instr(
+            "mov",
+            &[
+                Operand::Register(self.base_pointer()),
+                Operand::Register(self.stack_pointer()),
+            ],
+        )?;
+        for register in EAX..=EDX {
+            self.instr("push", &[Operand::Register(self.wide(register))])?;
+        }
+
+        Ok(())
+    }
+
+    fn function_end(&mut self) -> io::Result<()> {
+        if self.local_variables != 0 {
+            self.instr(
+                "add",
+                &[
+                    Operand::Register(self.stack_pointer()),
+                    Operand::immediate(self.local_variables),
+                ],
+            )?;
+        }
+
+        for register in (EAX..=EDX).rev() {
+            self.instr("pop", &[Operand::Register(self.wide(register))])?;
+        }
+        self.instr("pop", &[Operand::Register(self.base_pointer())])?;
+        self.instr("ret", &[])?;
+
+        if let Some(parent) = self.symbol_table.tables[self.current_table].parent {
+            self.current_table = parent;
+        }
+
+        Ok(())
+    }
+
+    /// The function of a label of the three address code.
+    fn function(&self, label: &str) -> &'a SymbolKind {
+        self.symbol_table
+            .function(label)
+            .map(|symbol| &symbol.kind)
+            .expect("functions are declared")
+    }
+
+    fn nb_arguments(&self, label: &str) -> usize {
+        match self.function(label) {
+            SymbolKind::Function { nb_arguments, .. } => *nb_arguments,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Results of computations are always temporaries.
+fn temp(tv: &TV) -> &Temp {
+    match tv {
+        TV::T(t) => t,
+        TV::V(_) => unreachable!(),
+    }
+}
+
+/// An operand of an instruction, written in the syntax of the generated code.
+#[derive(Clone)]
+enum Operand {
+    /// A register, `%` prefixed in the AT&T syntax.
+    Register(&'static str),
+    /// A constant or the address of a symbol, `$` prefixed in the AT&T syntax.
+    Immediate(String),
+    /// The target of a jump or a call.
+    Label(String),
+    /// An integer in memory when `dword`, or else a whole stack slot or an address given to
+    /// `lea`.
+    Memory { address: Address, dword: bool },
+}
+
+#[derive(Clone)]
+enum Address {
+    /// `symbol`, `symbol+8` or `symbol+index*4`.
+    Symbol {
+        symbol: String,
+        index: Option<&'static str>,
+        displacement: Option<i32>,
+    },
+    /// `base`, `base - 8`, `base + 8` or `base + index*4 - 8`, relative to a register.
+    Register {
+        base: &'static str,
+        index: Option<&'static str>,
+        displacement: Option<i32>,
+    },
+}
+
+impl Operand {
+    fn immediate<T: ToString>(value: T) -> Self {
+        Operand::Immediate(value.to_string())
+    }
+
+    fn label<T: ToString>(label: T) -> Self {
+        Operand::Label(label.to_string())
+    }
+
+    fn dword(address: Address) -> Self {
+        Operand::Memory {
+            address,
+            dword: true,
+        }
+    }
+
+    fn is_dword(&self) -> bool {
+        matches!(self, Operand::Memory { dword: true, .. })
+    }
+
+    fn write(&self, syntax: Syntax) -> String {
+        match (self, syntax) {
+            (Operand::Register(register), Syntax::Intel) => (*register).to_owned(),
+            (Operand::Register(register), Syntax::Att) => format!("%{}", register),
+            (Operand::Immediate(value), Syntax::Intel) => value.clone(),
+            (Operand::Immediate(value), Syntax::Att) => format!("${}", value),
+            (Operand::Label(label), _) => label.clone(),
+            (Operand::Memory { address, dword }, Syntax::Intel) => {
+                let size = if *dword { "dword " } else { "" };
+                format!("{}[{}]", size, address.intel())
+            }
+            (Operand::Memory { address, .. }, Syntax::Att) => address.att(),
+        }
+    }
+}
+
+impl Address {
+    /// `base - 8` or `base + 8`.
+    fn relative(base: &'static str, displacement: i32) -> Self {
+        Address::Register {
+            base,
+            index: None,
+            displacement: Some(displacement),
+        }
+    }
+
+    fn intel(&self) -> String {
+        match self {
+            Address::Symbol {
+                symbol,
+                index,
+                displacement,
+            } => {
+                let mut address = symbol.clone();
+                if let Some(index) = index {
+                    address.push_str(&format!("+{}*4", index));
+                }
+                if let Some(displacement) = displacement {
+                    address.push_str(&format!("+{}", displacement));
+                }
+                address
+            }
+            Address::Register {
+                base,
+                index,
+                displacement,
+            } => {
+                let mut address = (*base).to_owned();
+                if let Some(index) = index {
+                    address.push_str(&format!(" + {}*4", index));
+                }
+                match displacement {
+                    Some(displacement) if *displacement < 0 => {
+                        address.push_str(&format!(" - {}", -displacement))
+                    }
+                    Some(displacement) => address.push_str(&format!(" + {}", displacement)),
+                    None => {}
+                }
+                address
+            }
+        }
+    }
+
+    fn att(&self) -> String {
+        match self {
+            Address::Symbol {
+                symbol,
+                index,
+                displacement,
+            } => {
+                let mut address = symbol.clone();
+                if let Some(displacement) = displacement {
+                    address.push_str(&format!("+{}", displacement));
+                }
+                if let Some(index) = index {
+                    address.push_str(&format!("(,%{},4)", index));
+                }
+                address
+            }
+            Address::Register {
+                base,
+                index,
+                displacement,
+            } => {
+                let displacement = displacement.map(|d| d.to_string()).unwrap_or_default();
+                match index {
+                    Some(index) => format!("{}(%{},%{},4)", displacement, base, index),
+                    None => format!("{}(%{})", displacement, base),
+                }
+            }
+        }
+    }
+}
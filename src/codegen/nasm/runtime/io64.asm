@@ -0,0 +1,217 @@
+;------------------------------------------
+; void readline
+; Read a line from stdin, store string after [$rax]
+; At most 254 characters are kept, the end of a longer line is skipped. The end of the
+; input gives an empty string.
+readline:
+    push    rdi        ; preserve rdi on stack to be restored after function
+    push    rsi        ; preserve rsi on stack to be restored after function
+    push    rdx        ; preserve rdx on stack to be restored after function
+    push    rcx        ; preserve rcx on stack, syscall overwrites it
+    push    r11        ; preserve r11 on stack, syscall overwrites it
+    push    rax        ; preserve rax on stack to be restored after function
+    push    r8         ; preserve r8 on stack to be restored after function
+    mov     rsi, rax   ; address where store result
+    lea     r8, [rax+254] ; last byte of the buffer
+continue_reading:
+    mov     rax, 0     ; syscall is read = 0
+    mov     rdi, 0     ; fd is stdin = 0
+    mov     rdx, 1     ; nb. of bytes to read
+    syscall            ; call kernel
+    cmp     rax, 0     ; if no byte read, then we reached EOF or an error, stop
+    jle     end_reading
+    cmp     byte [rsi], 10 ; Found '\n', stop reading string
+    je      end_reading
+    cmp     rsi, r8    ; Buffer full, overwrite the last byte until '\n'
+    je      continue_reading
+    inc     rsi        ; None of above, increment pointer and read next byte
+    jmp     continue_reading
+end_reading:
+    mov     byte [rsi], 0 ; Add zero to yield null-terminated string
+    pop     r8
+    pop     rax
+    pop     r11
+    pop     rcx
+    pop     rdx
+    pop     rsi
+    pop     rdi
+    ret
+
+;------------------------------------------
+; void iprint(Integer number)
+; Integer printing function (itoa), the integer is in eax
+iprint:
+    push    rax             ; preserve rax on the stack to be restored after function runs
+    push    rcx             ; preserve rcx on the stack to be restored after function runs
+    push    rdx             ; preserve rdx on the stack to be restored after function runs
+    push    rsi             ; preserve rsi on the stack to be restored after function runs
+
+    cmp     eax, 0
+    jge     positive
+    push    rax             ; preserve rax value
+    mov     rax, '-'
+    push    rax
+    mov     rax, rsp
+    call    sprint
+    pop     rax             ; remove '-' sign
+    pop     rax             ; retrieve rax value
+    neg     eax             ; -2147483648 stays negative but is divided as unsigned
+positive:
+    mov     ecx, 0          ; counter of how many bytes we need to print in the end
+divideLoop:
+    inc     ecx             ; count each byte to print - number of characters
+    mov     edx, 0          ; empty edx
+    mov     esi, 10         ; mov 10 into esi
+    div     esi             ; divide eax by esi
+    add     edx, 48         ; convert edx to it's ascii representation - edx holds the remainder after a divide instruction
+    push    rdx             ; push rdx (string representation of an intger) onto the stack
+    cmp     eax, 0          ; can the integer be divided anymore?
+    jnz     divideLoop      ; jump if not zero to the label divideLoop
+
+printLoop:
+    dec     ecx             ; count down each byte that we put on the stack
+    mov     rax, rsp        ; mov the stack pointer into rax for printing
+    call    sprint          ; call our string print function
+    pop     rax             ; remove last character from the stack to move rsp forward
+    cmp     ecx, 0          ; have we printed all bytes we pushed onto the stack?
+    jnz     printLoop       ; jump is not zero to the label printLoop
+
+    pop     rsi             ; restore rsi from the value we pushed onto the stack at the start
+    pop     rdx             ; restore rdx from the value we pushed onto the stack at the start
+    pop     rcx             ; restore rcx from the value we pushed onto the stack at the start
+    pop     rax             ; restore rax from the value we pushed onto the stack at the start
+    ret
+
+
+;------------------------------------------
+; void iprintLF(Integer number)
+; Integer printing function with linefeed (itoa)
+iprintLF:
+    call    iprint          ; call our integer printing function
+
+    push    rax             ; push rax onto the stack to preserve it while we use the rax register in this function
+    mov     rax, 0Ah        ; move 0Ah into rax - 0Ah is the ascii character for a linefeed
+    push    rax             ; push the linefeed onto the stack so we can get the address
+    mov     rax, rsp        ; move the address of the current stack pointer into rax for sprint
+    call    sprint          ; call our sprint function
+    pop     rax             ; remove our linefeed character from the stack
+    pop     rax             ; restore the original value of rax before our function was called
+    ret
+
+
+;------------------------------------------
+; int slen(String message)
+; String length calculation function
+slen:
+    push    rbx
+    mov     rbx, rax
+
+nextchar:
+    cmp     byte [rax], 0
+    jz      finished
+    inc     rax
+    jmp     nextchar
+
+finished:
+    sub     rax, rbx
+    pop     rbx
+    ret
+
+
+;------------------------------------------
+; void sprint(String message)
+; String printing function
+sprint:
+    push    rdi
+    push    rsi
+    push    rdx
+    push    rcx             ; syscall overwrites rcx
+    push    r11             ; syscall overwrites r11
+    push    rax
+    call    slen
+
+    mov     rdx, rax
+    mov     rsi, [rsp]
+    mov     rdi, 1
+    mov     rax, 1
+    syscall
+
+    pop     rax
+    pop     r11
+    pop     rcx
+    pop     rdx
+    pop     rsi
+    pop     rdi
+    ret
+
+
+;------------------------------------------
+; void sprintLF(String message)
+; String printing with line feed function
+sprintLF:
+    call    sprint
+
+    push    rax
+    mov     rax, 0AH
+    push    rax
+    mov     rax, rsp
+    call    sprint
+    pop     rax
+    pop     rax
+    ret
+
+
+;------------------------------------------
+; void exit()
+; Exit program and restore resources
+quit:
+    mov     rdi, 0
+    mov     rax, 60
+    syscall
+    ret
+
+;------------------------------------------
+; int atoi(Integer number)
+; Ascii to integer function (atoi), the string is at [rax] and the integer is returned in eax
+; Leading blanks are skipped, then an optional sign and digits are read until any other
+; character: a string without digits gives 0 and larger numbers wrap around.
+atoi:
+    push    rbx             ; preserve rbx on the stack to be restored after function runs
+    push    rcx             ; preserve rcx on the stack to be restored after function runs
+    push    rdx             ; preserve rdx on the stack to be restored after function runs
+    push    rsi             ; preserve rsi on the stack to be restored after function runs
+    xor     ebx, ebx        ; initialize forming answer
+    xor     ecx, ecx        ; initialize sign flag
+    mov     rsi, rax
+atoi1:
+    lodsb                   ; scan off whitespace
+    cmp     al, ' '         ; ignore leading blanks
+    je atoi1
+    cmp al, '+'             ; if + sign proceed
+    je atoi2
+    cmp al, '-'             ; is it - sign?
+    jne atoi3               ; no, test if numeric
+    dec ecx                 ; was - sign, set flag for negative result
+atoi2:
+    lodsb                   ; get next character
+atoi3:
+    cmp al,'0'        ; is character valid?
+    jb atoi4          ; jump if not '0' to '9'
+    cmp al,'9'
+    ja atoi4          ; jump if not '0' to '9'
+    and eax, 000fh     ; isolate lower four bits
+    xchg ebx, eax      ; multiply answer x 10
+    mov edx, 10
+    mul edx
+    add ebx, eax      ; add this digit
+    jmp atoi2         ; convert next digit
+atoi4:
+    mov eax,ebx ; result into AX
+    jecxz atoi5 ; jump if sign flag clear
+    neg eax ; make result negative
+atoi5:
+    pop     rsi             ; restore rsi from the value we pushed onto the stack at the start
+    pop     rdx             ; restore rdx from the value we pushed onto the stack at the start
+    pop     rcx             ; restore rcx from the value we pushed onto the stack at the start
+    pop     rbx             ; restore rbx from the value we pushed onto the stack at the start
+    ret
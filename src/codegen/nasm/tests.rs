@@ -0,0 +1,295 @@
+use super::{Nasm, RUNTIME};
+use crate::codegen::{Syntax, Target};
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use std::fs::{read, read_dir, read_to_string};
+
+#[test]
+fn affect() {
+    test("affect", Target::X86);
+}
+
+#[test]
+fn appel() {
+    test("appel", Target::X86);
+}
+
+#[test]
+fn associativite() {
+    test("associativite", Target::X86);
+}
+
+#[test]
+fn boucle() {
+    test("boucle", Target::X86);
+}
+
+#[test]
+fn calculette() {
+    test("calculette", Target::X86);
+}
+
+#[test]
+fn expression() {
+    test("expression", Target::X86);
+}
+
+#[test]
+fn factorielle() {
+    test("factorielle", Target::X86);
+}
+
+#[test]
+fn fibo() {
+    test("fibo", Target::X86);
+}
+
+#[test]
+fn lexunits() {
+    test("lexunits", Target::X86);
+}
+
+#[test]
+fn max() {
+    test("max", Target::X86);
+}
+
+#[test]
+fn pgcd() {
+    test("pgcd", Target::X86);
+}
+
+#[test]
+fn procedure() {
+    test("procedure", Target::X86);
+}
+
+#[test]
+fn procedure_arg() {
+    test("procedure_arg", Target::X86);
+}
+
+#[test]
+fn procedure_retour() {
+    test("procedure_retour", Target::X86);
+}
+
+#[test]
+fn procedure_varloc() {
+    test("procedure_varloc", Target::X86);
+}
+
+#[test]
+fn si_booleen() {
+    test("si-booleen", Target::X86);
+}
+
+#[test]
+fn sinon_booleen() {
+    test("sinon-booleen", Target::X86);
+}
+
+#[test]
+fn sommeneg_booleen() {
+    test("sommeneg-booleen", Target::X86);
+}
+
+#[test]
+fn spill() {
+    test("spill", Target::X86);
+}
+
+#[test]
+fn tableau() {
+    test("tableau", Target::X86);
+}
+
+#[test]
+fn tableau2() {
+    test("tableau2", Target::X86);
+}
+
+#[test]
+fn tableau_argument() {
+    test("tableau-argument", Target::X86);
+}
+
+#[test]
+fn tantque_booleen() {
+    test("tantque-booleen", Target::X86);
+}
+
+#[test]
+fn tantque0_booleen() {
+    test("tantque0-booleen", Target::X86);
+}
+
+#[test]
+fn tri() {
+    test("tri", Target::X86);
+}
+
+#[test]
+fn tri_ugly() {
+    test("tri_ugly", Target::X86);
+}
+
+#[test]
+fn procedure_arg_x86_64() {
+    test("procedure_arg", Target::X86_64);
+}
+
+#[test]
+fn tableau_argument_x86_64() {
+    test("tableau-argument", Target::X86_64);
+}
+
+#[test]
+fn tri_x86_64() {
+    test("tri", Target::X86_64);
+}
+
+#[test]
+fn calculette_x86_64() {
+    test("calculette", Target::X86_64);
+}
+
+#[test]
+fn tri_att() {
+    test_att("tri", Target::X86);
+}
+
+#[test]
+fn tri_att_x86_64() {
+    test_att("tri", Target::X86_64);
+}
+
+#[test]
+fn procedure_varloc_att() {
+    test_att("procedure_varloc", Target::X86);
+}
+
+#[test]
+fn spill_att() {
+    test_att("spill", Target::X86);
+}
+
+#[test]
+fn spill_att_x86_64() {
+    test_att("spill", Target::X86_64);
+}
+
+#[test]
+fn tableau_att() {
+    test_att("tableau", Target::X86);
+}
+
+#[test]
+fn tableau_argument_att() {
+    test_att("tableau-argument", Target::X86);
+}
+
+#[test]
+fn tableau_argument_att_x86_64() {
+    test_att("tableau-argument", Target::X86_64);
+}
+
+#[test]
+fn tableau_local_att() {
+    test_att("tableau-local", Target::X86);
+}
+
+#[test]
+fn twice() {
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+
+    generate("tri", Target::X86, Syntax::Intel, &mut first);
+    generate("tri", Target::X86, Syntax::Intel, &mut second);
+
+    assert!(first == second);
+}
+
+/// Goldens of the C backend whose programs the type checker now rejects.
+const C_BACKEND_REJECTED: [&str; 5] = ["si", "sinon", "sommeneg", "tantque", "tantque0"];
+
+/// The register allocation and the bundled runtime changed the code written by the C backend,
+/// but not its variables, functions and jumps.
+#[test]
+fn c_backend() {
+    for entry in read_dir("tests/resources/c-backend").unwrap() {
+        let path = entry.unwrap().path();
+        let filename = path.file_stem().unwrap().to_str().unwrap();
+        let c_backend = read_to_string(&path).unwrap();
+        let c_backend = c_backend.trim_start_matches("%include\t'io.asm'\n\n");
+
+        if C_BACKEND_REJECTED.contains(&filename) {
+            let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+            let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+            assert!(program.analyse().is_err(), "{}", filename);
+            continue;
+        }
+
+        let mut generated_nasm = Vec::new();
+        generate(filename, Target::X86, Syntax::Intel, &mut generated_nasm);
+        let generated_nasm = String::from_utf8(generated_nasm).unwrap();
+        let generated_nasm = generated_nasm.trim_end_matches(RUNTIME);
+
+        assert_eq!(labels(c_backend), labels(generated_nasm), "{}", filename);
+    }
+}
+
+/// The labels of the code, with the size of the variables they name.
+fn labels(code: &str) -> Vec<&str> {
+    code.lines()
+        .filter(|line| !line.starts_with(|c: char| c.is_whitespace() || c == ';'))
+        .filter(|line| line.contains(':'))
+        .map(|line| line.split(';').next().unwrap().trim_end())
+        .collect()
+}
+
+fn test(filename: &str, target: Target) {
+    let nasm_file = match target {
+        Target::X86 => format!("tests/resources/{}.nasm", filename),
+        Target::X86_64 => format!("tests/resources/{}.x86_64.nasm", filename),
+    };
+    let nasm_file = read(nasm_file).unwrap();
+    let mut generated_nasm = Vec::with_capacity(nasm_file.capacity());
+
+    generate(filename, target, Syntax::Intel, &mut generated_nasm);
+
+    print!("{}", String::from_utf8_lossy(&generated_nasm));
+
+    assert!(nasm_file == generated_nasm);
+}
+
+fn test_att(filename: &str, target: Target) {
+    let s_file = match target {
+        Target::X86 => format!("tests/resources/{}.s", filename),
+        Target::X86_64 => format!("tests/resources/{}.x86_64.s", filename),
+    };
+    let s_file = read(s_file).unwrap();
+    let mut generated_s = Vec::with_capacity(s_file.capacity());
+
+    generate(filename, target, Syntax::Att, &mut generated_s);
+
+    print!("{}", String::from_utf8_lossy(&generated_s));
+
+    assert!(s_file == generated_s);
+}
+
+fn generate(filename: &str, target: Target, syntax: Syntax, f: &mut Vec<u8>) {
+    let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    let three_address_code = program.gen_three_address_code(&symbol_table, 0);
+    match syntax {
+        Syntax::Intel => three_address_code.to_nasm(&symbol_table, target, f),
+        Syntax::Att => three_address_code.to_gas(&symbol_table, target, f),
+    }
+    .unwrap();
+}
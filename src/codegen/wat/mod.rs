@@ -0,0 +1,488 @@
+#[cfg(test)]
+mod tests;
+
+use super::round_up;
+use crate::symbol_table::{Scope, Symbol, SymbolKind, SymbolTable};
+use crate::three_address_code::*;
+use std::collections::{BTreeSet, HashMap};
+use std::io;
+use std::io::Write;
+
+/// Translates to a WebAssembly module in text. `lire` and `ecrire` are imported from the
+/// module `l` of the host and `fmain` is exported as `main`. The global vectors are in the
+/// linear memory, followed by a stack for the local vectors.
+pub trait Wat {
+    fn to_wat(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()>;
+}
+
+impl Wat for ThreeAddressCode {
+    fn to_wat(&self, symbol_table: &SymbolTable, f: &mut dyn Write) -> io::Result<()> {
+        Generator::new(symbol_table, f).generate(self)
+    }
+}
+
+/// The imports of `lire` and `ecrire`, the division and the zeroing of the local vectors.
+const RUNTIME: &str = include_str!("runtime.wat");
+
+const PAGE: u32 = 0x10000;
+/// Size of the stack of the local vectors, in pages.
+const STACK: u32 = 16;
+
+struct Generator<'a> {
+    f: &'a mut dyn Write,
+    symbol_table: &'a SymbolTable,
+    /// Addresses of the global vectors in the linear memory.
+    vectors: HashMap<String, u32>,
+    /// Offsets of the local vectors of the current function in its frame, which starts at
+    /// `$cadre` on the stack.
+    frame: HashMap<String, u32>,
+    /// Segments of the current function, by label.
+    segments: HashMap<String, usize>,
+    /// Copies of the arguments of the calls being prepared, the innermost last.
+    calls: Vec<Vec<usize>>,
+    /// Number of arguments copied in the current function.
+    arguments: usize,
+}
+
+impl<'a> Generator<'a> {
+    fn new(symbol_table: &'a SymbolTable, f: &'a mut dyn Write) -> Self {
+        Self {
+            f,
+            symbol_table,
+            vectors: HashMap::new(),
+            frame: HashMap::new(),
+            segments: HashMap::new(),
+            calls: Vec::new(),
+            arguments: 0,
+        }
+    }
+
+    fn generate(&mut self, three_address_code: &ThreeAddressCode) -> io::Result<()> {
+        let instructions = &three_address_code.instructions;
+
+        writeln!(self.f, "(module")?;
+        write!(self.f, "{}", RUNTIME)?;
+
+        let globals = instructions
+            .iter()
+            .take_while(|instr| matches!(instr.kind, InstructionKind::Allocation { .. }))
+            .count();
+
+        let mut memory = 0;
+        writeln!(self.f)?;
+        for instr in &instructions[..globals] {
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                match self.global(variable.id()) {
+                    SymbolKind::Vector { .. } => {
+                        self.vectors.insert(variable.id().to_owned(), memory);
+                        memory += size.constant() as u32 * 4;
+                    }
+                    _ => writeln!(
+                        self.f,
+                        "  (global ${} (mut i32) (i32.const 0))",
+                        variable.id()
+                    )?,
+                }
+            }
+        }
+
+        let functions: Vec<&[Instruction]> = {
+            let begins: Vec<usize> = (globals..instructions.len())
+                .filter(|i| matches!(instructions[*i].kind, InstructionKind::FunctionBegin))
+                .collect();
+            begins
+                .iter()
+                .enumerate()
+                .map(|(n, begin)| {
+                    let end = begins.get(n + 1).cloned().unwrap_or(instructions.len());
+                    &instructions[*begin..end]
+                })
+                .collect()
+        };
+
+        let mut pages = round_up(memory, PAGE) / PAGE;
+        let stack = functions
+            .iter()
+            .any(|function| !self.local_vectors(function).is_empty());
+        if stack {
+            writeln!(
+                self.f,
+                "  (global $pile (mut i32) (i32.const {}))",
+                pages * PAGE
+            )?;
+            pages += STACK;
+        }
+        writeln!(self.f, "  (memory {})", pages)?;
+
+        for function in functions {
+            self.function(function)?;
+        }
+
+        writeln!(self.f, "\n  (export \"main\" (func $fmain)))")
+    }
+
+    /// Writes a function. As WebAssembly has no `goto`, the code is cut in segments at each
+    /// label: a loop dispatches to the segment in `$etiquette`, through blocks nested so that
+    /// each segment follows the end of its block, and a jump sets `$etiquette` then goes back
+    /// to the start of the loop.
+    fn function(&mut self, instructions: &[Instruction]) -> io::Result<()> {
+        let label = instructions[0]
+            .label
+            .as_ref()
+            .expect("a function begins with its label");
+
+        let mut signature = String::new();
+        for argument in self.arguments(label) {
+            signature.push_str(&format!(" (param $v{} i32)", argument.id));
+        }
+
+        self.frame = HashMap::new();
+        let mut frame = 0;
+        for (vector, size) in self.local_vectors(instructions) {
+            self.frame.insert(vector, frame);
+            frame += size * 4;
+        }
+
+        let mut temps = BTreeSet::new();
+        let mut locals = vec!["retour".to_owned(), "etiquette".to_owned()];
+        if frame != 0 {
+            locals.push("cadre".to_owned());
+        }
+        let mut arguments = 0;
+        for instr in instructions {
+            temps.extend(instr.uses().into_iter().map(Temp::temp));
+            if let Some(TV::T(t)) = instr.result() {
+                temps.insert(t.temp());
+            }
+            match &instr.kind {
+                InstructionKind::Allocation {
+                    variable: Some(variable),
+                    ..
+                } if !self.frame.contains_key(variable.id()) => {
+                    locals.push(variable.id().to_owned())
+                }
+                InstructionKind::FunctionPushArg { .. } => arguments += 1,
+                _ => {}
+            }
+        }
+        locals.extend(temps.iter().map(|t| format!("t{}", t)));
+        locals.extend((0..arguments).map(|p| format!("p{}", p)));
+
+        writeln!(
+            self.f,
+            "\n  (func ${}{} (result i32)",
+            label.label(),
+            signature
+        )?;
+        let locals: Vec<String> = locals
+            .iter()
+            .map(|local| format!("(local ${} i32)", local))
+            .collect();
+        writeln!(self.f, "    {}", locals.join(" "))?;
+
+        // The frame of the local vectors is taken from the stack and zeroed.
+        if frame != 0 {
+            self.line("global.get $pile")?;
+            self.line("local.set $cadre")?;
+            self.line("local.get $cadre")?;
+            self.line(&format!("i32.const {}", frame))?;
+            self.line("i32.add")?;
+            self.line("global.set $pile")?;
+            self.line("local.get $cadre")?;
+            self.line("global.get $pile")?;
+            self.line("call $mise_a_zero")?;
+        }
+
+        let mut segments = vec!["debut".to_owned()];
+        segments.extend(
+            instructions[1..]
+                .iter()
+                .filter_map(|instr| instr.label.as_ref())
+                .map(|label| label.label().to_owned()),
+        );
+        self.segments = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| (segment.clone(), i))
+            .collect();
+
+        self.line("loop $aiguillage")?;
+        for segment in segments.iter().rev() {
+            self.line(&format!("block ${}", segment))?;
+        }
+        self.line("local.get $etiquette")?;
+        let targets: Vec<String> = segments
+            .iter()
+            .map(|segment| format!("${}", segment))
+            .collect();
+        self.line(&format!("br_table {}", targets.join(" ")))?;
+        self.line("end")?;
+
+        self.arguments = 0;
+        for instr in &instructions[1..] {
+            if let Some(label) = &instr.label {
+                self.line(&format!("end ;; {}", label.label()))?;
+            }
+
+            self.instruction(instr)?;
+        }
+
+        self.line("end")?;
+        self.leave()?;
+        writeln!(self.f, "    local.get $retour)")
+    }
+
+    /// Gives the frame of the local vectors back to the stack.
+    fn leave(&mut self) -> io::Result<()> {
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+
+        self.line("local.get $cadre")?;
+        self.line("global.set $pile")
+    }
+
+    fn instruction(&mut self, instr: &Instruction) -> io::Result<()> {
+        use InstructionKind::*;
+
+        match &instr.kind {
+            Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => self.store(result, |generator| {
+                generator.load(left)?;
+                generator.load(right)?;
+                generator.line(match operator {
+                    ArithmeticOperator::Addition => "i32.add",
+                    ArithmeticOperator::Subtraction => "i32.sub",
+                    ArithmeticOperator::Multiplication => "i32.mul",
+                    ArithmeticOperator::Division => "call $division",
+                })
+            }),
+            Affectation { value, result } => self.store(result, |generator| generator.load(value)),
+            Allocation { variable: None, .. } => {
+                self.calls.push(Vec::new());
+                Ok(())
+            }
+            Allocation { .. } => Ok(()),
+            ReadFunction { result } => self.store(result, |generator| generator.line("call $lire")),
+            WriteFunction { value } => {
+                self.load(value)?;
+                self.line("call $ecrire")
+            }
+            FunctionCall { function, result } => {
+                let arguments = self
+                    .calls
+                    .pop()
+                    .expect("a call begins with the allocation of its result");
+                self.store(result, |generator| {
+                    for argument in arguments {
+                        generator.line(&format!("local.get $p{}", argument))?;
+                    }
+                    generator.line(&format!("call ${}", function.label()))
+                })
+            }
+            // The arguments are copied when pushed, as a call in a later argument may change
+            // the variables.
+            FunctionPushArg { arg } => {
+                let copy = self.arguments;
+                self.arguments += 1;
+                self.load(arg)?;
+                self.line(&format!("local.set $p{}", copy))?;
+                self.calls
+                    .last_mut()
+                    .expect("an argument is pushed during a call")
+                    .push(copy);
+                Ok(())
+            }
+            FunctionReturn { value } => {
+                self.load(value)?;
+                self.line("local.set $retour")
+            }
+            FunctionEnd => {
+                self.leave()?;
+                self.line("local.get $retour")?;
+                self.line("return")
+            }
+            FunctionBegin => unreachable!("a function begins only once"),
+            Jump { label } => self.jump(label),
+            JumpIf {
+                condition,
+                left,
+                right,
+                label,
+            } => {
+                self.load(left)?;
+                self.load(right)?;
+                self.line(match condition {
+                    JumpIfCondition::Less => "i32.lt_s",
+                    JumpIfCondition::LessOrEqual => "i32.le_s",
+                    JumpIfCondition::Equal => "i32.eq",
+                    JumpIfCondition::NotEqual => "i32.ne",
+                    JumpIfCondition::Greater => "i32.gt_s",
+                    JumpIfCondition::GreaterOrEqual => "i32.ge_s",
+                })?;
+                self.line("if")?;
+                self.jump(label)?;
+                self.line("end")
+            }
+            NOP => Ok(()),
+        }
+    }
+
+    fn line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.f, "    {}", line)
+    }
+
+    fn jump(&mut self, label: &Label) -> io::Result<()> {
+        let segment = self.segments[label.label()];
+        self.line(&format!("i32.const {}", segment))?;
+        self.line("local.set $etiquette")?;
+        self.line("br $aiguillage")
+    }
+
+    /// Pushes the address of an element of a vector, to which its offset is added by the load
+    /// or the store. The address of an element of a local vector is relative to `$cadre`, the
+    /// one of an element of a vector argument to the address it holds.
+    fn address(&mut self, v: &Variable, index: &CT) -> io::Result<u32> {
+        match index {
+            CT::C(c) => self.line(&format!("i32.const {}", c.constant()))?,
+            CT::T(t) => self.line(&format!("local.get $t{}", t.temp()))?,
+        }
+        self.line("i32.const 4")?;
+        self.line("i32.mul")?;
+
+        match v.scope() {
+            _ if v.access() == Access::Indirect => {
+                self.line(&format!("local.get ${}", v.id()))?;
+                self.line("i32.add")?;
+                Ok(0)
+            }
+            Scope::Global => Ok(self.vectors[v.id()]),
+            _ => {
+                self.line("local.get $cadre")?;
+                self.line("i32.add")?;
+                Ok(self.frame[v.id()])
+            }
+        }
+    }
+
+    /// Pushes a constant or the value of a temporary or a variable.
+    fn load(&mut self, ctv: &CTV) -> io::Result<()> {
+        let v = match ctv {
+            CTV::C(c) => return self.line(&format!("i32.const {}", c.constant())),
+            CTV::T(t) => return self.line(&format!("local.get $t{}", t.temp())),
+            CTV::V(v) => v,
+        };
+
+        match (v.scope(), v.indice()) {
+            (_, Some(index)) => {
+                let offset = self.address(v, index)?;
+                self.line(&format!("i32.load offset={}", offset))
+            }
+            // The address of a vector given as an argument.
+            (Scope::Global, None) if v.access() == Access::AddressOf => {
+                self.line(&format!("i32.const {}", self.vectors[v.id()]))
+            }
+            (Scope::Local, None) if v.access() == Access::AddressOf => {
+                self.line("local.get $cadre")?;
+                self.line(&format!("i32.const {}", self.frame[v.id()]))?;
+                self.line("i32.add")
+            }
+            (Scope::Global, None) => self.line(&format!("global.get ${}", v.id())),
+            _ => self.line(&format!("local.get ${}", v.id())),
+        }
+    }
+
+    /// Stores the value pushed by `value` in a temporary or a variable.
+    fn store<F>(&mut self, result: &TV, value: F) -> io::Result<()>
+    where
+        F: FnOnce(&mut Self) -> io::Result<()>,
+    {
+        let v = match result {
+            TV::T(t) => {
+                value(self)?;
+                return self.line(&format!("local.set $t{}", t.temp()));
+            }
+            TV::V(v) => v,
+        };
+
+        match (v.scope(), v.indice()) {
+            (_, Some(index)) => {
+                let offset = self.address(v, index)?;
+                value(self)?;
+                self.line(&format!("i32.store offset={}", offset))
+            }
+            (Scope::Global, None) => {
+                value(self)?;
+                self.line(&format!("global.set ${}", v.id()))
+            }
+            _ => {
+                value(self)?;
+                self.line(&format!("local.set ${}", v.id()))
+            }
+        }
+    }
+
+    /// The local vectors of a function and their sizes, in the order of their allocations.
+    fn local_vectors(&self, instructions: &[Instruction]) -> Vec<(String, u32)> {
+        let label = instructions[0]
+            .label
+            .as_ref()
+            .expect("a function begins with its label");
+        let table = match self.global(label.label()) {
+            SymbolKind::Function { symbol_table, .. } => *symbol_table,
+            _ => unreachable!(),
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instr| match &instr.kind {
+                InstructionKind::Allocation {
+                    variable: Some(variable),
+                    size,
+                } => {
+                    let vector = self.symbol_table.tables[table]
+                        .symbols
+                        .iter()
+                        .any(|symbol| match symbol.kind {
+                            SymbolKind::Vector { .. } => symbol.id == variable.id()[1..],
+                            _ => false,
+                        });
+                    if vector {
+                        Some((variable.id().to_owned(), size.constant() as u32))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The arguments of a function in the order of their addresses.
+    fn arguments(&self, label: &Label) -> Vec<&'a Symbol> {
+        let table = match self.global(label.label()) {
+            SymbolKind::Function { symbol_table, .. } => *symbol_table,
+            _ => unreachable!(),
+        };
+
+        self.symbol_table.arguments(table)
+    }
+
+    /// The global symbol of an identifier of the three address code, the label of a function
+    /// or `v` followed by the identifier of a variable.
+    fn global(&self, id: &str) -> &'a SymbolKind {
+        &self
+            .symbol_table
+            .global_symbol(id)
+            .expect("globals are declared")
+            .kind
+    }
+}
@@ -0,0 +1,20 @@
+  ;; Fournies par l'hôte : lire donne l'entier de la ligne suivante, ecrire affiche un entier.
+  (import "l" "lire" (func $lire (result i32)))
+  (import "l" "ecrire" (func $ecrire (param i32)))
+
+  ;; La division par zéro arrête le programme, -2147483648 / -1 donne -2147483648.
+  (func $division (param $gauche i32) (param $droite i32) (result i32)
+    (if (i32.eqz (local.get $droite))
+      (then unreachable))
+    (if (i32.eq (local.get $droite) (i32.const -1))
+      (then (return (i32.sub (i32.const 0) (local.get $gauche)))))
+    (i32.div_s (local.get $gauche) (local.get $droite)))
+
+  ;; Met à zéro la mémoire de $debut à $fin, où sont rangés les tableaux locaux d'un appel.
+  (func $mise_a_zero (param $debut i32) (param $fin i32)
+    (block $fini
+      (loop $suivant
+        (br_if $fini (i32.ge_u (local.get $debut) (local.get $fin)))
+        (i32.store (local.get $debut) (i32.const 0))
+        (local.set $debut (i32.add (local.get $debut) (i32.const 4)))
+        (br $suivant))))
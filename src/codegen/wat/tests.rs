@@ -0,0 +1,307 @@
+use super::Wat;
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::run_three_address_code::{self, RunThreeAddressCode};
+use crate::semantic_analyser::Analyse;
+use std::fs::{read_dir, read_to_string};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use wasmi::{
+    Error, Externals, FuncInstance, FuncRef, ImportsBuilder, Module, ModuleImportResolver,
+    ModuleInstance, RuntimeArgs, RuntimeValue, Signature, Trap, TrapKind,
+};
+
+#[test]
+fn affect() {
+    test("affect");
+}
+
+#[test]
+fn boolean_booleen() {
+    test("boolean-booleen");
+}
+
+#[test]
+fn boucle() {
+    test("boucle");
+}
+
+#[test]
+fn early_exit_and_booleen() {
+    test("early-exit-and-booleen");
+}
+
+#[test]
+fn early_exit_or_booleen() {
+    test("early-exit-or-booleen");
+}
+
+#[test]
+fn ecrire() {
+    test("ecrire");
+}
+
+#[test]
+fn expression() {
+    test("expression");
+}
+
+#[test]
+fn function_in_function_args() {
+    test("function-in-function-args");
+}
+
+#[test]
+fn global() {
+    test("global");
+}
+
+#[test]
+fn lire() {
+    test("lire");
+}
+
+#[test]
+fn local_shadowing() {
+    test("local-shadowing");
+}
+
+#[test]
+fn lire_invalide() {
+    test("lire-invalide");
+}
+
+#[test]
+fn local() {
+    test("local");
+}
+
+#[test]
+fn max() {
+    test("max");
+}
+
+#[test]
+fn operation() {
+    test("operation");
+}
+
+#[test]
+fn param_shadowing() {
+    test("param-shadowing");
+}
+
+#[test]
+fn param() {
+    test("param");
+}
+
+#[test]
+fn recursion_mutuelle() {
+    test("recursion-mutuelle");
+}
+
+#[test]
+fn return_middle() {
+    test("return-middle");
+}
+
+#[test]
+fn return_() {
+    test("return");
+}
+
+#[test]
+fn surcharge() {
+    test("surcharge");
+}
+
+#[test]
+fn si_false() {
+    test("si-false");
+}
+
+#[test]
+fn si_sinon_false() {
+    test("si-sinon-false");
+}
+
+#[test]
+fn si_sinon_true() {
+    test("si-sinon-true");
+}
+
+#[test]
+fn si_true() {
+    test("si-true");
+}
+
+#[test]
+fn tableau_acces_complexe() {
+    test("tableau-acces-complexe");
+}
+
+#[test]
+fn tableau_acces() {
+    test("tableau-acces");
+}
+
+#[test]
+fn tableau_local() {
+    test("tableau-local");
+}
+
+#[test]
+fn tri_reference() {
+    test("tri-reference");
+}
+
+#[test]
+fn tantque() {
+    test("tantque");
+}
+
+#[test]
+fn tri() {
+    test("tri");
+}
+
+#[test]
+fn division_by_zero() {
+    match run("tests/resources/division-by-zero.l", "") {
+        Err(Error::Trap(trap)) => match trap.kind() {
+            TrapKind::Unreachable => {}
+            _ => panic!("expected a division by zero"),
+        },
+        _ => panic!("expected a division by zero"),
+    }
+}
+
+/// The programs of the resources which pass the analysis behave as with the interpreter, but
+/// for the value of a call without retour.
+#[test]
+fn resources() {
+    let input = "3\n14\n-15\n92\n65\n";
+
+    for entry in read_dir("tests/resources").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some("l".as_ref()) {
+            continue;
+        }
+
+        let l_file = read_to_string(&path).unwrap();
+        let program = match Parser::new().parse(Lexer::new(&l_file)) {
+            Ok(program) => program,
+            Err(_) => continue,
+        };
+        let symbol_table = match program.analyse() {
+            Ok((symbol_table, _)) => symbol_table,
+            Err(_) => continue,
+        };
+
+        let mut expected = Vec::new();
+        let interpreted = program
+            .gen_three_address_code(&symbol_table, 0)
+            .run(&mut input.as_bytes(), &mut expected);
+        // A call without retour gives 0 instead of stopping the program.
+        if let Err(run_three_address_code::RuntimeError::MissingReturn(_)) = interpreted {
+            continue;
+        }
+
+        let filename = path.to_str().unwrap();
+        println!("{}", filename);
+        match run(filename, input) {
+            Ok(output) => assert_eq!(String::from_utf8(expected).unwrap(), output),
+            Err(_) => assert!(interpreted.is_err()),
+        }
+    }
+}
+
+fn test(filename: &str) {
+    let in_file = format!("testor/{}.in", filename);
+    let input = if Path::new(&in_file).is_file() {
+        read_to_string(in_file).unwrap()
+    } else {
+        String::new()
+    };
+    let out_file = read_to_string(format!("testor/{}.out", filename)).unwrap();
+
+    let output = run(&format!("testor/{}.l", filename), &input).unwrap();
+
+    print!("{}", output);
+
+    assert_eq!(out_file.trim_end(), output.trim_end());
+}
+
+/// Compiles a program to WebAssembly and runs it with `wasmi`.
+fn run(filename: &str, input: &str) -> Result<String, Error> {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+    let mut wat = Vec::new();
+
+    program
+        .gen_three_address_code(&symbol_table, 0)
+        .to_wat(&symbol_table, &mut wat)
+        .unwrap();
+
+    let wasm = wat::parse_bytes(&wat).unwrap();
+    let module = Module::from_buffer(&wasm)?;
+    let imports = ImportsBuilder::new().with_resolver("l", &Resolver);
+    let instance = ModuleInstance::new(&module, &imports)?.assert_no_start();
+
+    let mut host = Host {
+        input: &mut input.as_bytes(),
+        output: Vec::new(),
+    };
+    instance.invoke_export("main", &[], &mut host)?;
+
+    Ok(String::from_utf8(host.output).unwrap())
+}
+
+const LIRE: usize = 0;
+const ECRIRE: usize = 1;
+
+/// `lire` and `ecrire`, which read and write lines as the interpreter does.
+struct Host<'a> {
+    input: &'a mut dyn BufRead,
+    output: Vec<u8>,
+}
+
+impl Externals for Host<'_> {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        match index {
+            LIRE => {
+                let value = run_three_address_code::read(self.input).unwrap();
+                Ok(Some(RuntimeValue::I32(value)))
+            }
+            ECRIRE => {
+                let value: i32 = args.nth(0);
+                writeln!(self.output, "{}", value).unwrap();
+                Ok(None)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Gives the functions imported from `l`.
+struct Resolver;
+
+impl ModuleImportResolver for Resolver {
+    fn resolve_func(&self, field_name: &str, signature: &Signature) -> Result<FuncRef, Error> {
+        let index = match field_name {
+            "lire" => LIRE,
+            "ecrire" => ECRIRE,
+            _ => return Err(Error::Instantiation(field_name.to_owned())),
+        };
+
+        Ok(FuncInstance::alloc_host(signature.clone(), index))
+    }
+}
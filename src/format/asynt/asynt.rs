@@ -44,17 +44,11 @@ impl Asynt for Program {
         "prog"
     }
     fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
-        use Statement::*;
+        use StatementKind::*;
 
-        let var = self
-            .0
-            .iter()
-            .filter(|s| if let DclVariable(..) = s { true } else { false });
+        let var = self.0.iter().filter(|s| matches!(s.kind, DclVariable(..)));
 
-        let func = self
-            .0
-            .iter()
-            .filter(|s| if let DclFunction(..) = s { true } else { false });
+        let func = self.0.iter().filter(|s| matches!(s.kind, DclFunction(..)));
 
         (&var).to_asynt(f, indent)?;
         (&func).to_asynt(f, indent)
@@ -81,27 +75,24 @@ impl<'a, 'b: 'a, I: Iterator<Item = &'b Statement> + Clone> Asynt for &'a I {
 
 impl Asynt for Statement {
     fn name(&self) -> &'static str {
-        use Statement::*;
+        use StatementKind::*;
 
-        match self {
+        match self.kind {
             DclFunction(..) => "foncDec",
             _ => unreachable!(),
         }
     }
 
     fn with_tag(&self) -> bool {
-        use Statement::*;
+        use StatementKind::*;
 
-        match self {
-            DclVariable(..) => false,
-            _ => true,
-        }
+        !matches!(self.kind, DclVariable(..))
     }
 
     fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
-        use Statement::*;
+        use StatementKind::*;
 
-        match self {
+        match &self.kind {
             DclVariable(v) => v.to_asynt(f, indent),
             DclFunction(id, p, v, i) => {
                 let spaces = " ".repeat(indent);
@@ -115,7 +106,23 @@ impl Asynt for Statement {
     }
 }
 
-impl Asynt for [Scalar] {
+impl Asynt for [Argument] {
+    fn name(&self) -> &'static str {
+        "l_dec"
+    }
+
+    fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
+        match self.len() {
+            0 => Ok(()),
+            _ => {
+                self[0].to_asynt(f, indent)?;
+                self[1..].to_asynt(f, indent)
+            }
+        }
+    }
+}
+
+impl Asynt for [Variable] {
     fn name(&self) -> &'static str {
         "l_dec"
     }
@@ -146,6 +153,26 @@ impl Asynt for Variable {
     }
 }
 
+impl Asynt for Argument {
+    fn name(&self) -> &'static str {
+        match self {
+            Argument::Scalar(_) => "varDec",
+            Argument::Vector(_) => "tabDec",
+        }
+    }
+
+    fn one_line(&self) -> bool {
+        true
+    }
+
+    fn content(&self, f: &mut dyn Write, _indent: usize) -> Result<()> {
+        match self {
+            Argument::Scalar(s) => write!(f, "{}", s.1),
+            Argument::Vector(v) => write!(f, "{}[]", v.1),
+        }
+    }
+}
+
 impl Asynt for Scalar {
     fn name(&self) -> &'static str {
         "varDec"
@@ -197,9 +224,9 @@ impl Asynt for [Instruction] {
 
 impl Asynt for Instruction {
     fn name(&self) -> &'static str {
-        use Instruction::*;
+        use InstructionKind::*;
 
-        match self {
+        match self.kind {
             Affectation(..) => "instr_affect",
             CallFunction(..) => "instr_appel",
             Return(..) => "instr_retour",
@@ -212,18 +239,15 @@ impl Asynt for Instruction {
     }
 
     fn hide(&self) -> bool {
-        use Instruction::*;
+        use InstructionKind::*;
 
-        match self {
-            NOP => true,
-            _ => false,
-        }
+        matches!(self.kind, NOP)
     }
 
     fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
-        use Instruction::*;
+        use InstructionKind::*;
 
-        match self {
+        match &self.kind {
             Affectation(lv, e) => {
                 lv.to_asynt(f, indent)?;
                 e.to_asynt(f, indent)
@@ -253,27 +277,27 @@ impl Asynt for Instruction {
 
 impl Asynt for LeftValue {
     fn name(&self) -> &'static str {
-        use LeftValue::*;
+        use LeftValueKind::*;
 
-        match self {
+        match self.kind {
             Variable(..) => "var_simple",
             VariableAt(..) => "var_indicee",
         }
     }
 
     fn one_line(&self) -> bool {
-        use LeftValue::*;
+        use LeftValueKind::*;
 
-        match self {
+        match self.kind {
             Variable(..) => true,
             VariableAt(..) => false,
         }
     }
 
     fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
-        use LeftValue::*;
+        use LeftValueKind::*;
 
-        match self {
+        match &self.kind {
             Variable(id) => write!(f, "{}", id),
             VariableAt(id, e) => {
                 let spaces = " ".repeat(indent);
@@ -317,9 +341,9 @@ impl Asynt for [Expression] {
 
 impl Asynt for Expression {
     fn name(&self) -> &'static str {
-        use Expression::*;
+        use ExpressionKind::*;
 
-        match self {
+        match self.kind {
             Value(..) => "intExp",
             LeftValue(..) => "varExp",
             CallFunction(..) => "appelExp",
@@ -330,18 +354,15 @@ impl Asynt for Expression {
     }
 
     fn one_line(&self) -> bool {
-        use Expression::*;
+        use ExpressionKind::*;
 
-        match self {
-            Value(..) => true,
-            _ => false,
-        }
+        matches!(self.kind, Value(..))
     }
 
     fn content(&self, f: &mut dyn Write, indent: usize) -> Result<()> {
-        use Expression::*;
+        use ExpressionKind::*;
 
-        match self {
+        match &self.kind {
             Value(v) => write!(f, "{}", v),
             LeftValue(lv) => lv.to_asynt(f, indent),
             CallFunction(cf) => cf.to_asynt(f, indent),
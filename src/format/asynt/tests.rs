@@ -74,11 +74,21 @@ fn si() {
     test("si");
 }
 
+#[test]
+fn si_booleen() {
+    test("si-booleen");
+}
+
 #[test]
 fn sinon() {
     test("sinon");
 }
 
+#[test]
+fn sinon_booleen() {
+    test("sinon-booleen");
+}
+
 #[test]
 fn tableau2() {
     test("tableau2");
@@ -89,16 +99,36 @@ fn tableau() {
     test("tableau");
 }
 
+#[test]
+fn tableau_local() {
+    test("tableau-local");
+}
+
+#[test]
+fn tableau_argument() {
+    test("tableau-argument");
+}
+
 #[test]
 fn tantque0() {
     test("tantque0");
 }
 
+#[test]
+fn tantque0_booleen() {
+    test("tantque0-booleen");
+}
+
 #[test]
 fn tantque() {
     test("tantque");
 }
 
+#[test]
+fn tantque_booleen() {
+    test("tantque-booleen");
+}
+
 #[test]
 fn lexunits() {
     test("lexunits");
@@ -124,6 +154,11 @@ fn sommeneg() {
     test("sommeneg");
 }
 
+#[test]
+fn sommeneg_booleen() {
+    test("sommeneg-booleen");
+}
+
 #[test]
 fn lex_err() {
     test("lex-err");
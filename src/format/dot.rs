@@ -0,0 +1,62 @@
+use crate::cfg::ControlFlowGraph;
+use crate::format::three_a::ThreeA;
+use crate::three_address_code::ThreeAddressCode;
+use std::io;
+use std::io::Write;
+
+pub trait Dot {
+    fn dot(&self, three_address_code: &ThreeAddressCode, f: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Each function is a cluster, dashed edges link the blocks to their immediate dominator.
+impl Dot for ControlFlowGraph {
+    fn dot(&self, three_address_code: &ThreeAddressCode, f: &mut dyn Write) -> io::Result<()> {
+        let instructions = &three_address_code.instructions;
+
+        writeln!(f, "digraph cfg {{")?;
+        writeln!(f, "\tnode [shape=box, fontname=monospace];")?;
+
+        for function in &self.functions {
+            let name = function.label.label();
+
+            writeln!(f, "\tsubgraph cluster_{} {{", name)?;
+            writeln!(f, "\t\tlabel=\"{}\";", name)?;
+
+            for (b, block) in function.blocks.iter().enumerate() {
+                write!(f, "\t\t{}_{} [label=\"", name, b)?;
+                for instr in &instructions[block.instructions.clone()] {
+                    if let Some(label) = &instr.label {
+                        write!(f, "{}:\\l", label.label())?;
+                    }
+
+                    let mut line = Vec::new();
+                    instr.kind.three_a(&mut line)?;
+                    if !line.is_empty() {
+                        write!(f, "    {}\\l", String::from_utf8_lossy(&line))?;
+                    }
+                }
+                writeln!(f, "\"];")?;
+            }
+
+            for (b, block) in function.blocks.iter().enumerate() {
+                for successor in &block.successors {
+                    writeln!(f, "\t\t{0}_{1} -> {0}_{2};", name, b, successor)?;
+                }
+            }
+
+            for (b, dominator) in function.dominators.iter().enumerate() {
+                if let Some(dominator) = dominator {
+                    writeln!(
+                        f,
+                        "\t\t{0}_{1} -> {0}_{2} [style=dashed, color=gray];",
+                        name, dominator, b
+                    )?;
+                }
+            }
+
+            writeln!(f, "\t}}")?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
@@ -28,8 +28,8 @@ impl Token {
         let name = match self {
             Number(_) => "nombre",
             Id(_) => "identificateur",
-            IntegerType | ReadFunction | WriteFunction | Return | If | Then | Else | While | Do
-            | For => "mot_clef",
+            IntegerType | BooleanType | ReadFunction | WriteFunction | Return | If | Then
+            | Else | While | Do | For => "mot_clef",
             Comma | Semicolon | OpenParenthesis | CloseParenthesis | OpenCurlyBracket
             | CloseCurlyBracket | OpenSquareBracket | CloseSquareBracket | Addition
             | Subtraction | Multiplication | Division | LessThan | Equal | And | Or | Not => {
@@ -55,6 +55,7 @@ impl Token {
 
             // Types
             IntegerType => "entier",
+            BooleanType => "booleen",
 
             // Predefined functions
             ReadFunction => "lire",
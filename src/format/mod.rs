@@ -1,4 +1,5 @@
 pub mod asynt;
+pub mod dot;
 pub mod lex;
 pub mod tab;
 pub mod three_a;
@@ -1,3 +1,4 @@
+use crate::ast::Type;
 use crate::symbol_table::*;
 use std::io;
 use std::io::Write;
@@ -53,8 +54,16 @@ impl AsTab for Symbol {
         use SymbolKind::*;
 
         let (scope, kind, additional) = match self.kind {
-            Scalar { scope } => (scope, "ENTIER", 1),
-            Vector { scope, size } => (scope, "TABLEAU", size as usize),
+            Scalar {
+                scope,
+                ty: Type::Integer,
+            } => (scope, "ENTIER", 1),
+            Scalar {
+                scope,
+                ty: Type::Boolean,
+            } => (scope, "BOOLEEN", 1),
+            // The size of a vector argument is unknown.
+            Vector { scope, size, .. } => (scope, "TABLEAU", size.unwrap_or(0) as usize),
             Function { nb_arguments, .. } => (Scope::Global, "FONCTION", nb_arguments),
         };
 
@@ -114,6 +114,11 @@ fn already_declared_function_err() {
     test("already-declared-function-err");
 }
 
+#[test]
+fn already_declared_main_err() {
+    test("already-declared-main-err");
+}
+
 #[test]
 fn function_polymorphism() {
     test("function-polymorphism");
@@ -129,6 +134,31 @@ fn invalid_function_argument_more() {
     test("invalid-function-argument-more");
 }
 
+#[test]
+fn forward_call() {
+    test("forward-call");
+}
+
+#[test]
+fn booleen() {
+    test("booleen");
+}
+
+#[test]
+fn tableau_local() {
+    test("tableau-local");
+}
+
+#[test]
+fn tableau_argument() {
+    test("tableau-argument");
+}
+
+#[test]
+fn type_conversion_err() {
+    test("type-conversion-err");
+}
+
 #[test]
 fn undeclared_function() {
     test("undeclared-function");
@@ -144,7 +174,8 @@ fn test(filename: &str) {
         let tab_file = read(tab_file).unwrap();
         let mut generated_tab = Vec::with_capacity(tab_file.capacity());
 
-        analyse.unwrap().as_table(&mut generated_tab).unwrap();
+        let (symbol_table, _) = analyse.unwrap();
+        symbol_table.as_table(&mut generated_tab).unwrap();
 
         print!("{}", String::from_utf8_lossy(&generated_tab));
 
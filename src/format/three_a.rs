@@ -33,12 +33,16 @@ impl ThreeA for Label {
 impl ThreeA for Temp {
     fn three_a(&self, f: &mut dyn Write) -> io::Result<()> {
         write!(f, "t{}", self.temp())
-        // write!(f, "t{}{{{}}}", self.temp(), self.last_use())
     }
 }
 
 impl ThreeA for Variable {
     fn three_a(&self, f: &mut dyn Write) -> io::Result<()> {
+        match self.access() {
+            Access::Value => {}
+            Access::AddressOf => write!(f, "&")?,
+            Access::Indirect => write!(f, "*")?,
+        }
         write!(f, "{}", self.id())?;
 
         if let Some(indice) = self.indice() {
@@ -1,5 +1,6 @@
 use crate::ast;
 use crate::symbol_table::Scope;
+use crate::symbol_table::Symbol;
 use crate::symbol_table::SymbolKind;
 use crate::symbol_table::SymbolTable;
 use crate::three_address_code::*;
@@ -40,125 +41,47 @@ impl<'t> Data<'t> {
     }
 
     fn add_instr(&mut self, instr: Instruction) {
-        use InstructionKind::*;
-
-        let last_use = self.instructions.len() as i32;
-
-        let ct_set_last_use = |ct: &CT| {
-            if let CT::T(t) = ct {
-                t.set_last_use(last_use);
-            }
-        };
-        let v_set_last_use = |v: &Variable| {
-            if let Some(indice) = v.indice() {
-                ct_set_last_use(indice);
-            }
-        };
-        let tv_set_last_use = |tv: &TV, is_right: bool| match tv {
-            TV::T(t) => {
-                if is_right {
-                    t.set_last_use(last_use)
-                }
-            }
-            TV::V(v) => v_set_last_use(v),
-        };
-        let ctv_set_last_use = |ctv: &CTV, is_right: bool| match ctv {
-            CTV::T(t) => {
-                if is_right {
-                    t.set_last_use(last_use)
-                }
-            }
-            CTV::V(v) => v_set_last_use(v),
-            _ => {}
-        };
-
-        match &instr.kind {
-            Arithmetic {
-                operator: _,
-                left,
-                right,
-                result,
-            } => {
-                ctv_set_last_use(left, true);
-                ctv_set_last_use(right, true);
-                tv_set_last_use(result, false);
-            }
-            Affectation { value, result } => {
-                ctv_set_last_use(value, true);
-                tv_set_last_use(result, false);
-            }
-            Allocation { .. } => {}
-            ReadFunction { result } => {
-                tv_set_last_use(result, false);
-            }
-            WriteFunction { value } => {
-                ctv_set_last_use(value, true);
-            }
-            FunctionCall {
-                function: _,
-                result,
-            } => {
-                tv_set_last_use(result, false);
-            }
-            FunctionBegin => {}
-            FunctionEnd => {}
-            FunctionPushArg { arg } => {
-                ctv_set_last_use(arg, true);
-            }
-            FunctionReturn { value } => {
-                ctv_set_last_use(value, true);
-            }
-            Jump { .. } => {}
-            JumpIf {
-                condition: _,
-                left,
-                right,
-                label: _,
-            } => {
-                ctv_set_last_use(left, true);
-                ctv_set_last_use(right, true);
-            }
-            NOP => {}
-        }
-
         self.instructions.push(instr);
     }
 
     fn find_info(&self, id: &String) -> (usize, Scope) {
-        let symbol = self
-            .symbol_table
+        let symbol = self.variable(id);
+
+        (
+            symbol.address,
+            symbol.scope().expect("functions are skipped"),
+        )
+    }
+
+    fn variable(&self, id: &String) -> &'t Symbol {
+        let symbol_table = self.symbol_table;
+
+        symbol_table
             .iter(self.current_table)
-            .find(|symbol| !symbol.is_function() && symbol.id == *id);
-
-        match symbol {
-            Some(symbol) => (
-                symbol.address,
-                match symbol.kind {
-                    SymbolKind::Scalar { scope } => scope,
-                    SymbolKind::Vector { scope, .. } => scope,
-                    SymbolKind::Function { .. } => unreachable!(),
-                },
-            ),
-            None => unreachable!(),
-        }
+            .find(|symbol| !symbol.is_function() && symbol.id == *id)
+            .expect("variables are declared")
     }
 
-    fn enter_function(&mut self, id: &String) {
-        use crate::symbol_table::SymbolKind;
+    fn function(&self, id: &str, nb_arguments: usize) -> &'t Symbol {
+        let symbol_table = self.symbol_table;
 
-        let symbol = self
-            .symbol_table
+        symbol_table
             .iter(self.current_table)
-            .find(|symbol| symbol.id == *id && symbol.is_function());
+            .find(|symbol| symbol.is_function_of(id, nb_arguments))
+            .expect("functions are declared")
+    }
 
-        match symbol {
-            Some(symbol) => match symbol.kind {
-                SymbolKind::Function { symbol_table, .. } => {
-                    self.current_table = symbol_table;
-                }
-                _ => unreachable!(),
-            },
-            None => unreachable!(),
+    /// The label of the function `id` taking `nb_arguments` arguments.
+    fn function_label(&self, id: &str, nb_arguments: usize) -> Label {
+        let symbol = self.function(id, nb_arguments);
+
+        Label::new(self.symbol_table.function_label(symbol))
+    }
+
+    fn enter_function(&mut self, id: &str, nb_arguments: usize) {
+        match self.function(id, nb_arguments).kind {
+            SymbolKind::Function { symbol_table, .. } => self.current_table = symbol_table,
+            _ => unreachable!(),
         }
     }
 
@@ -169,10 +92,10 @@ impl<'t> Data<'t> {
     }
 }
 
-impl<'t> Into<ThreeAddressCode> for Data<'t> {
-    fn into(self) -> ThreeAddressCode {
+impl<'t> From<Data<'t>> for ThreeAddressCode {
+    fn from(d: Data<'t>) -> Self {
         ThreeAddressCode {
-            instructions: self.instructions,
+            instructions: d.instructions,
         }
     }
 }
@@ -186,22 +109,23 @@ impl<T: Gen<()>> Gen<()> for [T] {
 }
 
 impl Gen<()> for ast::Program {
-    fn gen(&self, d: &mut Data) -> () {
+    fn gen(&self, d: &mut Data) {
         self.0.gen(d);
     }
 }
 
 impl Gen<()> for ast::Statement {
-    fn gen(&self, d: &mut Data) -> () {
-        use ast::Statement::*;
+    fn gen(&self, d: &mut Data) {
+        use ast::StatementKind::*;
 
-        match self {
+        match &self.kind {
             DclVariable(v) => v.gen(d),
-            DclFunction(id, _, vars, instructions) => {
-                d.enter_function(id);
+            DclFunction(id, args, vars, instructions) => {
+                let label = d.function_label(id, args.len());
+                d.enter_function(id, args.len());
 
                 d.add_instr(Instruction {
-                    label: Some(Label::new(format!("f{}", id))),
+                    label: Some(label),
                     kind: InstructionKind::FunctionBegin,
                     comment: Some(format!("début fonction {}", id)),
                 });
@@ -222,7 +146,7 @@ impl Gen<()> for ast::Statement {
 }
 
 impl Gen<()> for ast::Variable {
-    fn gen(&self, d: &mut Data) -> () {
+    fn gen(&self, d: &mut Data) {
         use ast::Variable::*;
 
         match self {
@@ -233,8 +157,8 @@ impl Gen<()> for ast::Variable {
 }
 
 impl Gen<()> for ast::Scalar {
-    fn gen(&self, d: &mut Data) -> () {
-        let (t, id) = self;
+    fn gen(&self, d: &mut Data) {
+        let (t, id, _) = self;
         let (address, scope) = d.find_info(id);
 
         d.add_instr(Instruction {
@@ -249,8 +173,8 @@ impl Gen<()> for ast::Scalar {
 }
 
 impl Gen<()> for ast::Vector {
-    fn gen(&self, d: &mut Data) -> () {
-        let (t, size, id) = self;
+    fn gen(&self, d: &mut Data) {
+        let (t, size, id, _) = self;
         let (address, scope) = d.find_info(id);
 
         d.add_instr(Instruction {
@@ -265,10 +189,10 @@ impl Gen<()> for ast::Vector {
 }
 
 impl Gen<()> for ast::Instruction {
-    fn gen(&self, d: &mut Data) -> () {
-        use ast::Instruction::*;
+    fn gen(&self, d: &mut Data) {
+        use ast::InstructionKind::*;
 
-        match self {
+        match &self.kind {
             Affectation(lv, e) => {
                 let result = lv.gen(d).into();
                 let value = e.gen(d);
@@ -416,9 +340,9 @@ impl Gen<()> for ast::Instruction {
 
 impl Gen<CTV> for ast::Expression {
     fn gen(&self, d: &mut Data) -> CTV {
-        use ast::Expression::*;
+        use ast::ExpressionKind::*;
 
-        match self {
+        match &self.kind {
             Value(v) => Constant::new(*v).into(),
             LeftValue(lv) => lv.gen(d).into(),
             CallFunction(c) => c.gen(d),
@@ -660,16 +584,19 @@ impl Gen<CTV> for ast::Expression {
 
 impl Gen<Variable> for ast::LeftValue {
     fn gen(&self, d: &mut Data) -> Variable {
-        let id = match self {
-            ast::LeftValue::Variable(id) => id,
-            ast::LeftValue::VariableAt(id, _) => id,
-        };
-
-        let (address, scope) = d.find_info(id);
-
-        match self {
-            ast::LeftValue::Variable(id) => Variable::new(format!("v{}", id), None, address, scope),
-            ast::LeftValue::VariableAt(id, indice) => {
+        let (address, scope) = d.find_info(self.id());
+        let vector = matches!(d.variable(self.id()).kind, SymbolKind::Vector { .. });
+
+        match &self.kind {
+            // A vector without indice is given as an argument.
+            ast::LeftValueKind::Variable(id) if vector => {
+                Variable::new(format!("v{}", id), None, address, scope)
+                    .with_access(Access::AddressOf)
+            }
+            ast::LeftValueKind::Variable(id) => {
+                Variable::new(format!("v{}", id), None, address, scope)
+            }
+            ast::LeftValueKind::VariableAt(id, indice) => {
                 let indice = indice.gen(d);
 
                 let indice = match indice {
@@ -689,7 +616,11 @@ impl Gen<Variable> for ast::LeftValue {
                     }
                 };
 
-                Variable::new(format!("v{}", id), Some(indice), address, scope)
+                let access = match (vector, scope) {
+                    (true, Scope::Argument) => Access::Indirect,
+                    _ => Access::Value,
+                };
+                Variable::new(format!("v{}", id), Some(indice), address, scope).with_access(access)
             }
         }
     }
@@ -717,12 +648,13 @@ impl Gen<CTV> for ast::CallFunction {
             });
         }
 
+        let function = d.function_label(id, arguments.len());
         let result = d.new_temp();
 
         d.add_instr(Instruction {
             label: None,
             kind: InstructionKind::FunctionCall {
-                function: Label::new(format!("f{}", id)),
+                function,
                 result: result.clone().into(),
             },
             comment: Some(format!("fin appel {}", id)),
@@ -0,0 +1,340 @@
+#[cfg(test)]
+mod tests;
+
+use crate::ast::*;
+use crate::run_three_address_code::read;
+use crate::semantic_analyser::Analyse;
+use crate::symbol_table::{Symbol, SymbolKind, SymbolTable};
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Fail)]
+pub enum RuntimeError {
+    #[fail(display = "{}", _0)]
+    Semantic(failure::Error),
+    #[fail(display = "division by zero")]
+    DivisionByZero,
+    #[fail(display = "index {} is out of the bounds of {}", _1, _0)]
+    IndexOutOfBounds(Id, Number),
+    #[fail(display = "function {} ended without retour", _0)]
+    MissingReturn(Id),
+    #[fail(display = "{}", _0)]
+    Io(#[cause] io::Error),
+}
+
+impl From<io::Error> for RuntimeError {
+    fn from(error: io::Error) -> Self {
+        RuntimeError::Io(error)
+    }
+}
+
+/// Analyses the program then evaluates it from `main`, `lire` reading lines of `input` and
+/// `ecrire` writing to `output`. Returns the value given to `retour` by `main`, 0 without one.
+pub fn run(
+    program: &Program,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<i32, RuntimeError> {
+    let (symbol_table, _) = program.analyse().map_err(RuntimeError::Semantic)?;
+
+    Interpreter::new(program, &symbol_table, input, output)
+        .call(&"main".to_owned(), Vec::new())
+        .map(|value| value.unwrap_or(0))
+}
+
+/// What to do after an instruction.
+enum Flow {
+    Next,
+    Return(Number),
+}
+
+/// Variables are identified by their table and their index in it.
+type Key = (usize, usize);
+type Memory = HashMap<Key, Vec<Number>>;
+
+/// A variable of a frame, or a global one without frame.
+type Location = (Option<usize>, Key);
+
+/// The value given for an argument, a vector being given by reference.
+enum Value {
+    Scalar(Number),
+    Vector(Location),
+}
+
+struct Frame {
+    table: usize,
+    memory: Memory,
+    /// The vectors referenced by the vector arguments.
+    references: HashMap<Key, Location>,
+}
+
+/// The arguments and the instructions of a function.
+type Function<'a> = (&'a [Argument], &'a [Instruction]);
+
+struct Interpreter<'a> {
+    /// The functions by identifier and number of arguments, as they are overloaded on it.
+    functions: HashMap<(&'a str, usize), Function<'a>>,
+    symbol_table: &'a SymbolTable,
+    globals: Memory,
+    frames: Vec<Frame>,
+    input: &'a mut dyn BufRead,
+    output: &'a mut dyn Write,
+}
+
+impl<'a> Interpreter<'a> {
+    fn new(
+        program: &'a Program,
+        symbol_table: &'a SymbolTable,
+        input: &'a mut dyn BufRead,
+        output: &'a mut dyn Write,
+    ) -> Self {
+        let functions = program
+            .0
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                StatementKind::DclFunction(id, arguments, _, instructions) => Some((
+                    (id.as_str(), arguments.len()),
+                    (arguments.as_slice(), instructions.as_slice()),
+                )),
+                StatementKind::DclVariable(_) => None,
+            })
+            .collect();
+
+        Self {
+            functions,
+            symbol_table,
+            globals: Memory::new(),
+            frames: Vec::new(),
+            input,
+            output,
+        }
+    }
+
+    /// Returns the value given to `retour`, if any.
+    fn call(&mut self, id: &Id, arguments: Vec<Value>) -> Result<Option<Number>, RuntimeError> {
+        let (parameters, instructions) = self.functions[&(id.as_str(), arguments.len())];
+        let table = self
+            .symbol_table
+            .global()
+            .symbols
+            .iter()
+            .find_map(|symbol| match symbol.kind {
+                SymbolKind::Function { symbol_table, .. }
+                    if symbol.is_function_of(id, arguments.len()) =>
+                {
+                    Some(symbol_table)
+                }
+                _ => None,
+            })
+            .unwrap();
+
+        let mut frame = Frame {
+            table,
+            memory: Memory::new(),
+            references: HashMap::new(),
+        };
+        for (parameter, value) in parameters.iter().zip(arguments) {
+            let parameter = match parameter {
+                Argument::Scalar((_, id, _)) | Argument::Vector((_, id, _)) => id,
+            };
+            let key = find(self.symbol_table, table, parameter);
+            match value {
+                Value::Scalar(value) => {
+                    frame.memory.insert(key, vec![value]);
+                }
+                Value::Vector(location) => {
+                    frame.references.insert(key, location);
+                }
+            }
+        }
+
+        self.frames.push(frame);
+        let flow = self.instructions(instructions);
+        self.frames.pop();
+
+        Ok(match flow? {
+            Flow::Next => None,
+            Flow::Return(value) => Some(value),
+        })
+    }
+
+    fn call_function(&mut self, call: &CallFunction) -> Result<Option<Number>, RuntimeError> {
+        let mut arguments = Vec::new();
+        for argument in &call.1 {
+            // Only a vector is given without indice for a vector argument.
+            if let ExpressionKind::LeftValue(lv) = &argument.kind {
+                let (frame, key) = self.location(lv.id());
+                if let SymbolKind::Vector { .. } = self.symbol(key).kind {
+                    arguments.push(Value::Vector((frame, key)));
+                    continue;
+                }
+            }
+
+            arguments.push(Value::Scalar(self.expression(argument)?));
+        }
+
+        self.call(&call.0, arguments)
+    }
+
+    fn instructions(&mut self, instructions: &[Instruction]) -> Result<Flow, RuntimeError> {
+        for instruction in instructions {
+            if let Flow::Return(value) = self.instruction(instruction)? {
+                return Ok(Flow::Return(value));
+            }
+        }
+
+        Ok(Flow::Next)
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) -> Result<Flow, RuntimeError> {
+        use InstructionKind::*;
+
+        match &instruction.kind {
+            Affectation(lv, e) => {
+                let indice = self.indice(lv)?;
+                let value = self.expression(e)?;
+                *self.variable(lv, indice)? = value;
+            }
+            CallFunction(call) => {
+                self.call_function(call)?;
+            }
+            Return(e) => return Ok(Flow::Return(self.expression(e)?)),
+            If(e, then, otherwise) => {
+                if self.expression(e)? != 0 {
+                    return self.instructions(then);
+                }
+                return self.instructions(otherwise);
+            }
+            While(e, instructions) => {
+                while self.expression(e)? != 0 {
+                    if let Flow::Return(value) = self.instructions(instructions)? {
+                        return Ok(Flow::Return(value));
+                    }
+                }
+            }
+            For(init, e, step, instructions) => {
+                self.instruction(init)?;
+                while self.expression(e)? != 0 {
+                    if let Flow::Return(value) = self.instructions(instructions)? {
+                        return Ok(Flow::Return(value));
+                    }
+                    self.instruction(step)?;
+                }
+            }
+            WriteFunction(e) => {
+                let value = self.expression(e)?;
+                writeln!(self.output, "{}", value)?;
+            }
+            NOP => {}
+        }
+
+        Ok(Flow::Next)
+    }
+
+    /// Booleans follow the three-address code: 0 is false, 1 is true and anything else is
+    /// neither for `!`, `&` and `|`.
+    fn expression(&mut self, expression: &Expression) -> Result<Number, RuntimeError> {
+        use BinaryOperator::*;
+        use ExpressionKind::*;
+
+        Ok(match &expression.kind {
+            Value(n) => *n,
+            LeftValue(lv) => {
+                let indice = self.indice(lv)?;
+                *self.variable(lv, indice)?
+            }
+            CallFunction(call) => self
+                .call_function(call)?
+                .ok_or_else(|| RuntimeError::MissingReturn(call.0.clone()))?,
+            ReadFunction => read(self.input)?,
+            UnaryOperation(UnaryOperator::Not, e) => (self.expression(e)? != 1) as Number,
+            BinaryOperation(And, left, right) => {
+                (self.expression(left)? != 0 && self.expression(right)? != 0) as Number
+            }
+            BinaryOperation(Or, left, right) => {
+                (self.expression(left)? == 1 || self.expression(right)? == 1) as Number
+            }
+            BinaryOperation(op, left, right) => {
+                let left = self.expression(left)?;
+                let right = self.expression(right)?;
+
+                match op {
+                    Addidion => left.wrapping_add(right),
+                    Subtraction => left.wrapping_sub(right),
+                    Multiplication => left.wrapping_mul(right),
+                    Division => {
+                        if right == 0 {
+                            return Err(RuntimeError::DivisionByZero);
+                        }
+                        left.wrapping_div(right)
+                    }
+                    Equal => (left == right) as Number,
+                    LessThan => (left < right) as Number,
+                    And | Or => unreachable!(),
+                }
+            }
+        })
+    }
+
+    fn indice(&mut self, lv: &LeftValue) -> Result<Option<Number>, RuntimeError> {
+        match &lv.kind {
+            LeftValueKind::Variable(_) => Ok(None),
+            LeftValueKind::VariableAt(_, indice) => self.expression(indice).map(Some),
+        }
+    }
+
+    fn variable(
+        &mut self,
+        lv: &LeftValue,
+        indice: Option<Number>,
+    ) -> Result<&mut Number, RuntimeError> {
+        let (frame, key) = self.location(lv.id());
+        let size = match self.symbol(key).kind {
+            SymbolKind::Vector { size, .. } => {
+                size.expect("vector arguments are referenced") as usize
+            }
+            _ => 1,
+        };
+
+        let memory = match frame {
+            Some(frame) => &mut self.frames[frame].memory,
+            None => &mut self.globals,
+        };
+        let cells = memory.entry(key).or_insert_with(|| vec![0; size]);
+
+        let indice = indice.unwrap_or(0);
+        if indice < 0 {
+            return Err(RuntimeError::IndexOutOfBounds(lv.id().clone(), indice));
+        }
+
+        cells
+            .get_mut(indice as usize)
+            .ok_or_else(|| RuntimeError::IndexOutOfBounds(lv.id().clone(), indice))
+    }
+
+    /// Where the variable `id` of the current frame is, following the vector arguments.
+    fn location(&self, id: &Id) -> Location {
+        let current = self.frames.len() - 1;
+        let frame = &self.frames[current];
+        let key = find(self.symbol_table, frame.table, id);
+
+        match frame.references.get(&key) {
+            Some(location) => *location,
+            None if key.0 == 0 => (None, key),
+            None => (Some(current), key),
+        }
+    }
+
+    fn symbol(&self, key: Key) -> &'a Symbol {
+        let symbol_table = self.symbol_table;
+        &symbol_table.tables[key.0].symbols[key.1]
+    }
+}
+
+/// Finds the variable `id` as seen from `table`.
+fn find(symbol_table: &SymbolTable, table: usize, id: &Id) -> Key {
+    symbol_table
+        .position(table, |symbol| !symbol.is_function() && symbol.id == *id)
+        .unwrap()
+}
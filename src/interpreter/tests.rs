@@ -0,0 +1,231 @@
+use super::{run, RuntimeError};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use std::fs::read_to_string;
+use std::path::Path;
+
+#[test]
+fn affect() {
+    test("affect");
+}
+
+#[test]
+fn boolean_booleen() {
+    test("boolean-booleen");
+}
+
+#[test]
+fn boucle() {
+    test("boucle");
+}
+
+#[test]
+fn early_exit_and_booleen() {
+    test("early-exit-and-booleen");
+}
+
+#[test]
+fn early_exit_or_booleen() {
+    test("early-exit-or-booleen");
+}
+
+#[test]
+fn ecrire() {
+    test("ecrire");
+}
+
+#[test]
+fn expression() {
+    test("expression");
+}
+
+#[test]
+fn function_in_function_args() {
+    test("function-in-function-args");
+}
+
+#[test]
+fn global() {
+    test("global");
+}
+
+#[test]
+fn lire() {
+    test("lire");
+}
+
+#[test]
+fn local_shadowing() {
+    test("local-shadowing");
+}
+
+#[test]
+fn lire_invalide() {
+    test("lire-invalide");
+}
+
+#[test]
+fn local() {
+    test("local");
+}
+
+#[test]
+fn max() {
+    test("max");
+}
+
+#[test]
+fn operation() {
+    test("operation");
+}
+
+#[test]
+fn param_shadowing() {
+    test("param-shadowing");
+}
+
+#[test]
+fn param() {
+    test("param");
+}
+
+#[test]
+fn recursion_mutuelle() {
+    test("recursion-mutuelle");
+}
+
+#[test]
+fn return_middle() {
+    test("return-middle");
+}
+
+#[test]
+fn return_() {
+    test("return");
+}
+
+#[test]
+fn surcharge() {
+    test("surcharge");
+}
+
+#[test]
+fn si_false() {
+    test("si-false");
+}
+
+#[test]
+fn si_sinon_false() {
+    test("si-sinon-false");
+}
+
+#[test]
+fn si_sinon_true() {
+    test("si-sinon-true");
+}
+
+#[test]
+fn si_true() {
+    test("si-true");
+}
+
+#[test]
+fn tableau_acces_complexe() {
+    test("tableau-acces-complexe");
+}
+
+#[test]
+fn tableau_acces() {
+    test("tableau-acces");
+}
+
+#[test]
+fn tableau_local() {
+    test("tableau-local");
+}
+
+#[test]
+fn tri_reference() {
+    test("tri-reference");
+}
+
+#[test]
+fn tantque() {
+    test("tantque");
+}
+
+#[test]
+fn tri() {
+    test("tri");
+}
+
+#[test]
+fn division_by_zero() {
+    match run_file("tests/resources/division-by-zero.l", "") {
+        Err(RuntimeError::DivisionByZero) => {}
+        _ => panic!("expected a division by zero"),
+    }
+}
+
+#[test]
+fn index_out_of_bounds() {
+    match run_file("tests/resources/index-out-of-bounds.l", "") {
+        Err(RuntimeError::IndexOutOfBounds(id, 10)) => assert_eq!(id, "$t"),
+        _ => panic!("expected an index out of bounds"),
+    }
+}
+
+#[test]
+fn local_index_out_of_bounds() {
+    match run_file("tests/resources/local-index-out-of-bounds.l", "") {
+        Err(RuntimeError::IndexOutOfBounds(id, 10)) => assert_eq!(id, "$t"),
+        _ => panic!("expected an index out of bounds"),
+    }
+}
+
+#[test]
+fn missing_return() {
+    match run_file("tests/resources/missing-return.l", "") {
+        Err(RuntimeError::MissingReturn(id)) => assert_eq!(id, "f"),
+        _ => panic!("expected a missing retour"),
+    }
+}
+
+#[test]
+fn main_return() {
+    let mut output = Vec::new();
+    let program = parse("tests/resources/main-return.l");
+
+    assert_eq!(run(&program, &mut "".as_bytes(), &mut output).unwrap(), 42);
+    assert_eq!(String::from_utf8(output).unwrap(), "1\n");
+}
+
+fn test(filename: &str) {
+    let in_file = format!("testor/{}.in", filename);
+    let input = if Path::new(&in_file).is_file() {
+        read_to_string(in_file).unwrap()
+    } else {
+        String::new()
+    };
+    let out_file = read_to_string(format!("testor/{}.out", filename)).unwrap();
+
+    let output = run_file(&format!("testor/{}.l", filename), &input).unwrap();
+
+    print!("{}", output);
+
+    assert_eq!(out_file.trim_end(), output.trim_end());
+}
+
+fn run_file(filename: &str, input: &str) -> Result<String, RuntimeError> {
+    let mut output = Vec::new();
+
+    run(&parse(filename), &mut input.as_bytes(), &mut output)?;
+
+    Ok(String::from_utf8(output).unwrap())
+}
+
+fn parse(filename: &str) -> crate::ast::Program {
+    let l_file = read_to_string(filename).unwrap();
+
+    Parser::new().parse(Lexer::new(&l_file)).unwrap()
+}
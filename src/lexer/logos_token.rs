@@ -30,6 +30,9 @@ pub enum LogosToken {
     #[token = "entier"]
     IntegerType,
 
+    #[token = "booleen"]
+    BooleanType,
+
     // Predefined functions
     #[token = "lire"]
     ReadFunction,
@@ -121,6 +124,7 @@ impl LogosToken {
             Comma => T::Comma,
             Semicolon => T::Semicolon,
             IntegerType => T::IntegerType,
+            BooleanType => T::BooleanType,
             ReadFunction => T::ReadFunction,
             WriteFunction => T::WriteFunction,
             Return => T::Return,
@@ -8,12 +8,18 @@ extern crate codespan_reporting;
 extern crate structopt;
 
 mod app;
-mod ast;
-mod c_code;
+pub mod ast;
+mod bytecode;
+mod cfg;
+mod codegen;
 mod format;
 mod gen_three_address_code;
-mod lexer;
-mod parser;
+pub mod interpreter;
+pub mod lexer;
+mod liveness;
+mod optimize;
+pub mod parser;
+mod run_three_address_code;
 mod semantic_analyser;
 mod symbol_table;
 mod three_address_code;
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cfg::ControlFlowGraph;
+use crate::three_address_code::*;
+use std::collections::HashSet;
+
+/// The temporaries live before and after each instruction, that is which may still be read
+/// along some path.
+#[derive(Debug)]
+pub struct Liveness {
+    pub live_in: Vec<HashSet<Temp>>,
+    pub live_out: Vec<HashSet<Temp>>,
+}
+
+impl Liveness {
+    pub fn new(three_address_code: &ThreeAddressCode) -> Self {
+        let instructions = &three_address_code.instructions;
+        let mut live_in = vec![HashSet::new(); instructions.len()];
+        let mut live_out = vec![HashSet::new(); instructions.len()];
+
+        for function in ControlFlowGraph::new(three_address_code).functions {
+            let blocks = &function.blocks;
+            let mut block_in: Vec<HashSet<Temp>> = vec![HashSet::new(); blocks.len()];
+
+            // Going backward, the blocks are mostly seen after their successors.
+            let mut changed = true;
+            while changed {
+                changed = false;
+
+                for (b, block) in blocks.iter().enumerate().rev() {
+                    let mut live: HashSet<Temp> = block
+                        .successors
+                        .iter()
+                        .flat_map(|successor| block_in[*successor].iter().cloned())
+                        .collect();
+
+                    for i in block.instructions.clone().rev() {
+                        live_out[i] = live.clone();
+                        transfer(&instructions[i], &mut live);
+                        live_in[i] = live.clone();
+                    }
+
+                    if live != block_in[b] {
+                        block_in[b] = live;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        Self { live_in, live_out }
+    }
+}
+
+/// From the temporaries live after an instruction to the ones live before it.
+fn transfer(instr: &Instruction, live: &mut HashSet<Temp>) {
+    if let Some(TV::T(t)) = instr.result() {
+        live.remove(t);
+    }
+
+    for t in instr.uses() {
+        live.insert(t.clone());
+    }
+}
@@ -0,0 +1,97 @@
+use super::Liveness;
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::semantic_analyser::Analyse;
+use crate::three_address_code::*;
+use std::fs::read_to_string;
+
+#[test]
+fn tantque() {
+    let liveness = Liveness::new(&gen("testor/tantque.l"));
+    let t0 = Temp::new(0u32);
+    let t1 = Temp::new(1u32);
+
+    // t0 = 1 ; if va < 10 goto e2 ; t0 = 0 ; e2: if t0 == 0 goto e1
+    assert!(!liveness.live_in[4].contains(&t0));
+    assert!(liveness.live_out[4].contains(&t0));
+    assert!(liveness.live_out[5].contains(&t0));
+    assert!(!liveness.live_in[6].contains(&t0));
+    assert!(liveness.live_in[8].contains(&t0));
+    assert!(!liveness.live_out[8].contains(&t0));
+
+    // t1 = va + 1 ; va = t1 ; goto e0
+    assert!(liveness.live_out[10].contains(&t1));
+    assert!(!liveness.live_out[11].contains(&t1));
+    assert!(liveness.live_out[12].is_empty());
+}
+
+/// A temporary read in a loop is live along the back edge.
+#[test]
+fn back_edge() {
+    let t0 = Temp::new(0u32);
+    let t1 = Temp::new(1u32);
+    let e0 = Label::new("e0".to_owned());
+    let e1 = Label::new("e1".to_owned());
+
+    let instruction = |label: Option<&Label>, kind| Instruction {
+        label: label.cloned(),
+        kind,
+        comment: None,
+    };
+    let three_a = ThreeAddressCode {
+        instructions: vec![
+            instruction(
+                Some(&Label::new("fmain".to_owned())),
+                InstructionKind::FunctionBegin,
+            ),
+            instruction(
+                None,
+                InstructionKind::ReadFunction {
+                    result: t0.clone().into(),
+                },
+            ),
+            instruction(
+                Some(&e0),
+                InstructionKind::WriteFunction {
+                    value: t0.clone().into(),
+                },
+            ),
+            instruction(
+                None,
+                InstructionKind::ReadFunction {
+                    result: t1.clone().into(),
+                },
+            ),
+            instruction(
+                None,
+                InstructionKind::JumpIf {
+                    condition: JumpIfCondition::Equal,
+                    left: t1.clone().into(),
+                    right: Constant::new(0).into(),
+                    label: e1.clone(),
+                },
+            ),
+            instruction(None, InstructionKind::Jump { label: e0 }),
+            instruction(Some(&e1), InstructionKind::FunctionEnd),
+        ],
+    };
+
+    let liveness = Liveness::new(&three_a);
+
+    assert!(liveness.live_out[2].contains(&t0));
+    assert!(liveness.live_in[4].contains(&t0));
+    assert!(liveness.live_out[5].contains(&t0));
+    assert!(!liveness.live_in[6].contains(&t0));
+    assert!(liveness.live_in[4].contains(&t1));
+    assert!(!liveness.live_out[4].contains(&t1));
+}
+
+fn gen(filename: &str) -> ThreeAddressCode {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program.gen_three_address_code(&symbol_table, 0)
+}
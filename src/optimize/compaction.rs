@@ -0,0 +1,77 @@
+use crate::three_address_code::*;
+use std::collections::{HashMap, HashSet};
+
+/// Removes the labels nothing jumps to and the NOPs, a label on a NOP being moved to the next
+/// instruction.
+pub fn compaction(instructions: &mut Vec<Instruction>) -> bool {
+    let mut changed = false;
+
+    let targets: HashSet<Label> = instructions
+        .iter()
+        .filter_map(|instr| match &instr.kind {
+            InstructionKind::Jump { label } | InstructionKind::JumpIf { label, .. } => {
+                Some(label.clone())
+            }
+            _ => None,
+        })
+        .collect();
+
+    for instr in instructions.iter_mut() {
+        match (&instr.kind, &instr.label) {
+            (InstructionKind::FunctionBegin, _) | (_, None) => {}
+            (_, Some(label)) if !targets.contains(label) => {
+                instr.label = None;
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+
+    let len = instructions.len();
+    let mut renames = HashMap::new();
+    let mut compacted = Vec::with_capacity(len);
+    let mut pending: Option<Instruction> = None;
+
+    for mut instr in instructions.drain(..) {
+        if let InstructionKind::NOP = instr.kind {
+            if instr.label.is_none() {
+                continue;
+            }
+            if let Some(previous) = pending.take() {
+                renames.insert(previous.label.unwrap(), instr.label.clone().unwrap());
+            }
+            pending = Some(instr);
+            continue;
+        }
+
+        if let Some(nop) = pending.take() {
+            match (&instr.kind, &instr.label) {
+                (_, None) => {
+                    instr.label = nop.label;
+                    instr.comment = instr.comment.or(nop.comment);
+                }
+                (InstructionKind::FunctionBegin, _) => compacted.push(nop),
+                (_, Some(label)) => {
+                    renames.insert(nop.label.unwrap(), label.clone());
+                }
+            }
+        }
+
+        compacted.push(instr);
+    }
+    compacted.extend(pending);
+
+    for instr in &mut compacted {
+        if let InstructionKind::Jump { label } | InstructionKind::JumpIf { label, .. } =
+            &mut instr.kind
+        {
+            while let Some(renamed) = renames.get(label) {
+                *label = renamed.clone();
+            }
+        }
+    }
+
+    *instructions = compacted;
+
+    changed || instructions.len() != len
+}
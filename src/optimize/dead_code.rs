@@ -0,0 +1,59 @@
+use crate::three_address_code::*;
+use std::collections::HashMap;
+
+/// Removes the code following a jump or the end of a function up to the next label, and the
+/// computations of temporaries which are never read.
+pub fn dead_code(instructions: &mut Vec<Instruction>) -> bool {
+    let len = instructions.len();
+
+    let mut reachable = true;
+    instructions.retain(|instr| {
+        reachable |= instr.label.is_some();
+        let keep = reachable;
+        if let InstructionKind::Jump { .. } | InstructionKind::FunctionEnd = instr.kind {
+            reachable = false;
+        }
+        keep
+    });
+
+    let mut uses = HashMap::new();
+    for instr in instructions.iter() {
+        for t in instr.uses() {
+            *uses.entry(t.temp()).or_insert(0) += 1;
+        }
+    }
+
+    let dead = |instr: &Instruction| match (&instr.kind, instr.result()) {
+        (InstructionKind::Arithmetic { .. }, _) if may_fail(&instr.kind) => false,
+        (InstructionKind::Arithmetic { .. }, Some(TV::T(t)))
+        | (InstructionKind::Affectation { .. }, Some(TV::T(t))) => !uses.contains_key(&t.temp()),
+        _ => false,
+    };
+
+    // A label is kept on a NOP, the compaction moves it.
+    let mut changed = false;
+    for instr in instructions.iter_mut() {
+        if instr.label.is_some() && dead(instr) {
+            instr.kind = InstructionKind::NOP;
+            changed = true;
+        }
+    }
+    instructions.retain(|instr| !dead(instr));
+
+    changed || instructions.len() != len
+}
+
+/// A division by anything else than a constant other than zero.
+fn may_fail(kind: &InstructionKind) -> bool {
+    match kind {
+        InstructionKind::Arithmetic {
+            operator: ArithmeticOperator::Division,
+            right,
+            ..
+        } => match right {
+            CTV::C(c) => c.constant() == 0,
+            _ => true,
+        },
+        _ => false,
+    }
+}
@@ -0,0 +1,66 @@
+use crate::three_address_code::*;
+
+/// Computes the operations whose result is known and decides the conditional jumps between
+/// constants.
+pub fn constant_folding(instructions: &mut [Instruction]) -> bool {
+    let mut changed = false;
+
+    for instr in instructions {
+        let folded = match &instr.kind {
+            InstructionKind::Arithmetic {
+                operator,
+                left,
+                right,
+                result,
+            } => fold(*operator, left, right).map(|value| InstructionKind::Affectation {
+                value,
+                result: result.clone(),
+            }),
+            InstructionKind::JumpIf {
+                condition,
+                left: CTV::C(left),
+                right: CTV::C(right),
+                label,
+            } => Some(if condition.holds(left.constant(), right.constant()) {
+                InstructionKind::Jump {
+                    label: label.clone(),
+                }
+            } else {
+                InstructionKind::NOP
+            }),
+            _ => None,
+        };
+
+        if let Some(kind) = folded {
+            instr.kind = kind;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// A division by zero is kept for the program to fail at runtime.
+fn fold(operator: ArithmeticOperator, left: &CTV, right: &CTV) -> Option<CTV> {
+    use ArithmeticOperator::*;
+
+    let constant = |ctv: &CTV| match ctv {
+        CTV::C(c) => Some(c.constant()),
+        _ => None,
+    };
+
+    match (operator, constant(left), constant(right)) {
+        (_, Some(left), Some(right)) => operator
+            .compute(left, right)
+            .map(|value| Constant::new(value).into()),
+        (Addition, Some(0), _) | (Multiplication, Some(1), _) => Some(right.clone()),
+        (Addition, _, Some(0))
+        | (Subtraction, _, Some(0))
+        | (Multiplication, _, Some(1))
+        | (Division, _, Some(1)) => Some(left.clone()),
+        (Multiplication, Some(0), _) | (Multiplication, _, Some(0)) => {
+            Some(Constant::new(0).into())
+        }
+        _ => None,
+    }
+}
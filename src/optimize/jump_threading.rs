@@ -0,0 +1,94 @@
+use crate::three_address_code::*;
+use std::collections::{HashMap, HashSet};
+
+/// Jumps directly where a chain of jumps leads, removes the jumps to the next instruction and
+/// turns a conditional jump over a jump into a single conditional jump.
+pub fn jump_threading(instructions: &mut [Instruction]) -> bool {
+    let labels: HashMap<Label, usize> = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, instr)| instr.label.clone().map(|label| (label, i)))
+        .collect();
+    let mut changed = false;
+
+    for i in 0..instructions.len() {
+        let destination = match &instructions[i].kind {
+            InstructionKind::Jump { label } | InstructionKind::JumpIf { label, .. } => {
+                destination(instructions, &labels, label)
+            }
+            _ => continue,
+        };
+        if let InstructionKind::Jump { label } | InstructionKind::JumpIf { label, .. } =
+            &mut instructions[i].kind
+        {
+            if *label != destination {
+                *label = destination.clone();
+                changed = true;
+            }
+        }
+
+        if falls_to(instructions, &labels, i, &destination) {
+            instructions[i].kind = InstructionKind::NOP;
+            changed = true;
+            continue;
+        }
+
+        let over = match instructions.get(i + 1) {
+            Some(Instruction {
+                label: None,
+                kind: InstructionKind::Jump { label },
+                ..
+            }) if falls_to(instructions, &labels, i + 1, &destination) => label.clone(),
+            _ => continue,
+        };
+        if let InstructionKind::JumpIf {
+            condition, label, ..
+        } = &mut instructions[i].kind
+        {
+            *condition = condition.negate();
+            *label = over;
+            instructions[i + 1].kind = InstructionKind::NOP;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Follows the labels which lead to a jump.
+fn destination(
+    instructions: &[Instruction],
+    labels: &HashMap<Label, usize>,
+    label: &Label,
+) -> Label {
+    let mut label = label.clone();
+    let mut seen = HashSet::new();
+
+    while seen.insert(label.clone()) {
+        let next = instructions[labels[&label]..]
+            .iter()
+            .find(|instr| !matches!(instr.kind, InstructionKind::NOP));
+
+        match next.map(|instr| &instr.kind) {
+            Some(InstructionKind::Jump { label: next }) => label = next.clone(),
+            _ => break,
+        }
+    }
+
+    label
+}
+
+/// Whether only NOPs separate the instruction from the label.
+fn falls_to(
+    instructions: &[Instruction],
+    labels: &HashMap<Label, usize>,
+    from: usize,
+    label: &Label,
+) -> bool {
+    let to = labels[label];
+
+    to > from
+        && instructions[from + 1..to]
+            .iter()
+            .all(|instr| matches!(instr.kind, InstructionKind::NOP))
+}
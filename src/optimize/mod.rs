@@ -0,0 +1,171 @@
+mod compaction;
+mod dead_code;
+mod folding;
+mod jump_threading;
+mod propagation;
+#[cfg(test)]
+mod tests;
+
+use crate::three_address_code::*;
+use std::str::FromStr;
+
+/// Passes are run again until none of them changes the code, at most this many times.
+const MAX_ITERATIONS: usize = 16;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Level {
+    O0,
+    O1,
+    O2,
+}
+
+impl Level {
+    pub fn passes(self) -> &'static [Pass] {
+        use Pass::*;
+
+        match self {
+            Level::O0 => &[],
+            Level::O1 => &[ConstantFolding, ConstantPropagation, Compaction],
+            Level::O2 => &Pass::ALL,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Level::O0),
+            "1" => Ok(Level::O1),
+            "2" => Ok(Level::O2),
+            _ => Err(format!("invalid optimization level: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Pass {
+    ConstantFolding,
+    ConstantPropagation,
+    CopyPropagation,
+    DeadCode,
+    JumpThreading,
+    Compaction,
+}
+
+impl Pass {
+    /// Every pass, in the order they are run.
+    pub const ALL: [Pass; 6] = [
+        Pass::ConstantPropagation,
+        Pass::CopyPropagation,
+        Pass::ConstantFolding,
+        Pass::JumpThreading,
+        Pass::DeadCode,
+        Pass::Compaction,
+    ];
+
+    pub const NAMES: [&'static str; 6] = [
+        "constant-propagation",
+        "copy-propagation",
+        "constant-folding",
+        "jump-threading",
+        "dead-code",
+        "compaction",
+    ];
+
+    /// Returns whether the code changed.
+    fn run(self, instructions: &mut Vec<Instruction>) -> bool {
+        use Pass::*;
+
+        match self {
+            ConstantFolding => folding::constant_folding(instructions),
+            ConstantPropagation => propagation::constant_propagation(instructions),
+            CopyPropagation => propagation::copy_propagation(instructions),
+            DeadCode => dead_code::dead_code(instructions),
+            JumpThreading => jump_threading::jump_threading(instructions),
+            Compaction => compaction::compaction(instructions),
+        }
+    }
+}
+
+impl FromStr for Pass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pass::NAMES
+            .iter()
+            .position(|name| *name == s)
+            .map(|i| Pass::ALL[i])
+            .ok_or_else(|| format!("invalid optimization pass: {}", s))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PassManager {
+    passes: Vec<Pass>,
+}
+
+impl PassManager {
+    pub fn new(level: Level) -> Self {
+        Self {
+            passes: level.passes().to_vec(),
+        }
+    }
+
+    pub fn enable(&mut self, pass: Pass) {
+        if !self.passes.contains(&pass) {
+            self.passes.push(pass);
+        }
+    }
+
+    pub fn disable(&mut self, pass: Pass) {
+        self.passes.retain(|p| *p != pass);
+    }
+
+    pub fn run(&self, three_address_code: &mut ThreeAddressCode) {
+        if self.passes.is_empty() {
+            return;
+        }
+
+        let instructions = &mut three_address_code.instructions;
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut changed = false;
+
+            for pass in Pass::ALL.iter().filter(|pass| self.passes.contains(pass)) {
+                changed |= pass.run(instructions);
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+/// Operands read by an instruction, without the indices of the variables it writes.
+fn operands(kind: &mut InstructionKind) -> Vec<&mut CTV> {
+    use InstructionKind::*;
+
+    match kind {
+        Arithmetic { left, right, .. } | JumpIf { left, right, .. } => vec![left, right],
+        Affectation { value, .. }
+        | WriteFunction { value }
+        | FunctionReturn { value }
+        | FunctionPushArg { arg: value } => vec![value],
+        _ => Vec::new(),
+    }
+}
+
+fn result(kind: &mut InstructionKind) -> Option<&mut TV> {
+    use InstructionKind::*;
+
+    match kind {
+        Arithmetic { result, .. }
+        | Affectation { result, .. }
+        | ReadFunction { result }
+        | FunctionCall { result, .. } => Some(result),
+        _ => None,
+    }
+}
@@ -0,0 +1,125 @@
+use super::{operands, result};
+use crate::symbol_table::Scope;
+use crate::three_address_code::*;
+use std::collections::HashMap;
+
+/// A temporary or a scalar variable, known by its id, its scope and its address.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Key {
+    T(u32),
+    V(String, Scope, usize),
+}
+
+impl Key {
+    fn new(ctv: &CTV) -> Option<Self> {
+        match ctv {
+            CTV::C(_) => None,
+            CTV::T(t) => Some(Key::T(t.temp())),
+            CTV::V(v) => match (v.indice(), v.access()) {
+                (None, Access::Value) => Some(Key::V(v.id().clone(), v.scope(), v.address())),
+                _ => None,
+            },
+        }
+    }
+
+    fn is_global(&self) -> bool {
+        match self {
+            Key::V(_, scope, _) => *scope == Scope::Global,
+            Key::T(_) => false,
+        }
+    }
+}
+
+/// Replaces temporaries and scalar variables by the constant they were last given.
+pub fn constant_propagation(instructions: &mut [Instruction]) -> bool {
+    propagate(instructions, |value| value.is_constant())
+}
+
+/// Replaces temporaries and scalar variables by the temporary or the variable they were last
+/// given.
+pub fn copy_propagation(instructions: &mut [Instruction]) -> bool {
+    propagate(instructions, |value| Key::new(value).is_some())
+}
+
+/// What is known is forgotten at labels since the code may come from elsewhere, at calls for
+/// global variables, and when a temporary or a variable is written.
+fn propagate<F: Fn(&CTV) -> bool>(instructions: &mut [Instruction], tracked: F) -> bool {
+    let mut values: HashMap<Key, CTV> = HashMap::new();
+    let mut changed = false;
+
+    for instr in instructions {
+        if instr.label.is_some() {
+            values.clear();
+        }
+
+        changed |= substitute(&mut instr.kind, &values);
+
+        match &instr.kind {
+            InstructionKind::FunctionCall { .. } => values.retain(|key, value| {
+                !key.is_global() && !Key::new(value).is_some_and(|value| value.is_global())
+            }),
+            InstructionKind::FunctionEnd => values.clear(),
+            _ => {}
+        }
+
+        let written = match instr.result() {
+            Some(result) => Key::new(&result.clone().into()),
+            None => continue,
+        };
+        let written = match written {
+            Some(written) => written,
+            None => continue,
+        };
+
+        values.remove(&written);
+        values.retain(|_, value| Key::new(value).as_ref() != Some(&written));
+
+        if let InstructionKind::Affectation { value, .. } = &instr.kind {
+            if tracked(value) && Key::new(value).as_ref() != Some(&written) {
+                values.insert(written, value.clone());
+            }
+        }
+    }
+
+    changed
+}
+
+fn substitute(kind: &mut InstructionKind, values: &HashMap<Key, CTV>) -> bool {
+    let mut changed = false;
+
+    for operand in operands(kind) {
+        if let CTV::V(v) = operand {
+            changed |= substitute_indice(v, values);
+        }
+    }
+    if let Some(TV::V(v)) = result(kind) {
+        changed |= substitute_indice(v, values);
+    }
+
+    for operand in operands(kind) {
+        if let Some(value) = Key::new(operand).and_then(|key| values.get(&key)) {
+            *operand = value.clone();
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// An index can only be a constant or a temporary.
+fn substitute_indice(v: &mut Variable, values: &HashMap<Key, CTV>) -> bool {
+    let indice = v.indice_mut();
+
+    let value = match indice {
+        Some(CT::T(t)) => values.get(&Key::T(t.temp())),
+        _ => None,
+    };
+
+    match value {
+        Some(CTV::C(c)) => *indice = Some(CT::C(c.clone())),
+        Some(CTV::T(t)) => *indice = Some(CT::T(t.clone())),
+        _ => return false,
+    }
+
+    true
+}
@@ -0,0 +1,166 @@
+use super::{Level, Pass, PassManager};
+use crate::gen_three_address_code::GenThreeAddressCode;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::run_three_address_code::RunThreeAddressCode;
+use crate::semantic_analyser::Analyse;
+use crate::three_address_code::*;
+use std::fs::read_to_string;
+use std::path::Path;
+
+#[test]
+fn level_0() {
+    let three_a = optimize(
+        "tests/resources/constant-folding.l",
+        &PassManager::new(Level::O0),
+    );
+
+    assert_eq!(
+        three_a.instructions.len(),
+        gen("tests/resources/constant-folding.l").instructions.len()
+    );
+}
+
+#[test]
+fn constant_folding() {
+    let mut passes = PassManager::new(Level::O0);
+    passes.enable(Pass::ConstantFolding);
+    let three_a = optimize("tests/resources/constant-folding.l", &passes);
+
+    assert!(three_a.instructions.iter().any(|instr| matches!(
+        &instr.kind,
+        InstructionKind::Affectation {
+            value: CTV::C(c), ..
+        } if c.constant() == 5
+    )));
+    assert!(!three_a.instructions.iter().any(|instr| matches!(
+        &instr.kind,
+        InstructionKind::Arithmetic {
+            left: CTV::C(_),
+            right: CTV::C(_),
+            ..
+        }
+    )));
+}
+
+#[test]
+fn level_2() {
+    let three_a = optimize(
+        "tests/resources/constant-folding.l",
+        &PassManager::new(Level::O2),
+    );
+
+    let kinds: Vec<_> = three_a
+        .instructions
+        .iter()
+        .map(|instr| match &instr.kind {
+            InstructionKind::Affectation { .. } => "affectation",
+            InstructionKind::WriteFunction { value: CTV::C(c) } if c.constant() == 20 => "ecrire",
+            InstructionKind::FunctionBegin => "begin",
+            InstructionKind::FunctionEnd => "end",
+            InstructionKind::Allocation { .. } => "allocation",
+            _ => "other",
+        })
+        .collect();
+
+    assert_eq!(
+        kinds,
+        ["begin", "allocation", "affectation", "ecrire", "end"]
+    );
+}
+
+#[test]
+fn compaction() {
+    let mut passes = PassManager::new(Level::O0);
+    passes.enable(Pass::Compaction);
+
+    for instr in &optimize("testor/tantque.l", &passes).instructions {
+        if let InstructionKind::NOP = instr.kind {
+            panic!("a NOP is left");
+        }
+    }
+}
+
+#[test]
+fn disable() {
+    let mut passes = PassManager::new(Level::O2);
+    passes.disable(Pass::Compaction);
+    passes.disable(Pass::DeadCode);
+    passes.disable(Pass::JumpThreading);
+
+    assert!(optimize("testor/si-true.l", &passes)
+        .instructions
+        .iter()
+        .any(|instr| matches!(instr.kind, InstructionKind::NOP)));
+}
+
+/// The programs of testor written before the booleans, which store them in integers.
+const REJECTED: [&str; 3] = [
+    "testor/boolean.l",
+    "testor/early-exit-and.l",
+    "testor/early-exit-or.l",
+];
+
+#[test]
+fn testor() {
+    for entry in Path::new("testor").read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "l") {
+            continue;
+        }
+        let l_file = read_to_string(&path).unwrap();
+        let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+        if REJECTED.iter().any(|rejected| path == Path::new(rejected)) {
+            assert!(program.analyse().is_err(), "{:?} passes the analysis", path);
+            continue;
+        }
+
+        for level in &[Level::O1, Level::O2] {
+            for pass in Pass::ALL.iter() {
+                let mut passes = PassManager::new(*level);
+                passes.disable(*pass);
+                test(&path, &passes);
+            }
+            test(&path, &PassManager::new(*level));
+        }
+    }
+}
+
+/// The optimized code writes the same as the code of the generation.
+fn test(path: &Path, passes: &PassManager) {
+    let input = read_to_string(path.with_extension("in")).unwrap_or_default();
+    let filename = path.to_str().unwrap();
+
+    let mut expected = Vec::new();
+    gen(filename)
+        .run(&mut input.as_bytes(), &mut expected)
+        .unwrap();
+
+    let mut output = Vec::new();
+    optimize(filename, passes)
+        .run(&mut input.as_bytes(), &mut output)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(expected).unwrap(),
+        String::from_utf8(output).unwrap(),
+        "{} with {:?}",
+        filename,
+        passes
+    );
+}
+
+fn gen(filename: &str) -> ThreeAddressCode {
+    let l_file = read_to_string(filename).unwrap();
+
+    let program = Parser::new().parse(Lexer::new(&l_file)).unwrap();
+    let (symbol_table, _) = program.analyse().unwrap();
+
+    program.gen_three_address_code(&symbol_table, 0)
+}
+
+fn optimize(filename: &str, passes: &PassManager) -> ThreeAddressCode {
+    let mut three_a = gen(filename);
+    passes.run(&mut three_a);
+    three_a
+}
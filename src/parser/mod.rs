@@ -5,13 +5,17 @@ lalrpop_mod!(
     "/parser/grammar.rs"
 );
 
-use crate::ast::Program;
+use crate::ast::{BinaryOperator, Expression, ExpressionKind, Program, Span};
 use crate::lexer::Location;
 use crate::lexer::Spanned;
 use crate::token::Token;
+use codespan::ByteIndex;
 use failure::Error;
 use grammar::ProgramParser;
 
+#[cfg(test)]
+mod tests;
+
 pub struct Parser {
     parser: ProgramParser,
 }
@@ -19,8 +23,7 @@ pub struct Parser {
 #[derive(Debug, Fail)]
 #[fail(display = "parse error occured")]
 pub struct ParseError {
-    #[cause]
-    pub error: lalrpop_util::ParseError<Location, Token, Error>,
+    pub errors: Vec<lalrpop_util::ParseError<Location, Token, Error>>,
 }
 
 impl Parser {
@@ -34,9 +37,44 @@ impl Parser {
         self,
         tokens: Tokens,
     ) -> Result<Program, Error> {
-        match self.parser.parse(tokens) {
-            Ok(v) => Ok(v),
-            Err(error) => Err(ParseError { error }.into()),
+        let (program, errors) = self.parse_partial(tokens);
+
+        match program {
+            Some(program) if errors.is_empty() => Ok(program),
+            _ => Err(ParseError { errors }.into()),
         }
     }
+
+    /// Parses as much as possible. The program is missing only if an error could not be
+    /// recovered, in which case it is the last one.
+    pub fn parse_partial<Tokens: IntoIterator<Item = Spanned<Token, Location, Error>>>(
+        self,
+        tokens: Tokens,
+    ) -> (
+        Option<Program>,
+        Vec<lalrpop_util::ParseError<Location, Token, Error>>,
+    ) {
+        let mut recovered = Vec::new();
+        let program = self.parser.parse(&mut recovered, tokens);
+        let mut errors: Vec<_> = recovered.into_iter().map(|e| e.error).collect();
+
+        match program {
+            Ok(program) => (Some(program), errors),
+            Err(error) => {
+                errors.push(error);
+                (None, errors)
+            }
+        }
+    }
+}
+
+pub fn span(l: Location, r: Location) -> Span {
+    Span::new(ByteIndex(l as u32), ByteIndex(r as u32))
+}
+
+pub fn binary_operation(o: BinaryOperator, l: Expression, r: Expression, span: Span) -> Expression {
+    Expression {
+        kind: ExpressionKind::BinaryOperation(o, Box::new(l), Box::new(r)),
+        span,
+    }
 }
@@ -0,0 +1,30 @@
+use super::Parser;
+use crate::lexer::Lexer;
+use std::fs::read_to_string;
+
+#[test]
+fn multiple_errors() {
+    let l_file = read_to_string("tests/resources/multiple-errors.l").unwrap();
+
+    let (program, errors) = Parser::new().parse_partial(Lexer::new(&l_file));
+
+    assert_eq!(errors.len(), 3);
+    assert_eq!(program.unwrap().0.len(), 3);
+
+    let l_file = read_to_string("tests/resources/multiple-errors-global.l").unwrap();
+
+    let (program, errors) = Parser::new().parse_partial(Lexer::new(&l_file));
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(program.unwrap().0.len(), 2);
+}
+
+#[test]
+fn unrecoverable_error() {
+    let l_file = read_to_string("tests/resources/affect-err.l").unwrap();
+
+    let (program, errors) = Parser::new().parse_partial(Lexer::new(&l_file));
+
+    assert_eq!(errors.len(), 1);
+    assert!(program.is_none());
+}
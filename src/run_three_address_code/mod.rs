@@ -0,0 +1,414 @@
+#[cfg(test)]
+mod tests;
+
+use crate::symbol_table::Scope;
+use crate::three_address_code::*;
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Fail)]
+pub enum RuntimeError {
+    #[fail(display = "division by zero")]
+    DivisionByZero,
+    #[fail(display = "index {} is out of the bounds of {}", _1, _0)]
+    IndexOutOfBounds(String, i32),
+    #[fail(display = "undefined label {}", _0)]
+    UndefinedLabel(String),
+    #[fail(display = "invalid reference {}", _0)]
+    InvalidReference(i32),
+    #[fail(display = "function {} ended without retour", _0)]
+    MissingReturn(String),
+    #[fail(display = "{}", _0)]
+    Io(#[cause] io::Error),
+}
+
+impl From<io::Error> for RuntimeError {
+    fn from(error: io::Error) -> Self {
+        RuntimeError::Io(error)
+    }
+}
+
+/// Executes the code from `fmain`, `lire` reading lines of `input` and `ecrire` writing to
+/// `output`.
+pub trait RunThreeAddressCode {
+    fn run(&self, input: &mut dyn BufRead, output: &mut dyn Write) -> Result<(), RuntimeError>;
+}
+
+impl RunThreeAddressCode for ThreeAddressCode {
+    fn run(&self, input: &mut dyn BufRead, output: &mut dyn Write) -> Result<(), RuntimeError> {
+        Machine::new(&self.instructions, input, output).run()
+    }
+}
+
+#[derive(Default)]
+struct Frame {
+    temps: HashMap<u32, i32>,
+    /// Temporaries given the value of a call which ended without `retour`, with the function
+    /// called, an error being raised only if they are read.
+    missing: HashMap<u32, String>,
+    locals: Vec<i32>,
+    /// Sizes of the local variables, which bound the indices of the local vectors.
+    sizes: HashMap<String, i32>,
+    arguments: Vec<i32>,
+    return_value: Option<i32>,
+    /// Index of the call which created the frame, `None` for `main`.
+    caller: Option<usize>,
+    /// Number of references when the frame was created, those given to its callees being
+    /// forgotten when it ends.
+    references: usize,
+}
+
+/// A vector given to a vector argument, which gets the index of the reference.
+#[derive(Clone, PartialEq)]
+enum Reference {
+    Global(String),
+    Local {
+        frame: usize,
+        address: i32,
+        size: i32,
+    },
+}
+
+struct Machine<'a> {
+    instructions: &'a [Instruction],
+    labels: HashMap<&'a str, usize>,
+    globals: HashMap<&'a str, Vec<i32>>,
+    frames: Vec<Frame>,
+    /// Arguments of the calls being prepared.
+    calls: Vec<Vec<i32>>,
+    references: Vec<Reference>,
+    input: &'a mut dyn BufRead,
+    output: &'a mut dyn Write,
+}
+
+impl<'a> Machine<'a> {
+    fn new(
+        instructions: &'a [Instruction],
+        input: &'a mut dyn BufRead,
+        output: &'a mut dyn Write,
+    ) -> Self {
+        let mut labels = HashMap::new();
+        let mut globals = HashMap::new();
+
+        for (i, instr) in instructions.iter().enumerate() {
+            if let Some(label) = &instr.label {
+                labels.insert(label.label().as_str(), i);
+            }
+
+            if let InstructionKind::Allocation {
+                variable: Some(variable),
+                size,
+            } = &instr.kind
+            {
+                if variable.scope() == Scope::Global {
+                    globals.insert(
+                        variable.id().as_str(),
+                        vec![0; size.constant().max(0) as usize],
+                    );
+                }
+            }
+        }
+
+        Self {
+            instructions,
+            labels,
+            globals,
+            frames: Vec::new(),
+            calls: Vec::new(),
+            references: Vec::new(),
+            input,
+            output,
+        }
+    }
+
+    fn run(&mut self) -> Result<(), RuntimeError> {
+        use InstructionKind::*;
+
+        let instructions = self.instructions;
+        let mut pc = self.label("fmain")?;
+        self.frames.push(Frame::default());
+
+        loop {
+            let instr = &instructions[pc];
+            pc += 1;
+
+            match &instr.kind {
+                Arithmetic {
+                    operator,
+                    left,
+                    right,
+                    result,
+                } => {
+                    let left = self.value(left)?;
+                    let right = self.value(right)?;
+                    let value = operator
+                        .compute(left, right)
+                        .ok_or(RuntimeError::DivisionByZero)?;
+                    self.store(result, value)?;
+                }
+                Affectation { value, result } => {
+                    let value = self.value(value)?;
+                    self.store(result, value)?;
+                }
+                Allocation {
+                    variable: Some(variable),
+                    size,
+                } => {
+                    let frame = self.frame();
+                    let end = variable.address() / 4 + size.constant().max(0) as usize;
+                    if frame.locals.len() < end {
+                        frame.locals.resize(end, 0);
+                    }
+                    frame
+                        .sizes
+                        .insert(variable.id().to_owned(), size.constant());
+                }
+                Allocation { variable: None, .. } => self.calls.push(Vec::new()),
+                ReadFunction { result } => {
+                    let value = read(self.input)?;
+                    self.store(result, value)?;
+                }
+                WriteFunction { value } => {
+                    let value = self.value(value)?;
+                    writeln!(self.output, "{}", value)?;
+                }
+                FunctionCall { function, .. } => {
+                    let arguments = self.calls.pop().unwrap_or_default();
+                    self.frames.push(Frame {
+                        arguments,
+                        caller: Some(pc - 1),
+                        references: self.references.len(),
+                        ..Frame::default()
+                    });
+                    pc = self.label(function.label())?;
+                }
+                FunctionBegin => {}
+                FunctionEnd => {
+                    let frame = self.frames.pop().unwrap();
+                    self.references.truncate(frame.references);
+                    match frame.caller {
+                        Some(caller) => {
+                            if let FunctionCall { function, result } = &instructions[caller].kind {
+                                match (frame.return_value, result) {
+                                    (Some(value), _) => self.store(result, value)?,
+                                    (None, TV::T(t)) => {
+                                        let name = function_name(function);
+                                        self.frame().missing.insert(t.temp(), name);
+                                    }
+                                    (None, TV::V(_)) => {
+                                        return Err(RuntimeError::MissingReturn(function_name(
+                                            function,
+                                        )))
+                                    }
+                                }
+                            }
+                            pc = caller + 1;
+                        }
+                        None => return Ok(()),
+                    }
+                }
+                FunctionPushArg { arg } => {
+                    let value = self.value(arg)?;
+                    if let Some(arguments) = self.calls.last_mut() {
+                        arguments.push(value);
+                    }
+                }
+                FunctionReturn { value } => {
+                    self.frame().return_value = Some(self.value(value)?);
+                }
+                Jump { label } => pc = self.label(label.label())?,
+                JumpIf {
+                    condition,
+                    left,
+                    right,
+                    label,
+                } => {
+                    let left = self.value(left)?;
+                    let right = self.value(right)?;
+                    if condition.holds(left, right) {
+                        pc = self.label(label.label())?;
+                    }
+                }
+                NOP => {}
+            }
+        }
+    }
+
+    fn label(&self, label: &str) -> Result<usize, RuntimeError> {
+        self.labels
+            .get(label)
+            .cloned()
+            .ok_or_else(|| RuntimeError::UndefinedLabel(label.to_owned()))
+    }
+
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn value(&mut self, ctv: &CTV) -> Result<i32, RuntimeError> {
+        Ok(match ctv {
+            CTV::C(c) => c.constant(),
+            CTV::T(t) => self.temp(t)?,
+            // A vector argument already holds a reference.
+            CTV::V(v) if v.access() == Access::AddressOf && v.scope() != Scope::Argument => {
+                self.reference(v)
+            }
+            CTV::V(v) => *self.variable(v)?,
+        })
+    }
+
+    /// The index of a reference to a vector, the same vector of a frame keeping its index.
+    fn reference(&mut self, v: &Variable) -> i32 {
+        let reference = match v.scope() {
+            Scope::Global => Reference::Global(v.id().to_owned()),
+            _ => {
+                let frame = self.frames.len() - 1;
+                Reference::Local {
+                    frame,
+                    address: v.address() as i32 / 4,
+                    size: self.frames[frame].sizes[v.id()],
+                }
+            }
+        };
+
+        match self.references.iter().position(|r| *r == reference) {
+            Some(index) => index as i32,
+            None => {
+                self.references.push(reference);
+                self.references.len() as i32 - 1
+            }
+        }
+    }
+
+    /// The vector referenced by the vector argument `v`.
+    fn dereference(&self, v: &Variable) -> Result<Reference, RuntimeError> {
+        let frame = self.frames.last().unwrap();
+        let index = frame.arguments.get(v.address() / 4).cloned().unwrap_or(-1);
+
+        self.references
+            .get(index as usize)
+            .cloned()
+            .ok_or(RuntimeError::InvalidReference(index))
+    }
+
+    fn store(&mut self, tv: &TV, value: i32) -> Result<(), RuntimeError> {
+        match tv {
+            TV::T(t) => {
+                let frame = self.frame();
+                frame.missing.remove(&t.temp());
+                frame.temps.insert(t.temp(), value);
+            }
+            TV::V(v) => *self.variable(v)? = value,
+        }
+
+        Ok(())
+    }
+
+    fn temp(&mut self, t: &Temp) -> Result<i32, RuntimeError> {
+        let frame = self.frame();
+        if let Some(function) = frame.missing.get(&t.temp()) {
+            return Err(RuntimeError::MissingReturn(function.clone()));
+        }
+
+        Ok(frame.temps.get(&t.temp()).cloned().unwrap_or(0))
+    }
+
+    fn variable(&mut self, v: &Variable) -> Result<&mut i32, RuntimeError> {
+        let indice = match v.indice() {
+            Some(CT::C(c)) => c.constant(),
+            Some(CT::T(t)) => self.temp(t)?,
+            None => 0,
+        };
+        let address = v.address() as i32 / 4;
+        let out_of_bounds = || RuntimeError::IndexOutOfBounds(v.id()[1..].to_owned(), indice);
+
+        let reference = match v.access() {
+            Access::Indirect => Some(self.dereference(v)?),
+            _ => None,
+        };
+
+        let (memory, slot) = match (v.scope(), reference) {
+            (_, Some(Reference::Global(id))) => {
+                (self.globals.get_mut(id.as_str()).unwrap(), indice)
+            }
+            (
+                _,
+                Some(Reference::Local {
+                    frame,
+                    address,
+                    size,
+                }),
+            ) => {
+                if indice >= size {
+                    return Err(out_of_bounds());
+                }
+                (&mut self.frames[frame].locals, address + indice)
+            }
+            (Scope::Global, None) => (self.globals.get_mut(v.id().as_str()).unwrap(), indice),
+            (Scope::Local, None) => {
+                let frame = self.frames.last_mut().unwrap();
+                if indice >= frame.sizes[v.id()] {
+                    return Err(out_of_bounds());
+                }
+                (&mut frame.locals, address + indice)
+            }
+            (Scope::Argument, None) => (
+                &mut self.frames.last_mut().unwrap().arguments,
+                address + indice,
+            ),
+        };
+
+        if indice < 0 || slot < 0 {
+            return Err(out_of_bounds());
+        }
+
+        memory.get_mut(slot as usize).ok_or_else(out_of_bounds)
+    }
+}
+
+/// The identifier of a function from its label, without the number of arguments of an
+/// overloaded function.
+fn function_name(label: &Label) -> String {
+    let name = &label.label()[1..];
+    name.split('$').next().unwrap_or(name).to_owned()
+}
+
+/// Reads a line like `atoi`: leading blanks are skipped, then an optional sign and digits
+/// are read until any other character. An empty line or the end of the input gives 0. As in
+/// the nasm runtime, only the first 254 bytes of a line are kept.
+pub fn read(input: &mut dyn BufRead) -> io::Result<i32> {
+    let mut line = Vec::new();
+    input.read_until(b'\n', &mut line)?;
+    line.truncate(LINE_MAX);
+
+    let mut bytes = line.iter().skip_while(|b| **b == b' ').peekable();
+    let negative = match bytes.peek() {
+        Some(b'-') => {
+            bytes.next();
+            true
+        }
+        Some(b'+') => {
+            bytes.next();
+            false
+        }
+        _ => false,
+    };
+
+    let mut value = 0i32;
+    for b in bytes {
+        match (*b as char).to_digit(10) {
+            Some(digit) => value = value.wrapping_mul(10).wrapping_add(digit as i32),
+            None => break,
+        }
+    }
+
+    Ok(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+/// Size of the input buffer of the nasm runtime, its last byte ending the string.
+const LINE_MAX: usize = 254;
@@ -1,9 +1,11 @@
 use crate::ast::*;
+use crate::semantic_analyser::type_check::type_check;
 use crate::symbol_table::Scope;
 use crate::symbol_table::Symbol;
 use crate::symbol_table::SymbolKind;
 use crate::symbol_table::SymbolTable;
 use crate::symbol_table::Table;
+use std::collections::HashSet;
 
 #[derive(Debug, Fail)]
 #[fail(display = "semantic(s) error occured: {:?}", diagnostics)]
@@ -11,39 +13,68 @@ pub struct Error {
     pub diagnostics: Vec<diagnostic::Diagnostic>,
 }
 
-mod diagnostic {
-    #[allow(dead_code)]
+pub mod diagnostic {
+    use crate::ast::{Span, Type};
+
     #[derive(Debug)]
     pub enum Diagnostic {
         Error(Error),
         Warning(Warning),
     }
 
-    #[allow(dead_code)]
     #[derive(Debug)]
     pub enum Error {
-        AlreadyDeclared,
-        Undeclared,
-        VectorWithoutIndice,
-        ScalarWithIndice,
-        TypeConversion,
-        InvalidFunctionArguments,
+        AlreadyDeclared {
+            span: Span,
+            previous: Span,
+        },
+        Undeclared {
+            span: Span,
+        },
+        VectorWithoutIndice {
+            span: Span,
+            declaration: Span,
+        },
+        ScalarWithIndice {
+            span: Span,
+            declaration: Span,
+        },
+        /// An expression given for a vector argument, which only takes the identifier of a
+        /// vector.
+        VectorExpected {
+            span: Span,
+            declaration: Span,
+        },
+        TypeConversion {
+            span: Span,
+            expected: Type,
+            found: Type,
+        },
+        InvalidFunctionArguments {
+            span: Span,
+            declaration: Span,
+            expected: usize,
+            found: usize,
+        },
         MainUndeclared,
     }
 
-    #[allow(dead_code)]
     #[derive(Debug)]
     pub enum Warning {
-        VariableShadowing,
+        VariableShadowing { span: Span, previous: Span },
+        UnusedVariable { span: Span },
+        UnusedFunction { span: Span },
+        UnusedArgument { span: Span },
     }
 }
 
 pub struct Data<'t> {
     pub symbol_table: &'t mut SymbolTable,
     pub current_table: usize,
-    pub errors: Vec<diagnostic::Diagnostic>,
+    pub diagnostics: Vec<diagnostic::Diagnostic>,
     pub scope: Scope,
     pub address: usize,
+    pub used: HashSet<(usize, usize)>,
 }
 
 impl<'t> Data<'t> {
@@ -51,9 +82,10 @@ impl<'t> Data<'t> {
         Self {
             symbol_table,
             current_table: 0,
-            errors: Vec::new(),
+            diagnostics: Vec::new(),
             scope: Scope::Global,
             address: 0,
+            used: HashSet::new(),
         }
     }
 
@@ -61,28 +93,91 @@ impl<'t> Data<'t> {
         &mut self.symbol_table.tables[self.current_table]
     }
 
-    fn already_declared_variable(&self, id: &str) -> bool {
-        self.symbol_table.iter(self.current_table).any(|symbol| {
-            if symbol.is_function() || symbol.id != id {
-                return false;
-            }
+    fn already_declared_variable(&self, id: &str) -> Option<Span> {
+        self.symbol_table
+            .iter(self.current_table)
+            .find(|symbol| {
+                if symbol.is_function() || symbol.id != id {
+                    return false;
+                }
 
-            let scope = match symbol.kind {
-                SymbolKind::Scalar { scope, .. } => scope,
-                SymbolKind::Vector { scope, .. } => scope,
-                SymbolKind::Function { .. } => unreachable!(),
-            };
+                let scope = symbol.scope().expect("functions are skipped");
 
-            if self.scope == scope {
-                return true;
-            }
+                if self.scope == scope {
+                    return true;
+                }
 
-            if self.scope == Scope::Local && scope == Scope::Argument {
-                return true;
-            }
+                if self.scope == Scope::Local && scope == Scope::Argument {
+                    return true;
+                }
+
+                false
+            })
+            .map(|symbol| symbol.span)
+    }
+
+    fn shadowed_variable(&self, id: &str) -> Option<Span> {
+        self.symbol_table
+            .iter(self.current_table)
+            .find(|symbol| !symbol.is_function() && symbol.id == id)
+            .map(|symbol| symbol.span)
+    }
+
+    /// Registers a variable taking `size` bytes in the current table, unless it is already
+    /// declared.
+    fn declare_variable(&mut self, id: &str, span: Span, kind: SymbolKind, size: usize) {
+        if let Some(previous) = self.already_declared_variable(id) {
+            self.diagnostics.push(diagnostic::Diagnostic::Error(
+                diagnostic::Error::AlreadyDeclared { span, previous },
+            ));
+            return;
+        }
+
+        if let Some(previous) = self.shadowed_variable(id) {
+            self.diagnostics.push(diagnostic::Diagnostic::Warning(
+                diagnostic::Warning::VariableShadowing { span, previous },
+            ));
+        }
+
+        let s = Symbol {
+            id: id.to_owned(),
+            address: self.address,
+            kind,
+            span,
+        };
+        self.table().symbols.push(s);
+        self.address += size;
+    }
+
+    /// Finds a symbol visible from the current table and marks it as used.
+    fn use_symbol<P: Fn(&Symbol) -> bool>(&mut self, predicate: P) -> Option<(usize, usize)> {
+        let position = self.symbol_table.position(self.current_table, predicate)?;
+        self.used.insert(position);
+
+        Some(position)
+    }
+
+    fn unused_symbols(&mut self) {
+        use diagnostic::Warning::*;
 
-            false
-        })
+        for (table, t) in self.symbol_table.tables.iter().enumerate() {
+            for (index, symbol) in t.symbols.iter().enumerate() {
+                if self.used.contains(&(table, index)) {
+                    continue;
+                }
+
+                let span = symbol.span;
+                let warning = match symbol.kind {
+                    SymbolKind::Function { .. } if symbol.id == "main" => continue,
+                    SymbolKind::Function { .. } => UnusedFunction { span },
+                    _ if symbol.scope() == Some(Scope::Argument) => UnusedArgument { span },
+                    _ => UnusedVariable { span },
+                };
+
+                self.diagnostics
+                    .push(diagnostic::Diagnostic::Warning(warning));
+            }
+        }
     }
 }
 
@@ -98,40 +193,39 @@ impl<T: Analyse> Analyse for [T] {
     }
 }
 
-impl Analyse for Program {
-    fn analyse(&self, d: &mut Data) {
-        self.0.analyse(d);
-
-        let main_exists = d
-            .symbol_table
-            .global()
-            .symbols
-            .iter()
-            .any(|symbol| symbol.is_function() && symbol.id == "main");
+/// First pass over the program, which registers the global variables and the signature of
+/// every function so that a function can be called before its declaration.
+pub trait Declare {
+    fn declare(&self, d: &mut Data);
+}
 
-        if !main_exists {
-            d.errors.push(diagnostic::Diagnostic::Error(
-                diagnostic::Error::MainUndeclared,
-            ));
+impl<T: Declare> Declare for [T] {
+    fn declare(&self, d: &mut Data) {
+        for i in self {
+            i.declare(d);
         }
     }
 }
 
-impl Analyse for Statement {
-    fn analyse(&self, d: &mut Data) {
-        use Statement::*;
+impl Declare for Statement {
+    fn declare(&self, d: &mut Data) {
+        use StatementKind::*;
 
-        match self {
+        match &self.kind {
             DclVariable(v) => v.analyse(d),
-            DclFunction(id, args, vars, instructions) => {
-                let exists = d
-                    .symbol_table
-                    .iter(d.current_table)
-                    .any(|symbol| symbol.is_function() && symbol.id == *id);
+            DclFunction(id, args, _, _) => {
+                // Functions are overloaded on their number of arguments, except `main`.
+                let previous = d.symbol_table.iter(d.current_table).find(|symbol| {
+                    symbol.is_function_of(id, args.len())
+                        || (symbol.is_function() && symbol.id == "main" && id == "main")
+                });
 
-                if exists {
-                    d.errors.push(diagnostic::Diagnostic::Error(
-                        diagnostic::Error::AlreadyDeclared,
+                if let Some(previous) = previous {
+                    d.diagnostics.push(diagnostic::Diagnostic::Error(
+                        diagnostic::Error::AlreadyDeclared {
+                            span: self.span,
+                            previous: previous.span,
+                        },
                     ));
                     return;
                 }
@@ -143,7 +237,9 @@ impl Analyse for Statement {
                     kind: SymbolKind::Function {
                         nb_arguments: args.len(),
                         symbol_table: table,
+                        return_type: Type::Integer,
                     },
+                    span: self.span,
                 });
                 d.current_table = table;
 
@@ -151,6 +247,66 @@ impl Analyse for Statement {
                 d.address = 0;
                 args.analyse(d);
 
+                if let Some(parent) = d.table().parent {
+                    d.current_table = parent;
+                }
+            }
+        }
+    }
+}
+
+impl Analyse for Program {
+    fn analyse(&self, d: &mut Data) {
+        self.0.declare(d);
+        self.0.analyse(d);
+        d.unused_symbols();
+
+        let main_exists = d
+            .symbol_table
+            .global()
+            .symbols
+            .iter()
+            .any(|symbol| symbol.is_function() && symbol.id == "main");
+
+        if !main_exists {
+            d.diagnostics.push(diagnostic::Diagnostic::Error(
+                diagnostic::Error::MainUndeclared,
+            ));
+        }
+
+        let has_errors = d.diagnostics.iter().any(|diagnostic| match diagnostic {
+            diagnostic::Diagnostic::Error(_) => true,
+            diagnostic::Diagnostic::Warning(_) => false,
+        });
+        if !has_errors {
+            type_check(self, d);
+        }
+    }
+}
+
+/// Second pass over the program, which analyses the body of the functions declared.
+impl Analyse for Statement {
+    fn analyse(&self, d: &mut Data) {
+        use StatementKind::*;
+
+        match &self.kind {
+            DclVariable(_) => {}
+            DclFunction(_, _, vars, instructions) => {
+                // A function declared twice is only reported, its body is not analysed.
+                let table = d
+                    .symbol_table
+                    .iter(d.current_table)
+                    .find(|symbol| symbol.is_function() && symbol.span == self.span)
+                    .map(|symbol| match symbol.kind {
+                        SymbolKind::Function { symbol_table, .. } => symbol_table,
+                        _ => unreachable!(),
+                    });
+                let table = match table {
+                    Some(table) => table,
+                    None => return,
+                };
+                d.current_table = table;
+
                 d.scope = Scope::Local;
                 d.address = 0;
                 vars.analyse(d);
@@ -176,56 +332,53 @@ impl Analyse for Variable {
     }
 }
 
-impl Analyse for Scalar {
+impl Analyse for Argument {
     fn analyse(&self, d: &mut Data) {
-        let (t, id) = self;
-
-        if d.already_declared_variable(id) {
-            d.errors.push(diagnostic::Diagnostic::Error(
-                diagnostic::Error::AlreadyDeclared,
-            ));
-            return;
+        match self {
+            Argument::Scalar(s) => s.analyse(d),
+            // A vector argument holds the address of the vector given by the caller.
+            Argument::Vector((t, id, span)) => {
+                let kind = SymbolKind::Vector {
+                    scope: d.scope,
+                    size: None,
+                    ty: *t,
+                };
+                d.declare_variable(id, *span, kind, t.size());
+            }
         }
+    }
+}
 
-        let s = Symbol {
-            id: id.clone(),
-            address: d.address,
-            kind: SymbolKind::Scalar { scope: d.scope },
+impl Analyse for Scalar {
+    fn analyse(&self, d: &mut Data) {
+        let (t, id, span) = self;
+
+        let kind = SymbolKind::Scalar {
+            scope: d.scope,
+            ty: *t,
         };
-        d.table().symbols.push(s);
-        d.address += t.size();
+        d.declare_variable(id, *span, kind, t.size());
     }
 }
 
 impl Analyse for Vector {
     fn analyse(&self, d: &mut Data) {
-        let (t, size, id) = self;
-
-        if d.already_declared_variable(id) {
-            d.errors.push(diagnostic::Diagnostic::Error(
-                diagnostic::Error::AlreadyDeclared,
-            ));
-            return;
-        }
+        let (t, size, id, span) = self;
 
-        let s = Symbol {
-            id: id.clone(),
-            address: d.address,
-            kind: SymbolKind::Vector {
-                scope: d.scope,
-                size: *size,
-            },
+        let kind = SymbolKind::Vector {
+            scope: d.scope,
+            size: Some(*size),
+            ty: *t,
         };
-        d.table().symbols.push(s);
-        d.address += t.size() * (*size) as usize;
+        d.declare_variable(id, *span, kind, t.size() * (*size) as usize);
     }
 }
 
 impl Analyse for Instruction {
     fn analyse(&self, d: &mut Data) {
-        use Instruction::*;
+        use InstructionKind::*;
 
-        match self {
+        match &self.kind {
             Affectation(lv, e) => {
                 lv.analyse(d);
                 e.analyse(d);
@@ -261,9 +414,9 @@ impl Analyse for Instruction {
 
 impl Analyse for Expression {
     fn analyse(&self, d: &mut Data) {
-        use Expression::*;
+        use ExpressionKind::*;
 
-        match self {
+        match &self.kind {
             Value(_) => {}
             LeftValue(lv) => {
                 lv.analyse(d);
@@ -285,80 +438,131 @@ impl Analyse for Expression {
 
 impl Analyse for LeftValue {
     fn analyse(&self, d: &mut Data) {
-        use LeftValue::*;
+        use LeftValueKind::*;
         use SymbolKind::*;
 
-        match self {
-            Variable(id) => {
-                let symbol = d
-                    .symbol_table
-                    .iter(d.current_table)
-                    .find(|symbol| symbol.id == *id && !symbol.is_function());
-
-                if let Some(symbol) = symbol {
-                    match symbol.kind {
-                        Scalar { .. } => {
-                            return;
-                        }
-                        Vector { .. } => {
-                            d.errors.push(diagnostic::Diagnostic::Error(
-                                diagnostic::Error::VectorWithoutIndice,
-                            ));
-                            return;
-                        }
-                        Function { .. } => unreachable!(),
-                    }
-                }
+        if let VariableAt(_, indice) = &self.kind {
+            indice.analyse(d);
+        }
+
+        let symbol = match d.use_symbol(|symbol| symbol.id == *self.id() && !symbol.is_function()) {
+            Some((table, index)) => &d.symbol_table.tables[table].symbols[index],
+            None => {
+                d.diagnostics.push(diagnostic::Diagnostic::Error(
+                    diagnostic::Error::Undeclared { span: self.span },
+                ));
+                return;
             }
-            VariableAt(id, _) => {
-                let symbol = d
-                    .symbol_table
-                    .iter(d.current_table)
-                    .find(|symbol| symbol.id == *id && !symbol.is_function());
-
-                if let Some(symbol) = symbol {
-                    match symbol.kind {
-                        Scalar { .. } => {
-                            d.errors.push(diagnostic::Diagnostic::Error(
-                                diagnostic::Error::ScalarWithIndice,
-                            ));
-                            return;
-                        }
-                        Vector { .. } => {
-                            return;
-                        }
-                        Function { .. } => unreachable!(),
-                    }
-                }
+        };
+
+        match (&self.kind, &symbol.kind) {
+            (Variable(_), Scalar { .. }) | (VariableAt(..), Vector { .. }) => {}
+            (Variable(_), Vector { .. }) => {
+                d.diagnostics.push(diagnostic::Diagnostic::Error(
+                    diagnostic::Error::VectorWithoutIndice {
+                        span: self.span,
+                        declaration: symbol.span,
+                    },
+                ));
             }
+            (VariableAt(..), Scalar { .. }) => {
+                d.diagnostics.push(diagnostic::Diagnostic::Error(
+                    diagnostic::Error::ScalarWithIndice {
+                        span: self.span,
+                        declaration: symbol.span,
+                    },
+                ));
+            }
+            (_, Function { .. }) => unreachable!(),
         }
-
-        d.errors
-            .push(diagnostic::Diagnostic::Error(diagnostic::Error::Undeclared));
     }
 }
 
 impl Analyse for CallFunction {
     fn analyse(&self, d: &mut Data) {
-        let (id, expressions) = (&self.0, &self.1);
+        let (id, expressions, span) = (&self.0, &self.1, self.2);
+
+        let function = d.use_symbol(|symbol| symbol.is_function_of(id, expressions.len()));
+
+        // The vector arguments of the function called, by position.
+        let vectors: Vec<Option<Span>> = match function {
+            Some((table, index)) => match d.symbol_table.tables[table].symbols[index].kind {
+                SymbolKind::Function { symbol_table, .. } => d
+                    .symbol_table
+                    .arguments(symbol_table)
+                    .iter()
+                    .map(|argument| match argument.kind {
+                        SymbolKind::Vector { .. } => Some(argument.span),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => unreachable!(),
+            },
+            None => Vec::new(),
+        };
+
+        for (i, expression) in expressions.iter().enumerate() {
+            match vectors.get(i) {
+                Some(Some(declaration)) => vector_argument(expression, *declaration, d),
+                _ => expression.analyse(d),
+            }
+        }
+
+        if function.is_some() {
+            return;
+        }
 
         let symbol = d
             .symbol_table
             .iter(d.current_table)
             .find(|symbol| symbol.id == *id && symbol.is_function());
 
-        if let Some(symbol) = symbol {
-            if let SymbolKind::Function { nb_arguments, .. } = symbol.kind {
-                if nb_arguments != expressions.len() {
-                    d.errors.push(diagnostic::Diagnostic::Error(
-                        diagnostic::Error::InvalidFunctionArguments,
-                    ));
-                }
-                return;
-            }
+        match symbol {
+            Some(Symbol {
+                kind: SymbolKind::Function { nb_arguments, .. },
+                span: declaration,
+                ..
+            }) => d.diagnostics.push(diagnostic::Diagnostic::Error(
+                diagnostic::Error::InvalidFunctionArguments {
+                    span,
+                    declaration: *declaration,
+                    expected: *nb_arguments,
+                    found: expressions.len(),
+                },
+            )),
+            _ => d.diagnostics.push(diagnostic::Diagnostic::Error(
+                diagnostic::Error::Undeclared { span },
+            )),
         }
+    }
+}
 
-        d.errors
-            .push(diagnostic::Diagnostic::Error(diagnostic::Error::Undeclared));
+/// Analyses the expression given for a vector argument declared at `declaration`, which must
+/// be the identifier of a vector.
+fn vector_argument(expression: &Expression, declaration: Span, d: &mut Data) {
+    if let ExpressionKind::LeftValue(LeftValue {
+        kind: LeftValueKind::Variable(id),
+        span,
+    }) = &expression.kind
+    {
+        let symbol = d.use_symbol(|symbol| symbol.id == *id && !symbol.is_function());
+        match symbol.map(|(table, index)| &d.symbol_table.tables[table].symbols[index].kind) {
+            Some(SymbolKind::Vector { .. }) => return,
+            Some(_) => {}
+            None => {
+                return d.diagnostics.push(diagnostic::Diagnostic::Error(
+                    diagnostic::Error::Undeclared { span: *span },
+                ));
+            }
+        }
+    } else {
+        expression.analyse(d);
     }
+
+    d.diagnostics.push(diagnostic::Diagnostic::Error(
+        diagnostic::Error::VectorExpected {
+            span: expression.span,
+            declaration,
+        },
+    ));
 }
@@ -1,29 +1,50 @@
-use crate::semantic_analyser::analyse::{Data, Error};
+use crate::semantic_analyser::analyse::Data;
+pub use crate::semantic_analyser::analyse::{diagnostic, Error};
 use crate::symbol_table::SymbolTable;
 use failure::Fallible;
 
 mod analyse;
+#[cfg(test)]
+mod tests;
+mod type_check;
 
+/// Analyses a program and returns its symbol table along with the warnings found.
+/// If there is any error, every diagnostic (warnings included) is returned in an `Error`.
 pub trait Analyse {
-    fn analyse(&self) -> Fallible<SymbolTable>;
+    fn analyse(&self) -> Fallible<(SymbolTable, Vec<diagnostic::Warning>)>;
 }
 
-// TODO return warning
-
 impl<T: analyse::Analyse> Analyse for T {
-    fn analyse(&self) -> Fallible<SymbolTable> {
+    fn analyse(&self) -> Fallible<(SymbolTable, Vec<diagnostic::Warning>)> {
+        use diagnostic::Diagnostic;
+
         let mut symbol_table = SymbolTable::new();
         let mut d = Data::new(&mut symbol_table);
 
         self.analyse(&mut d);
 
-        if d.errors.is_empty() {
-            Ok(symbol_table)
-        } else {
-            Err(Error {
-                diagnostics: d.errors,
+        let has_errors = d
+            .diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic, Diagnostic::Error(_)));
+
+        if has_errors {
+            return Err(Error {
+                diagnostics: d.diagnostics,
             }
-            .into())
+            .into());
         }
+
+        let warnings = d
+            .diagnostics
+            .into_iter()
+            .filter_map(|diagnostic| match diagnostic {
+                Diagnostic::Warning(warning) => Some(warning),
+                Diagnostic::Error(_) => None,
+            })
+            .collect();
+
+        symbol_table.index_globals();
+        Ok((symbol_table, warnings))
     }
 }
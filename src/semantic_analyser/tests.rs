@@ -0,0 +1,163 @@
+use super::diagnostic::Warning::{self, *};
+use super::diagnostic::{self, Diagnostic};
+use super::{Analyse, Error};
+use crate::ast::{Span, Type};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use codespan::ByteIndex;
+use std::fs::read_to_string;
+
+#[test]
+fn variable_shadowing() {
+    let warnings = warnings("variable-shadowing");
+
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(warnings[0], VariableShadowing { .. }));
+}
+
+#[test]
+fn unused() {
+    let warnings = warnings("unused");
+
+    assert_eq!(warnings.len(), 4);
+    assert!(matches!(warnings[0], UnusedVariable { .. }));
+    assert!(matches!(warnings[1], UnusedFunction { .. }));
+    assert!(matches!(warnings[2], UnusedArgument { .. }));
+    assert!(matches!(warnings[3], UnusedVariable { .. }));
+}
+
+#[test]
+fn unused_vector_argument() {
+    let warnings = warnings("unused-vector-argument");
+
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0], UnusedArgument { .. }));
+}
+
+#[test]
+fn tri() {
+    assert!(warnings("tri").is_empty());
+}
+
+#[test]
+fn type_conversion_err() {
+    let errors = errors("type-conversion-err");
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        Diagnostic::Error(diagnostic::Error::TypeConversion {
+            expected: Type::Boolean,
+            found: Type::Integer,
+            ..
+        })
+    ));
+    assert!(matches!(
+        errors[1],
+        Diagnostic::Error(diagnostic::Error::TypeConversion {
+            expected: Type::Integer,
+            found: Type::Boolean,
+            ..
+        })
+    ));
+}
+
+/// The programs written before the booleans use integers as conditions.
+#[test]
+fn integer_conditions_err() {
+    let programs = [
+        ("si", span(23, 24)),
+        ("sinon", span(37, 39)),
+        ("tantque", span(38, 40)),
+        ("tantque0", span(28, 29)),
+    ];
+
+    for (filename, span) in &programs {
+        assert_eq!(
+            type_conversions(filename),
+            vec![(*span, Type::Boolean, Type::Integer)]
+        );
+    }
+}
+
+#[test]
+fn integer_not_err() {
+    assert_eq!(
+        type_conversions("sommeneg"),
+        vec![
+            (span(20, 21), Type::Boolean, Type::Integer),
+            (span(19, 21), Type::Integer, Type::Boolean),
+            (span(25, 26), Type::Boolean, Type::Integer),
+            (span(24, 26), Type::Integer, Type::Boolean),
+        ]
+    );
+}
+
+#[test]
+fn boolean_to_integer_err() {
+    assert_eq!(
+        type_conversions("boolean-to-integer-err"),
+        vec![
+            (span(99, 104), Type::Integer, Type::Boolean),
+            (span(120, 126), Type::Integer, Type::Boolean),
+        ]
+    );
+}
+
+#[test]
+fn vector_expected_err() {
+    let errors = errors("vector-expected-err");
+
+    assert_eq!(errors.len(), 2);
+    for error in errors {
+        assert!(matches!(
+            error,
+            Diagnostic::Error(diagnostic::Error::VectorExpected { .. })
+        ));
+    }
+}
+
+fn warnings(filename: &str) -> Vec<Warning> {
+    let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+
+    let (_, warnings) = Parser::new()
+        .parse(Lexer::new(&l_file))
+        .unwrap()
+        .analyse()
+        .unwrap();
+
+    warnings
+}
+
+fn errors(filename: &str) -> Vec<Diagnostic> {
+    let l_file = read_to_string(format!("tests/resources/{}.l", filename)).unwrap();
+
+    let error = Parser::new()
+        .parse(Lexer::new(&l_file))
+        .unwrap()
+        .analyse()
+        .unwrap_err();
+
+    error.downcast::<Error>().unwrap().diagnostics
+}
+
+/// The spans, expected and found types of the errors of a program, which must all be type
+/// conversions.
+fn type_conversions(filename: &str) -> Vec<(Span, Type, Type)> {
+    errors(filename)
+        .into_iter()
+        .filter_map(|diagnostic| match diagnostic {
+            Diagnostic::Error(diagnostic::Error::TypeConversion {
+                span,
+                expected,
+                found,
+            }) => Some((span, expected, found)),
+            Diagnostic::Error(error) => panic!("unexpected error {:?}", error),
+            Diagnostic::Warning(_) => None,
+        })
+        .collect()
+}
+
+fn span(start: u32, end: u32) -> Span {
+    Span::new(ByteIndex(start), ByteIndex(end))
+}
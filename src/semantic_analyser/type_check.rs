@@ -0,0 +1,294 @@
+use crate::ast::*;
+use crate::semantic_analyser::analyse::{diagnostic, Data};
+use crate::symbol_table::{SymbolKind, SymbolTable};
+use std::collections::HashMap;
+
+/// Last pass over a program without any other error. The type returned by each function is
+/// first inferred from its `retour` instructions, a function whose type depends on no other
+/// returning an integer, then the type of every expression is checked.
+pub fn type_check(program: &Program, d: &mut Data) {
+    let mut checker = Checker {
+        symbol_table: &*d.symbol_table,
+        current_table: 0,
+        return_types: HashMap::new(),
+        diagnostics: Vec::new(),
+    };
+
+    checker.infer(program);
+    for statement in &program.0 {
+        if let StatementKind::DclFunction(_, _, _, instructions) = &statement.kind {
+            checker.current_table = checker.function_table(statement);
+            checker.instructions(instructions);
+        }
+    }
+
+    let Checker {
+        return_types,
+        diagnostics,
+        ..
+    } = checker;
+
+    for symbol in &mut d.symbol_table.tables[0].symbols {
+        if let SymbolKind::Function {
+            symbol_table,
+            return_type,
+            ..
+        } = &mut symbol.kind
+        {
+            *return_type = return_types[symbol_table];
+        }
+    }
+    d.diagnostics.extend(diagnostics);
+}
+
+struct Checker<'t> {
+    symbol_table: &'t SymbolTable,
+    current_table: usize,
+    /// Types returned by the functions, by their table.
+    return_types: HashMap<usize, Type>,
+    diagnostics: Vec<diagnostic::Diagnostic>,
+}
+
+impl<'t> Checker<'t> {
+    /// Gives each function the type of its first `retour` whose type is known, until no more
+    /// function gets a type.
+    fn infer(&mut self, program: &Program) {
+        let functions: Vec<(usize, Vec<&Expression>)> = program
+            .0
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                StatementKind::DclFunction(_, _, _, instructions) => {
+                    let mut values = Vec::new();
+                    returns(instructions, &mut values);
+                    Some((self.function_table(statement), values))
+                }
+                StatementKind::DclVariable(_) => None,
+            })
+            .collect();
+
+        loop {
+            let mut inferred = false;
+
+            for (table, values) in &functions {
+                if self.return_types.contains_key(table) {
+                    continue;
+                }
+
+                self.current_table = *table;
+                if let Some(ty) = values.iter().find_map(|value| self.ty(value)) {
+                    self.return_types.insert(*table, ty);
+                    inferred = true;
+                }
+            }
+
+            if !inferred {
+                break;
+            }
+        }
+
+        for (table, _) in functions {
+            self.return_types.entry(table).or_insert(Type::Integer);
+        }
+    }
+
+    fn instructions(&mut self, instructions: &[Instruction]) {
+        for instruction in instructions {
+            self.instruction(instruction);
+        }
+    }
+
+    fn instruction(&mut self, instruction: &Instruction) {
+        use InstructionKind::*;
+
+        match &instruction.kind {
+            Affectation(lv, e) => {
+                let ty = self.left_value(lv);
+                self.expect(e, ty);
+            }
+            CallFunction(c) => {
+                self.call_function(c);
+            }
+            Return(e) => {
+                let ty = self.return_types[&self.current_table];
+                self.expect(e, ty);
+            }
+            If(e, i1, i2) => {
+                self.expect(e, Type::Boolean);
+                self.instructions(i1);
+                self.instructions(i2);
+            }
+            While(e, i) => {
+                self.expect(e, Type::Boolean);
+                self.instructions(i);
+            }
+            For(i1, e, i2, i3) => {
+                self.instruction(i1);
+                self.expect(e, Type::Boolean);
+                self.instruction(i2);
+                self.instructions(i3);
+            }
+            // A boolean is written as 1 or 0.
+            WriteFunction(e) => {
+                self.expression(e);
+            }
+            NOP => {}
+        }
+    }
+
+    /// Checks an expression and reports it if its type is not the one expected.
+    fn expect(&mut self, expression: &Expression, expected: Type) {
+        let found = self.expression(expression);
+
+        if found != expected {
+            self.diagnostics.push(diagnostic::Diagnostic::Error(
+                diagnostic::Error::TypeConversion {
+                    span: expression.span,
+                    expected,
+                    found,
+                },
+            ));
+        }
+    }
+
+    /// Checks the operands of an expression and returns its type.
+    fn expression(&mut self, expression: &Expression) -> Type {
+        use BinaryOperator::*;
+        use ExpressionKind::*;
+
+        match &expression.kind {
+            Value(_) | ReadFunction => {}
+            LeftValue(lv) => {
+                self.left_value(lv);
+            }
+            CallFunction(c) => {
+                self.call_function(c);
+            }
+            UnaryOperation(UnaryOperator::Not, e) => self.expect(e, Type::Boolean),
+            BinaryOperation(operator, e1, e2) => match operator {
+                Addidion | Subtraction | Multiplication | Division | LessThan => {
+                    self.expect(e1, Type::Integer);
+                    self.expect(e2, Type::Integer);
+                }
+                And | Or => {
+                    self.expect(e1, Type::Boolean);
+                    self.expect(e2, Type::Boolean);
+                }
+                Equal => {
+                    let ty = self.expression(e1);
+                    self.expect(e2, ty);
+                }
+            },
+        }
+
+        self.ty(expression)
+            .expect("the type of every function is inferred")
+    }
+
+    fn left_value(&mut self, lv: &LeftValue) -> Type {
+        if let LeftValueKind::VariableAt(_, indice) = &lv.kind {
+            self.expect(indice, Type::Integer);
+        }
+
+        self.variable(lv.id())
+    }
+
+    fn call_function(&mut self, call: &CallFunction) {
+        let (id, expressions) = (&call.0, &call.1);
+        let table = self.function(id, expressions.len());
+
+        let arguments = self.symbol_table.arguments(table);
+
+        // A vector is given for a vector argument, whose elements have the same type.
+        for (expression, argument) in expressions.iter().zip(arguments) {
+            match argument.kind {
+                SymbolKind::Scalar { ty, .. } | SymbolKind::Vector { ty, .. } => {
+                    self.expect(expression, ty)
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// The type of an expression, unknown for a call to a function whose type is not inferred
+    /// yet.
+    fn ty(&self, expression: &Expression) -> Option<Type> {
+        use BinaryOperator::*;
+        use ExpressionKind::*;
+
+        match &expression.kind {
+            Value(_) | ReadFunction => Some(Type::Integer),
+            LeftValue(lv) => Some(self.variable(lv.id())),
+            CallFunction(c) => {
+                let table = self.function(&c.0, c.1.len());
+                self.return_types.get(&table).cloned()
+            }
+            UnaryOperation(UnaryOperator::Not, _) => Some(Type::Boolean),
+            BinaryOperation(operator, _, _) => match operator {
+                Addidion | Subtraction | Multiplication | Division => Some(Type::Integer),
+                And | Or | Equal | LessThan => Some(Type::Boolean),
+            },
+        }
+    }
+
+    fn variable(&self, id: &str) -> Type {
+        let symbol = self
+            .symbol_table
+            .iter(self.current_table)
+            .find(|symbol| !symbol.is_function() && symbol.id == id);
+
+        match symbol.map(|symbol| &symbol.kind) {
+            Some(SymbolKind::Scalar { ty, .. }) | Some(SymbolKind::Vector { ty, .. }) => *ty,
+            _ => unreachable!("variables are declared"),
+        }
+    }
+
+    /// The table of the function `id` taking `nb_arguments` arguments.
+    fn function(&self, id: &str, nb_arguments: usize) -> usize {
+        let symbol = self
+            .symbol_table
+            .iter(self.current_table)
+            .find(|symbol| symbol.is_function_of(id, nb_arguments));
+
+        match symbol.map(|symbol| &symbol.kind) {
+            Some(SymbolKind::Function { symbol_table, .. }) => *symbol_table,
+            _ => unreachable!("functions are declared"),
+        }
+    }
+
+    /// The table of a function declaration.
+    fn function_table(&self, statement: &Statement) -> usize {
+        let symbol = self
+            .symbol_table
+            .global()
+            .symbols
+            .iter()
+            .find(|symbol| symbol.is_function() && symbol.span == statement.span);
+
+        match symbol.map(|symbol| &symbol.kind) {
+            Some(SymbolKind::Function { symbol_table, .. }) => *symbol_table,
+            _ => unreachable!("functions are declared"),
+        }
+    }
+}
+
+/// The values of the `retour` instructions of a function.
+fn returns<'a>(instructions: &'a [Instruction], values: &mut Vec<&'a Expression>) {
+    use InstructionKind::*;
+
+    for instruction in instructions {
+        match &instruction.kind {
+            Return(e) => values.push(e),
+            If(_, i1, i2) => {
+                returns(i1, values);
+                returns(i2, values);
+            }
+            While(_, i) => returns(i, values),
+            For(i1, _, i2, i3) => {
+                returns(std::slice::from_ref(i1), values);
+                returns(std::slice::from_ref(i2), values);
+                returns(i3, values);
+            }
+            _ => {}
+        }
+    }
+}
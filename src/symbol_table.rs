@@ -1,6 +1,12 @@
+use crate::ast::{Span, Type};
+use std::collections::HashMap;
+
 #[derive(Debug)]
 pub struct SymbolTable {
     pub tables: Vec<Table>,
+    /// Indices in the global table of the global symbols by their identifier in the three
+    /// address code, filled by `index_globals`.
+    globals: HashMap<String, usize>,
 }
 
 #[derive(Debug)]
@@ -13,6 +19,7 @@ impl SymbolTable {
     pub fn new() -> Self {
         Self {
             tables: vec![Table::new()],
+            globals: HashMap::new(),
         }
     }
 
@@ -40,6 +47,96 @@ impl SymbolTable {
 
         Box::new(self_it)
     }
+
+    /// Finds a symbol in the same order as `iter` and returns its table and its index in it.
+    pub fn position<P: Fn(&Symbol) -> bool>(
+        &self,
+        from: usize,
+        predicate: P,
+    ) -> Option<(usize, usize)> {
+        let table = &self.tables[from];
+
+        if let Some(index) = table.symbols.iter().rposition(&predicate) {
+            return Some((from, index));
+        }
+
+        table
+            .parent
+            .and_then(|parent| self.position(parent, predicate))
+    }
+
+    /// The label of a function in the three address code: `f` followed by its identifier,
+    /// then by `$` and its number of arguments when the identifier is overloaded.
+    pub fn function_label(&self, function: &Symbol) -> String {
+        let overloads = self
+            .global()
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.is_function() && symbol.id == function.id)
+            .count();
+
+        label(function, overloads)
+    }
+
+    /// The arguments of the function of a table in the order of their addresses.
+    pub fn arguments(&self, table: usize) -> Vec<&Symbol> {
+        let mut arguments: Vec<&Symbol> = self.tables[table]
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.scope() == Some(Scope::Argument))
+            .collect();
+        arguments.sort_by_key(|symbol| symbol.address);
+        arguments
+    }
+
+    /// Indexes the global symbols by their identifier in the three address code, once every
+    /// function is declared.
+    pub fn index_globals(&mut self) {
+        let symbols = &self.global().symbols;
+        let mut overloads = HashMap::new();
+        for symbol in symbols.iter().filter(|symbol| symbol.is_function()) {
+            *overloads.entry(symbol.id.as_str()).or_insert(0) += 1;
+        }
+
+        let globals = symbols
+            .iter()
+            .enumerate()
+            .map(|(index, symbol)| {
+                let id = if symbol.is_function() {
+                    label(symbol, overloads[symbol.id.as_str()])
+                } else {
+                    format!("v{}", symbol.id)
+                };
+                (id, index)
+            })
+            .collect();
+
+        self.globals = globals;
+    }
+
+    /// The global symbol of an identifier of the three address code, the label of a function
+    /// or `v` followed by the identifier of a variable.
+    pub fn global_symbol(&self, id: &str) -> Option<&Symbol> {
+        self.globals
+            .get(id)
+            .map(|index| &self.global().symbols[*index])
+    }
+
+    /// The function of a label of the three address code.
+    pub fn function(&self, label: &str) -> Option<&Symbol> {
+        self.global_symbol(label)
+            .filter(|symbol| symbol.is_function())
+    }
+}
+
+/// The label of a function with its number of overloads.
+fn label(function: &Symbol, overloads: usize) -> String {
+    match function.kind {
+        SymbolKind::Function { nb_arguments, .. } if overloads > 1 => {
+            format!("f{}${}", function.id, nb_arguments)
+        }
+        _ => format!("f{}", function.id),
+    }
 }
 
 impl Table {
@@ -56,6 +153,7 @@ pub struct Symbol {
     pub id: String,
     pub address: usize,
     pub kind: SymbolKind,
+    pub span: Span,
 }
 
 impl Symbol {
@@ -66,20 +164,46 @@ impl Symbol {
 
         false
     }
+
+    /// The scope of a variable.
+    pub fn scope(&self) -> Option<Scope> {
+        match self.kind {
+            SymbolKind::Scalar { scope, .. } | SymbolKind::Vector { scope, .. } => Some(scope),
+            SymbolKind::Function { .. } => None,
+        }
+    }
+
+    /// Whether the symbol is the function `id` taking `nb_arguments` arguments, as functions
+    /// are overloaded on their number of arguments.
+    pub fn is_function_of(&self, id: &str, nb_arguments: usize) -> bool {
+        match self.kind {
+            SymbolKind::Function {
+                nb_arguments: n, ..
+            } => self.id == id && n == nb_arguments,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum SymbolKind {
     Scalar {
         scope: Scope,
+        ty: Type,
     },
+    /// The size of a vector argument is the one of the vector given by the caller, unknown
+    /// here.
     Vector {
         scope: Scope,
-        size: u32,
+        size: Option<u32>,
+        ty: Type,
     },
+    /// The type returned by a function is inferred from its `retour` instructions once every
+    /// function is declared.
     Function {
         nb_arguments: usize,
         symbol_table: usize,
+        return_type: Type,
     },
 }
 
@@ -1,7 +1,4 @@
 use crate::symbol_table::Scope;
-use std::cell::RefCell;
-use std::hash::Hash;
-use std::hash::Hasher;
 use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -30,31 +27,17 @@ impl Label {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Temp(u32, Rc<RefCell<i32>>);
-
-impl Hash for Temp {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
-    }
-}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Temp(u32);
 
 impl Temp {
     pub fn new<T: Into<u32>>(t: T) -> Self {
-        Temp(t.into(), Rc::new(RefCell::new(-1)))
+        Temp(t.into())
     }
 
     pub fn temp(&self) -> u32 {
         self.0
     }
-
-    pub fn last_use(&self) -> i32 {
-        *self.1.borrow()
-    }
-
-    pub fn set_last_use(&self, last_use: i32) {
-        *self.1.borrow_mut() = last_use;
-    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -63,6 +46,20 @@ pub struct Variable {
     indice: Option<CT>,
     address: usize,
     scope: Scope,
+    access: Access,
+}
+
+/// How a variable is accessed, a vector argument holding the address of the vector of the
+/// caller.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Access {
+    /// The value of a scalar or of an element of a vector.
+    Value,
+    /// The address of a vector, only given as an argument. The address held by a vector
+    /// argument is given as it is.
+    AddressOf,
+    /// An element of the vector whose address is held by a vector argument.
+    Indirect,
 }
 
 impl Variable {
@@ -72,9 +69,14 @@ impl Variable {
             indice,
             address,
             scope,
+            access: Access::Value,
         }
     }
 
+    pub fn with_access(self, access: Access) -> Self {
+        Variable { access, ..self }
+    }
+
     pub fn id(&self) -> &String {
         &self.id
     }
@@ -83,6 +85,10 @@ impl Variable {
         &self.indice
     }
 
+    pub fn indice_mut(&mut self) -> &mut Option<CT> {
+        &mut self.indice
+    }
+
     pub fn address(&self) -> usize {
         self.address
     }
@@ -90,6 +96,10 @@ impl Variable {
     pub fn scope(&self) -> Scope {
         self.scope
     }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -113,13 +123,6 @@ impl CTV {
             _ => false,
         }
     }
-
-    pub fn is_variable(&self) -> bool {
-        match self {
-            CTV::V(_) => true,
-            _ => false,
-        }
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -201,6 +204,76 @@ pub struct Instruction {
     pub comment: Option<String>,
 }
 
+impl Instruction {
+    /// Temporaries read by the instruction, the index of a written variable included.
+    pub fn uses(&self) -> Vec<&Temp> {
+        use InstructionKind::*;
+
+        let mut uses = Vec::new();
+
+        match &self.kind {
+            Arithmetic {
+                left,
+                right,
+                result,
+                ..
+            } => {
+                ctv_uses(left, &mut uses);
+                ctv_uses(right, &mut uses);
+                tv_uses(result, &mut uses);
+            }
+            Affectation { value, result } => {
+                ctv_uses(value, &mut uses);
+                tv_uses(result, &mut uses);
+            }
+            ReadFunction { result } | FunctionCall { result, .. } => tv_uses(result, &mut uses),
+            WriteFunction { value } | FunctionReturn { value } | FunctionPushArg { arg: value } => {
+                ctv_uses(value, &mut uses)
+            }
+            JumpIf { left, right, .. } => {
+                ctv_uses(left, &mut uses);
+                ctv_uses(right, &mut uses);
+            }
+            Allocation { .. } | FunctionBegin | FunctionEnd | Jump { .. } | NOP => {}
+        }
+
+        uses
+    }
+
+    /// The temporary or the variable written by the instruction.
+    pub fn result(&self) -> Option<&TV> {
+        use InstructionKind::*;
+
+        match &self.kind {
+            Arithmetic { result, .. }
+            | Affectation { result, .. }
+            | ReadFunction { result }
+            | FunctionCall { result, .. } => Some(result),
+            _ => None,
+        }
+    }
+}
+
+fn variable_uses<'a>(v: &'a Variable, uses: &mut Vec<&'a Temp>) {
+    if let Some(CT::T(t)) = v.indice() {
+        uses.push(t);
+    }
+}
+
+fn ctv_uses<'a>(ctv: &'a CTV, uses: &mut Vec<&'a Temp>) {
+    match ctv {
+        CTV::T(t) => uses.push(t),
+        CTV::V(v) => variable_uses(v, uses),
+        CTV::C(_) => {}
+    }
+}
+
+fn tv_uses<'a>(tv: &'a TV, uses: &mut Vec<&'a Temp>) {
+    if let TV::V(v) = tv {
+        variable_uses(v, uses);
+    }
+}
+
 #[derive(Debug)]
 pub enum InstructionKind {
     Arithmetic {
@@ -247,7 +320,7 @@ pub enum InstructionKind {
     NOP,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub enum ArithmeticOperator {
     Addition,
     Subtraction,
@@ -255,8 +328,22 @@ pub enum ArithmeticOperator {
     Division,
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
+impl ArithmeticOperator {
+    /// Computes like the processor does, `None` meaning a division by zero.
+    pub fn compute(self, left: i32, right: i32) -> Option<i32> {
+        use ArithmeticOperator::*;
+
+        match self {
+            Addition => Some(left.wrapping_add(right)),
+            Subtraction => Some(left.wrapping_sub(right)),
+            Multiplication => Some(left.wrapping_mul(right)),
+            Division if right == 0 => None,
+            Division => Some(left.wrapping_div(right)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum JumpIfCondition {
     Less,
     LessOrEqual,
@@ -266,6 +353,34 @@ pub enum JumpIfCondition {
     GreaterOrEqual,
 }
 
+impl JumpIfCondition {
+    pub fn holds(self, left: i32, right: i32) -> bool {
+        use JumpIfCondition::*;
+
+        match self {
+            Less => left < right,
+            LessOrEqual => left <= right,
+            Equal => left == right,
+            NotEqual => left != right,
+            Greater => left > right,
+            GreaterOrEqual => left >= right,
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        use JumpIfCondition::*;
+
+        match self {
+            Less => GreaterOrEqual,
+            LessOrEqual => Greater,
+            Equal => NotEqual,
+            NotEqual => Equal,
+            Greater => LessOrEqual,
+            GreaterOrEqual => Less,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ThreeAddressCode {
     pub instructions: Vec<Instruction>,
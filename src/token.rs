@@ -9,6 +9,7 @@ pub enum Token {
 
     // Types
     IntegerType,
+    BooleanType,
 
     // Predefined functions
     ReadFunction,
@@ -0,0 +1,43 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+static int f_procedure(int A_a, int A_b);
+static int f_main(void);
+
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
+
+static int f_procedure(int A_a, int A_b)
+{
+	int V_c = 0;
+	int t0, t1, t2;
+	int retour = 0;
+
+	V_c = 2;
+	t0 = (int)((unsigned)A_b + (unsigned)A_a);
+	t1 = (int)((unsigned)t0 + (unsigned)5);
+	t2 = (int)((unsigned)t1 + (unsigned)V_c);
+	retour = t2;
+	return retour;
+	return retour;
+}
+
+static int f_main(void)
+{
+	int t3, p0, p1;
+	int retour = 0;
+
+	p0 = 1;
+	p1 = 2;
+	t3 = f_procedure(p0, p1);
+	ecrire(t3);
+	return retour;
+}
+
+int main(void)
+{
+	f_main();
+	return 0;
+}
@@ -0,0 +1,94 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+static int f_fact(int A_f);
+static int f_main(void);
+
+/* Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+ * blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+ * une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32. */
+static int lire(void)
+{
+	char ligne[255] = "";
+	const char *c = ligne;
+	unsigned valeur = 0;
+	int negatif = 0;
+
+	if (scanf("%254[^\n]", ligne) != 1)
+		ligne[0] = '\0';
+	/* La fin d'une ligne trop longue est sautée, puis le saut de ligne. */
+	if (scanf("%*[^\n]") != EOF)
+		getchar();
+
+	while (*c == ' ')
+		c++;
+	if (*c == '-' || *c == '+')
+		negatif = *c++ == '-';
+	while (*c >= '0' && *c <= '9')
+		valeur = valeur * 10 + (unsigned)(*c++ - '0');
+
+	return (int)(negatif ? 0u - valeur : valeur);
+}
+
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
+
+static int f_fact(int A_f)
+{
+	int t0, t1, t2, t3, t4, t5, p0;
+	int retour = 0;
+
+	t0 = 1;
+	if (A_f == 1)
+		goto e3;
+	t0 = 0;
+e3:;
+	t1 = 1;
+	if (t0 == 1)
+		goto e2;
+	t2 = 1;
+	if (A_f == 0)
+		goto e4;
+	t2 = 0;
+e4:;
+	if (t2 == 1)
+		goto e2;
+	t1 = 0;
+e2:;
+	if (t1 == 0)
+		goto e0;
+	retour = 1;
+	return retour;
+	goto e1;
+e0:;
+	t3 = (int)((unsigned)A_f - (unsigned)1);
+	p0 = t3;
+	t4 = f_fact(p0);
+	t5 = (int)((unsigned)t4 * (unsigned)A_f);
+	retour = t5;
+	return retour;
+e1:;
+	return retour;
+}
+
+static int f_main(void)
+{
+	int V_a = 0;
+	int t6, t7, p0;
+	int retour = 0;
+
+	t6 = lire();
+	V_a = t6;
+	p0 = V_a;
+	t7 = f_fact(p0);
+	ecrire(t7);
+	return retour;
+}
+
+int main(void)
+{
+	f_main();
+	return 0;
+}
@@ -0,0 +1,217 @@
+; Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+; blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+; une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32.
+define internal i32 @lire() {
+entree:
+  %ligne = alloca [255 x i8]
+  %i = alloca i64
+  %valeur = alloca i32
+  %debut = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 0
+  store i8 0, i8* %debut
+  store i64 0, i64* %i
+  store i32 0, i32* %valeur
+  %lus = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([9 x i8], [9 x i8]* @format.ligne, i64 0, i64 0), i8* %debut)
+  %lue = icmp eq i32 %lus, 1
+  br i1 %lue, label %reste, label %vide
+
+vide:
+  store i8 0, i8* %debut
+  br label %reste
+
+; La fin d'une ligne trop longue est sautée, puis le saut de ligne.
+reste:
+  %sautes = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([7 x i8], [7 x i8]* @format.reste, i64 0, i64 0))
+  %fin = icmp eq i32 %sautes, -1
+  br i1 %fin, label %blancs, label %saut
+
+saut:
+  %saut.ligne = call i32 @getchar()
+  br label %blancs
+
+blancs:
+  %blanc.i = load i64, i64* %i
+  %blanc.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %blanc.i
+  %blanc.c = load i8, i8* %blanc.p
+  %est.blanc = icmp eq i8 %blanc.c, 32
+  br i1 %est.blanc, label %blanc.suivant, label %signe
+
+blanc.suivant:
+  %blanc.j = add i64 %blanc.i, 1
+  store i64 %blanc.j, i64* %i
+  br label %blancs
+
+signe:
+  %moins = icmp eq i8 %blanc.c, 45
+  %plus = icmp eq i8 %blanc.c, 43
+  %signe.present = or i1 %moins, %plus
+  br i1 %signe.present, label %signe.suivant, label %chiffres
+
+signe.suivant:
+  %signe.j = add i64 %blanc.i, 1
+  store i64 %signe.j, i64* %i
+  br label %chiffres
+
+chiffres:
+  %chiffre.i = load i64, i64* %i
+  %chiffre.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %chiffre.i
+  %chiffre.c = load i8, i8* %chiffre.p
+  %chiffre = sub i8 %chiffre.c, 48
+  %est.chiffre = icmp ult i8 %chiffre, 10
+  br i1 %est.chiffre, label %chiffre.suivant, label %nombre
+
+chiffre.suivant:
+  %avant = load i32, i32* %valeur
+  %dizaines = mul i32 %avant, 10
+  %unites = zext i8 %chiffre to i32
+  %apres = add i32 %dizaines, %unites
+  store i32 %apres, i32* %valeur
+  %chiffre.j = add i64 %chiffre.i, 1
+  store i64 %chiffre.j, i64* %i
+  br label %chiffres
+
+nombre:
+  %absolu = load i32, i32* %valeur
+  %oppose = sub i32 0, %absolu
+  %resultat = select i1 %moins, i32 %oppose, i32 %absolu
+  ret i32 %resultat
+}
+
+define internal void @ecrire(i32 %valeur) {
+  %ecrits = call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @format.entier, i64 0, i64 0), i32 %valeur)
+  ret void
+}
+
+; La division par zéro arrête le programme, -2147483648 / -1 donne -2147483648.
+define internal i32 @division(i32 %gauche, i32 %droite) {
+  %zero = icmp eq i32 %droite, 0
+  br i1 %zero, label %arret, label %diviseur
+
+arret:
+  call void @llvm.trap()
+  unreachable
+
+diviseur:
+  %moins.un = icmp eq i32 %droite, -1
+  br i1 %moins.un, label %oppose, label %quotient
+
+oppose:
+  %resultat = sub i32 0, %gauche
+  ret i32 %resultat
+
+quotient:
+  %q = sdiv i32 %gauche, %droite
+  ret i32 %q
+}
+
+@format.ligne = private unnamed_addr constant [9 x i8] c"%254[^\0A]\00"
+@format.reste = private unnamed_addr constant [7 x i8] c"%*[^\0A]\00"
+@format.entier = private unnamed_addr constant [4 x i8] c"%d\0A\00"
+
+declare i32 @scanf(i8*, ...)
+declare i32 @printf(i8*, ...)
+declare i32 @getchar()
+declare void @llvm.trap()
+
+define internal i32 @ffact(i32 %v$f.param) {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$f.arg = alloca i32
+  store i32 %v$f.param, i32* %v$f.arg
+  %t0 = alloca i32
+  %t1 = alloca i32
+  %t2 = alloca i32
+  %t3 = alloca i32
+  %t4 = alloca i32
+  %t5 = alloca i32
+  store i32 1, i32* %t0
+  %r0 = load i32, i32* %v$f.arg
+  %r1 = icmp eq i32 %r0, 1
+  br i1 %r1, label %e3, label %b0
+
+b0:
+  store i32 0, i32* %t0
+  br label %e3
+
+e3:
+  store i32 1, i32* %t1
+  %r2 = load i32, i32* %t0
+  %r3 = icmp eq i32 %r2, 1
+  br i1 %r3, label %e2, label %b1
+
+b1:
+  store i32 1, i32* %t2
+  %r4 = load i32, i32* %v$f.arg
+  %r5 = icmp eq i32 %r4, 0
+  br i1 %r5, label %e4, label %b2
+
+b2:
+  store i32 0, i32* %t2
+  br label %e4
+
+e4:
+  %r6 = load i32, i32* %t2
+  %r7 = icmp eq i32 %r6, 1
+  br i1 %r7, label %e2, label %b3
+
+b3:
+  store i32 0, i32* %t1
+  br label %e2
+
+e2:
+  %r8 = load i32, i32* %t1
+  %r9 = icmp eq i32 %r8, 0
+  br i1 %r9, label %e0, label %b4
+
+b4:
+  store i32 1, i32* %retour
+  %r10 = load i32, i32* %retour
+  ret i32 %r10
+
+b5:
+  br label %e1
+
+e0:
+  %r11 = load i32, i32* %v$f.arg
+  %r12 = sub i32 %r11, 1
+  store i32 %r12, i32* %t3
+  %r13 = load i32, i32* %t3
+  %r14 = call i32 @ffact(i32 %r13)
+  store i32 %r14, i32* %t4
+  %r15 = load i32, i32* %t4
+  %r16 = load i32, i32* %v$f.arg
+  %r17 = mul i32 %r15, %r16
+  store i32 %r17, i32* %t5
+  %r18 = load i32, i32* %t5
+  store i32 %r18, i32* %retour
+  %r19 = load i32, i32* %retour
+  ret i32 %r19
+
+e1:
+  %r20 = load i32, i32* %retour
+  ret i32 %r20
+}
+
+define internal i32 @fmain() {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$a = alloca i32
+  store i32 0, i32* %v$a
+  %t6 = alloca i32
+  %t7 = alloca i32
+  %r0 = call i32 @lire()
+  store i32 %r0, i32* %t6
+  %r1 = load i32, i32* %t6
+  store i32 %r1, i32* %v$a
+  %r2 = load i32, i32* %v$a
+  %r3 = call i32 @ffact(i32 %r2)
+  store i32 %r3, i32* %t7
+  %r4 = load i32, i32* %t7
+  call void @ecrire(i32 %r4)
+  %r5 = load i32, i32* %retour
+  ret i32 %r5
+}
+
+define i32 @main() {
+  %retour = call i32 @fmain()
+  ret i32 0
+}
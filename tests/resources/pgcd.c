@@ -0,0 +1,119 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+static int G_a;
+static int G_b;
+
+static int f_pgcd(int A_a, int A_b);
+static int f_main(void);
+
+/* Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+ * blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+ * une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32. */
+static int lire(void)
+{
+	char ligne[255] = "";
+	const char *c = ligne;
+	unsigned valeur = 0;
+	int negatif = 0;
+
+	if (scanf("%254[^\n]", ligne) != 1)
+		ligne[0] = '\0';
+	/* La fin d'une ligne trop longue est sautée, puis le saut de ligne. */
+	if (scanf("%*[^\n]") != EOF)
+		getchar();
+
+	while (*c == ' ')
+		c++;
+	if (*c == '-' || *c == '+')
+		negatif = *c++ == '-';
+	while (*c >= '0' && *c <= '9')
+		valeur = valeur * 10 + (unsigned)(*c++ - '0');
+
+	return (int)(negatif ? 0u - valeur : valeur);
+}
+
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
+
+/* La division par zéro arrête le programme avec l'erreur de l'interpréteur, -2147483648 / -1
+ * donne -2147483648. */
+static int division(int gauche, int droite)
+{
+	if (droite == 0) {
+		fputs("Error: division by zero\n", stderr);
+		exit(1);
+	}
+	if (droite == -1)
+		return (int)(0u - (unsigned)gauche);
+
+	return gauche / droite;
+}
+
+static int f_pgcd(int A_a, int A_b)
+{
+	int V_c = 0;
+	int t0, t1, t2, t3, t4, p0, p1;
+	int retour = 0;
+
+	t0 = division(A_a, A_b);
+	t1 = (int)((unsigned)t0 * (unsigned)A_b);
+	t2 = (int)((unsigned)A_a - (unsigned)t1);
+	V_c = t2;
+	t3 = 1;
+	if (V_c == 0)
+		goto e2;
+	t3 = 0;
+e2:;
+	if (t3 == 0)
+		goto e0;
+	retour = A_b;
+	return retour;
+	goto e1;
+e0:;
+	p0 = A_b;
+	p1 = V_c;
+	t4 = f_pgcd(p0, p1);
+	retour = t4;
+	return retour;
+e1:;
+	return retour;
+}
+
+static int f_main(void)
+{
+	int t5, t6, t7, t8, t9, p0, p1, p2, p3;
+	int retour = 0;
+
+	t5 = lire();
+	G_a = t5;
+	t6 = lire();
+	G_b = t6;
+	t7 = 1;
+	if (G_b < G_a)
+		goto e5;
+	t7 = 0;
+e5:;
+	if (t7 == 0)
+		goto e3;
+	p0 = G_a;
+	p1 = G_b;
+	t8 = f_pgcd(p0, p1);
+	ecrire(t8);
+	goto e4;
+e3:;
+	p2 = G_b;
+	p3 = G_a;
+	t9 = f_pgcd(p2, p3);
+	ecrire(t9);
+e4:;
+	return retour;
+}
+
+int main(void)
+{
+	f_main();
+	return 0;
+}
@@ -0,0 +1,52 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+static int f_afficher(int *A_t, int A_n);
+static int f_main(void);
+
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
+
+static int f_afficher(int *A_t, int A_n)
+{
+	int V_i = 0;
+	int t0, t1, t2;
+	int retour = 0;
+
+	V_i = 0;
+e0:;
+	t0 = 1;
+	if (V_i < A_n)
+		goto e2;
+	t0 = 0;
+e2:;
+	if (t0 == 0)
+		goto e1;
+	t1 = V_i;
+	ecrire(A_t[t1]);
+	t2 = (int)((unsigned)V_i + (unsigned)1);
+	V_i = t2;
+	goto e0;
+e1:;
+	return retour;
+}
+
+static int f_main(void)
+{
+	int V_t[3] = {0};
+	int p0;
+	int retour = 0;
+
+	V_t[0] = 1;
+	p0 = 1;
+	f_afficher(V_t, p0);
+	return retour;
+}
+
+int main(void)
+{
+	f_main();
+	return 0;
+}
@@ -0,0 +1,184 @@
+; Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+; blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+; une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32.
+define internal i32 @lire() {
+entree:
+  %ligne = alloca [255 x i8]
+  %i = alloca i64
+  %valeur = alloca i32
+  %debut = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 0
+  store i8 0, i8* %debut
+  store i64 0, i64* %i
+  store i32 0, i32* %valeur
+  %lus = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([9 x i8], [9 x i8]* @format.ligne, i64 0, i64 0), i8* %debut)
+  %lue = icmp eq i32 %lus, 1
+  br i1 %lue, label %reste, label %vide
+
+vide:
+  store i8 0, i8* %debut
+  br label %reste
+
+; La fin d'une ligne trop longue est sautée, puis le saut de ligne.
+reste:
+  %sautes = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([7 x i8], [7 x i8]* @format.reste, i64 0, i64 0))
+  %fin = icmp eq i32 %sautes, -1
+  br i1 %fin, label %blancs, label %saut
+
+saut:
+  %saut.ligne = call i32 @getchar()
+  br label %blancs
+
+blancs:
+  %blanc.i = load i64, i64* %i
+  %blanc.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %blanc.i
+  %blanc.c = load i8, i8* %blanc.p
+  %est.blanc = icmp eq i8 %blanc.c, 32
+  br i1 %est.blanc, label %blanc.suivant, label %signe
+
+blanc.suivant:
+  %blanc.j = add i64 %blanc.i, 1
+  store i64 %blanc.j, i64* %i
+  br label %blancs
+
+signe:
+  %moins = icmp eq i8 %blanc.c, 45
+  %plus = icmp eq i8 %blanc.c, 43
+  %signe.present = or i1 %moins, %plus
+  br i1 %signe.present, label %signe.suivant, label %chiffres
+
+signe.suivant:
+  %signe.j = add i64 %blanc.i, 1
+  store i64 %signe.j, i64* %i
+  br label %chiffres
+
+chiffres:
+  %chiffre.i = load i64, i64* %i
+  %chiffre.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %chiffre.i
+  %chiffre.c = load i8, i8* %chiffre.p
+  %chiffre = sub i8 %chiffre.c, 48
+  %est.chiffre = icmp ult i8 %chiffre, 10
+  br i1 %est.chiffre, label %chiffre.suivant, label %nombre
+
+chiffre.suivant:
+  %avant = load i32, i32* %valeur
+  %dizaines = mul i32 %avant, 10
+  %unites = zext i8 %chiffre to i32
+  %apres = add i32 %dizaines, %unites
+  store i32 %apres, i32* %valeur
+  %chiffre.j = add i64 %chiffre.i, 1
+  store i64 %chiffre.j, i64* %i
+  br label %chiffres
+
+nombre:
+  %absolu = load i32, i32* %valeur
+  %oppose = sub i32 0, %absolu
+  %resultat = select i1 %moins, i32 %oppose, i32 %absolu
+  ret i32 %resultat
+}
+
+define internal void @ecrire(i32 %valeur) {
+  %ecrits = call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @format.entier, i64 0, i64 0), i32 %valeur)
+  ret void
+}
+
+; La division par zéro arrête le programme, -2147483648 / -1 donne -2147483648.
+define internal i32 @division(i32 %gauche, i32 %droite) {
+  %zero = icmp eq i32 %droite, 0
+  br i1 %zero, label %arret, label %diviseur
+
+arret:
+  call void @llvm.trap()
+  unreachable
+
+diviseur:
+  %moins.un = icmp eq i32 %droite, -1
+  br i1 %moins.un, label %oppose, label %quotient
+
+oppose:
+  %resultat = sub i32 0, %gauche
+  ret i32 %resultat
+
+quotient:
+  %q = sdiv i32 %gauche, %droite
+  ret i32 %q
+}
+
+@format.ligne = private unnamed_addr constant [9 x i8] c"%254[^\0A]\00"
+@format.reste = private unnamed_addr constant [7 x i8] c"%*[^\0A]\00"
+@format.entier = private unnamed_addr constant [4 x i8] c"%d\0A\00"
+
+declare i32 @scanf(i8*, ...)
+declare i32 @printf(i8*, ...)
+declare i32 @getchar()
+declare void @llvm.trap()
+
+define internal i32 @fafficher(i32* %v$t.param, i32 %v$n.param) {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$t.arg = alloca i32*
+  store i32* %v$t.param, i32** %v$t.arg
+  %v$n.arg = alloca i32
+  store i32 %v$n.param, i32* %v$n.arg
+  %v$i = alloca i32
+  store i32 0, i32* %v$i
+  %t0 = alloca i32
+  %t1 = alloca i32
+  %t2 = alloca i32
+  store i32 0, i32* %v$i
+  br label %e0
+
+e0:
+  store i32 1, i32* %t0
+  %r0 = load i32, i32* %v$i
+  %r1 = load i32, i32* %v$n.arg
+  %r2 = icmp slt i32 %r0, %r1
+  br i1 %r2, label %e2, label %b0
+
+b0:
+  store i32 0, i32* %t0
+  br label %e2
+
+e2:
+  %r3 = load i32, i32* %t0
+  %r4 = icmp eq i32 %r3, 0
+  br i1 %r4, label %e1, label %b1
+
+b1:
+  %r5 = load i32, i32* %v$i
+  store i32 %r5, i32* %t1
+  %r6 = load i32, i32* %t1
+  %r7 = load i32*, i32** %v$t.arg
+  %r8 = getelementptr inbounds i32, i32* %r7, i32 %r6
+  %r9 = load i32, i32* %r8
+  call void @ecrire(i32 %r9)
+  %r10 = load i32, i32* %v$i
+  %r11 = add i32 %r10, 1
+  store i32 %r11, i32* %t2
+  %r12 = load i32, i32* %t2
+  store i32 %r12, i32* %v$i
+  br label %e0
+
+e1:
+  %r13 = load i32, i32* %retour
+  ret i32 %r13
+}
+
+define internal i32 @fmain() {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$t = alloca [3 x i32]
+  store [3 x i32] zeroinitializer, [3 x i32]* %v$t
+  %t3 = alloca i32
+  %r0 = getelementptr inbounds [3 x i32], [3 x i32]* %v$t, i32 0, i32 0
+  store i32 1, i32* %r0
+  %r1 = getelementptr inbounds [3 x i32], [3 x i32]* %v$t, i32 0, i32 0
+  %r2 = call i32 @fafficher(i32* %r1, i32 1)
+  store i32 %r2, i32* %t3
+  %r3 = load i32, i32* %retour
+  ret i32 %r3
+}
+
+define i32 @main() {
+  %retour = call i32 @fmain()
+  ret i32 0
+}
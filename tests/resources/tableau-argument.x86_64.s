@@ -0,0 +1,287 @@
+	.bss
+sinput:	.skip	255	# reserve a 255 byte space in memory for the users input string
+
+	.text
+	.globl	_start
+_start:
+	call	fmain
+	mov	$60, %eax
+	mov	$0, %edi
+	syscall
+fafficher:
+	push	%rbp
+	mov	%rsp, %rbp
+	push	%rax
+	push	%rbx
+	push	%rcx
+	push	%rdx
+	sub	$8, %rsp	# allocation variable locale v$i
+	movl	$0, -40(%rbp)
+e0:
+	mov	$1, %eax
+	movl	-40(%rbp), %edi
+	cmpl	16(%rbp), %edi
+	jl	e2
+	mov	$0, %eax
+e2:
+	cmp	$0, %eax
+	je	e1
+	movl	-40(%rbp), %eax
+	mov	%eax, %esi
+	shl	$2, %rsi
+	add	24(%rbp), %rsi
+	movl	(%rsi), %eax
+	call	iprintLF
+	movl	-40(%rbp), %eax
+	add	$1, %eax
+	movl	%eax, -40(%rbp)
+	jmp	e0
+e1:
+	add	$8, %rsp
+	pop	%rdx
+	pop	%rcx
+	pop	%rbx
+	pop	%rax
+	pop	%rbp
+	ret
+fmain:
+	push	%rbp
+	mov	%rsp, %rbp
+	push	%rax
+	push	%rbx
+	push	%rcx
+	push	%rdx
+	sub	$24, %rsp	# allocation variable locale v$t
+	movl	$1, -56(%rbp)
+	sub	$8, %rsp
+	lea	-56(%rbp), %rsi
+	push	%rsi
+	push	$1
+	call	fafficher
+	add	$16, %rsp		# desallocation parametres
+	pop	%rax
+	add	$24, %rsp
+	pop	%rdx
+	pop	%rcx
+	pop	%rbx
+	pop	%rax
+	pop	%rbp
+	ret
+
+#------------------------------------------
+# void readline
+# Read a line from stdin, store string after [$rax]
+# At most 254 characters are kept, the end of a longer line is skipped. The end of the
+# input gives an empty string.
+readline:
+    push    %rdi        # preserve rdi on stack to be restored after function
+    push    %rsi        # preserve rsi on stack to be restored after function
+    push    %rdx        # preserve rdx on stack to be restored after function
+    push    %rcx        # preserve rcx on stack, syscall overwrites it
+    push    %r11        # preserve r11 on stack, syscall overwrites it
+    push    %rax        # preserve rax on stack to be restored after function
+    push    %r8         # preserve r8 on stack to be restored after function
+    mov     %rax, %rsi   # address where store result
+    lea     254(%rax), %r8 # last byte of the buffer
+continue_reading:
+    mov     $0, %rax     # syscall is read = 0
+    mov     $0, %rdi     # fd is stdin = 0
+    mov     $1, %rdx     # nb. of bytes to read
+    syscall            # call kernel
+    cmp     $0, %rax     # if no byte read, then we reached EOF or an error, stop
+    jle     end_reading
+    cmpb    $10, (%rsi) # Found '\n', stop reading string
+    je      end_reading
+    cmp     %r8, %rsi    # Buffer full, overwrite the last byte until '\n'
+    je      continue_reading
+    inc     %rsi        # None of above, increment pointer and read next byte
+    jmp     continue_reading
+end_reading:
+    movb    $0, (%rsi) # Add zero to yield null-terminated string
+    pop     %r8
+    pop     %rax
+    pop     %r11
+    pop     %rcx
+    pop     %rdx
+    pop     %rsi
+    pop     %rdi
+    ret
+
+#------------------------------------------
+# void iprint(Integer number)
+# Integer printing function (itoa), the integer is in eax
+iprint:
+    push    %rax             # preserve rax on the stack to be restored after function runs
+    push    %rcx             # preserve rcx on the stack to be restored after function runs
+    push    %rdx             # preserve rdx on the stack to be restored after function runs
+    push    %rsi             # preserve rsi on the stack to be restored after function runs
+
+    cmp     $0, %eax
+    jge     positive
+    push    %rax             # preserve rax value
+    mov     $45, %rax
+    push    %rax
+    mov     %rsp, %rax
+    call    sprint
+    pop     %rax             # remove '-' sign
+    pop     %rax             # retrieve rax value
+    neg     %eax             # -2147483648 stays negative but is divided as unsigned
+positive:
+    mov     $0, %ecx          # counter of how many bytes we need to print in the end
+divideLoop:
+    inc     %ecx             # count each byte to print - number of characters
+    mov     $0, %edx          # empty edx
+    mov     $10, %esi         # mov 10 into esi
+    div     %esi             # divide eax by esi
+    add     $48, %edx         # convert edx to it's ascii representation - edx holds the remainder after a divide instruction
+    push    %rdx             # push rdx (string representation of an intger) onto the stack
+    cmp     $0, %eax          # can the integer be divided anymore?
+    jnz     divideLoop      # jump if not zero to the label divideLoop
+
+printLoop:
+    dec     %ecx             # count down each byte that we put on the stack
+    mov     %rsp, %rax        # mov the stack pointer into rax for printing
+    call    sprint          # call our string print function
+    pop     %rax             # remove last character from the stack to move rsp forward
+    cmp     $0, %ecx          # have we printed all bytes we pushed onto the stack?
+    jnz     printLoop       # jump is not zero to the label printLoop
+
+    pop     %rsi             # restore rsi from the value we pushed onto the stack at the start
+    pop     %rdx             # restore rdx from the value we pushed onto the stack at the start
+    pop     %rcx             # restore rcx from the value we pushed onto the stack at the start
+    pop     %rax             # restore rax from the value we pushed onto the stack at the start
+    ret
+
+
+#------------------------------------------
+# void iprintLF(Integer number)
+# Integer printing function with linefeed (itoa)
+iprintLF:
+    call    iprint          # call our integer printing function
+
+    push    %rax             # push rax onto the stack to preserve it while we use the rax register in this function
+    mov     $0xA, %rax        # move 0Ah into rax - 0Ah is the ascii character for a linefeed
+    push    %rax             # push the linefeed onto the stack so we can get the address
+    mov     %rsp, %rax        # move the address of the current stack pointer into rax for sprint
+    call    sprint          # call our sprint function
+    pop     %rax             # remove our linefeed character from the stack
+    pop     %rax             # restore the original value of rax before our function was called
+    ret
+
+
+#------------------------------------------
+# int slen(String message)
+# String length calculation function
+slen:
+    push    %rbx
+    mov     %rax, %rbx
+
+nextchar:
+    cmpb    $0, (%rax)
+    jz      finished
+    inc     %rax
+    jmp     nextchar
+
+finished:
+    sub     %rbx, %rax
+    pop     %rbx
+    ret
+
+
+#------------------------------------------
+# void sprint(String message)
+# String printing function
+sprint:
+    push    %rdi
+    push    %rsi
+    push    %rdx
+    push    %rcx             # syscall overwrites rcx
+    push    %r11             # syscall overwrites r11
+    push    %rax
+    call    slen
+
+    mov     %rax, %rdx
+    mov     (%rsp), %rsi
+    mov     $1, %rdi
+    mov     $1, %rax
+    syscall
+
+    pop     %rax
+    pop     %r11
+    pop     %rcx
+    pop     %rdx
+    pop     %rsi
+    pop     %rdi
+    ret
+
+
+#------------------------------------------
+# void sprintLF(String message)
+# String printing with line feed function
+sprintLF:
+    call    sprint
+
+    push    %rax
+    mov     $0xA, %rax
+    push    %rax
+    mov     %rsp, %rax
+    call    sprint
+    pop     %rax
+    pop     %rax
+    ret
+
+
+#------------------------------------------
+# void exit()
+# Exit program and restore resources
+quit:
+    mov     $0, %rdi
+    mov     $60, %rax
+    syscall
+    ret
+
+#------------------------------------------
+# int atoi(Integer number)
+# Ascii to integer function (atoi), the string is at [rax] and the integer is returned in eax
+# Leading blanks are skipped, then an optional sign and digits are read until any other
+# character: a string without digits gives 0 and larger numbers wrap around.
+atoi:
+    push    %rbx             # preserve rbx on the stack to be restored after function runs
+    push    %rcx             # preserve rcx on the stack to be restored after function runs
+    push    %rdx             # preserve rdx on the stack to be restored after function runs
+    push    %rsi             # preserve rsi on the stack to be restored after function runs
+    xor     %ebx, %ebx        # initialize forming answer
+    xor     %ecx, %ecx        # initialize sign flag
+    mov     %rax, %rsi
+atoi1:
+    lodsb                   # scan off whitespace
+    cmp     $32, %al         # ignore leading blanks
+    je      atoi1
+    cmp     $43, %al             # if + sign proceed
+    je      atoi2
+    cmp     $45, %al             # is it - sign?
+    jne     atoi3               # no, test if numeric
+    dec     %ecx                 # was - sign, set flag for negative result
+atoi2:
+    lodsb                   # get next character
+atoi3:
+    cmp     $48, %al        # is character valid?
+    jb      atoi4          # jump if not '0' to '9'
+    cmp     $57, %al
+    ja      atoi4          # jump if not '0' to '9'
+    and     $0xf, %eax     # isolate lower four bits
+    xchg    %eax, %ebx      # multiply answer x 10
+    mov     $10, %edx
+    mul     %edx
+    add     %eax, %ebx      # add this digit
+    jmp     atoi2         # convert next digit
+atoi4:
+    mov     %ebx, %eax # result into AX
+    jecxz   atoi5 # jump if sign flag clear
+    neg     %eax # make result negative
+atoi5:
+    pop     %rsi             # restore rsi from the value we pushed onto the stack at the start
+    pop     %rdx             # restore rdx from the value we pushed onto the stack at the start
+    pop     %rcx             # restore rcx from the value we pushed onto the stack at the start
+    pop     %rbx             # restore rbx from the value we pushed onto the stack at the start
+    ret
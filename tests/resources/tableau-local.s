@@ -0,0 +1,288 @@
+	.bss
+sinput:	.skip	255	# reserve a 255 byte space in memory for the users input string
+
+	.text
+	.globl	_start
+_start:
+	call	fmain
+	mov	$1, %eax
+	int	$0x80
+fsomme:
+	push	%ebp
+	mov	%esp, %ebp
+	push	%eax
+	push	%ebx
+	push	%ecx
+	push	%edx
+	sub	$4, %esp	# allocation variable locale v$i
+	sub	$12, %esp	# allocation variable locale v$t
+	sub	$4, %esp	# allocation variable locale v$s
+	movl	$0, -20(%ebp)
+e0:
+	mov	$1, %eax
+	cmpl	$3, -20(%ebp)
+	jl	e2
+	mov	$0, %eax
+e2:
+	cmp	$0, %eax
+	je	e1
+	movl	-20(%ebp), %eax
+	movl	8(%ebp), %ebx
+	imull	-20(%ebp), %ebx
+	mov	%eax, %esi
+	movl	%ebx, -32(%ebp,%esi,4)
+	movl	-20(%ebp), %eax
+	add	$1, %eax
+	movl	%eax, -20(%ebp)
+	jmp	e0
+e1:
+	movl	-32(%ebp), %eax
+	addl	-28(%ebp), %eax
+	addl	-24(%ebp), %eax
+	movl	%eax, -36(%ebp)
+	movl	-36(%ebp), %edi
+	movl	%edi, 12(%ebp)
+	add	$20, %esp
+	pop	%edx
+	pop	%ecx
+	pop	%ebx
+	pop	%eax
+	pop	%ebp
+	ret
+	add	$20, %esp
+	pop	%edx
+	pop	%ecx
+	pop	%ebx
+	pop	%eax
+	pop	%ebp
+	ret
+fmain:
+	push	%ebp
+	mov	%esp, %ebp
+	push	%eax
+	push	%ebx
+	push	%ecx
+	push	%edx
+	sub	$4, %esp
+	push	$4
+	call	fsomme
+	add	$4, %esp		# desallocation parametres
+	pop	%eax
+	call	iprintLF
+	pop	%edx
+	pop	%ecx
+	pop	%ebx
+	pop	%eax
+	pop	%ebp
+	ret
+
+#------------------------------------------
+# void readline
+# Read a line from stdin, store string after [$eax]
+# At most 254 characters are kept, the end of a longer line is skipped. The end of the
+# input gives an empty string.
+readline:
+    push    %edx        # preserve edx on stack to be restored after function
+    push    %ecx        # preserve ecx on stack to be restored after function
+    push    %ebx        # preserve ebx on stack to be restored after function
+    push    %eax        # preserve eax on stack to be restored after function
+    push    %esi        # preserve esi on stack to be restored after function
+    mov     %eax, %ecx       # address where store result
+    lea     254(%eax), %esi # last byte of the buffer
+continue_reading:
+    mov     $3, %eax         # syscall is read = 3
+    mov     $0, %ebx         # fd is stdin = 0
+    mov     $1, %edx         # nb. of bytes to read
+    int     $0x80            # call interruption
+    cmp     $0, %eax         # if no byte read, then we reached EOF or an error, stop
+    jle     end_reading
+    cmpb    $10, (%ecx) # Found '\n', stop reading string
+    je      end_reading
+    cmp     %esi, %ecx       # Buffer full, overwrite the last byte until '\n'
+    je      continue_reading
+    inc     %ecx            # None of above, increment pointer and read next byte
+    jmp     continue_reading
+end_reading:
+    movb    $0, (%ecx)  # Add zero to yield null-terminated string
+    pop     %esi        # restore esi from value pushed onto stack at start
+    pop     %eax        # restore eax from value pushed onto stack at start
+    pop     %ebx        # restore ebx from value pushed onto stack at start
+    pop     %ecx        # restore ecx from value pushed onto stack at start
+    pop     %edx        # restore edx from value pushed onto stack at start
+    ret
+
+#------------------------------------------
+# void iprint(Integer number)
+# Integer printing function (itoa)
+iprint:
+    push    %eax             # preserve eax on the stack to be restored after function runs
+    push    %ecx             # preserve ecx on the stack to be restored after function runs
+    push    %edx             # preserve edx on the stack to be restored after function runs
+    push    %esi             # preserve esi on the stack to be restored after function runs
+
+    cmp     $0, %eax
+    jge     positive
+    push    %eax             # preserve eax value
+    mov     $45, %eax
+    push    %eax
+    mov     %esp, %eax
+    call    sprint
+    pop     %eax             # remove '-' sign
+    pop     %eax             # retrieve eax value
+    neg     %eax             # -2147483648 stays negative but is divided as unsigned
+positive:
+    mov     $0, %ecx          # counter of how many bytes we need to print in the end
+divideLoop:
+    inc     %ecx             # count each byte to print - number of characters
+    mov     $0, %edx          # empty edx
+    mov     $10, %esi         # mov 10 into esi
+    div     %esi             # divide eax by esi
+    add     $48, %edx         # convert edx to it's ascii representation - edx holds the remainder after a divide instruction
+    push    %edx             # push edx (string representation of an intger) onto the stack
+    cmp     $0, %eax          # can the integer be divided anymore?
+    jnz     divideLoop      # jump if not zero to the label divideLoop
+
+printLoop:
+    dec     %ecx             # count down each byte that we put on the stack
+    mov     %esp, %eax        # mov the stack pointer into eax for printing
+    call    sprint          # call our string print function
+    pop     %eax             # remove last character from the stack to move esp forward
+    cmp     $0, %ecx          # have we printed all bytes we pushed onto the stack?
+    jnz     printLoop       # jump is not zero to the label printLoop
+
+    pop     %esi             # restore esi from the value we pushed onto the stack at the start
+    pop     %edx             # restore edx from the value we pushed onto the stack at the start
+    pop     %ecx             # restore ecx from the value we pushed onto the stack at the start
+    pop     %eax             # restore eax from the value we pushed onto the stack at the start
+    ret
+
+
+#------------------------------------------
+# void iprintLF(Integer number)
+# Integer printing function with linefeed (itoa)
+iprintLF:
+    call    iprint          # call our integer printing function
+
+    push    %eax             # push eax onto the stack to preserve it while we use the eax register in this function
+    mov     $0xA, %eax        # move 0Ah into eax - 0Ah is the ascii character for a linefeed
+    push    %eax             # push the linefeed onto the stack so we can get the address
+    mov     %esp, %eax        # move the address of the current stack pointer into eax for sprint
+    call    sprint          # call our sprint function
+    pop     %eax             # remove our linefeed character from the stack
+    pop     %eax             # restore the original value of eax before our function was called
+    ret
+
+
+#------------------------------------------
+# int slen(String message)
+# String length calculation function
+slen:
+    push    %ebx
+    mov     %eax, %ebx
+
+nextchar:
+    cmpb    $0, (%eax)
+    jz      finished
+    inc     %eax
+    jmp     nextchar
+
+finished:
+    sub     %ebx, %eax
+    pop     %ebx
+    ret
+
+
+#------------------------------------------
+# void sprint(String message)
+# String printing function
+sprint:
+    push    %edx
+    push    %ecx
+    push    %ebx
+    push    %eax
+    call    slen
+
+    mov     %eax, %edx
+    pop     %eax
+
+    mov     %eax, %ecx
+    mov     $1, %ebx
+    mov     $4, %eax
+    int     $0x80
+
+    pop     %ebx
+    pop     %ecx
+    pop     %edx
+    ret
+
+
+#------------------------------------------
+# void sprintLF(String message)
+# String printing with line feed function
+sprintLF:
+    call    sprint
+
+    push    %eax
+    mov     $0xA, %eax
+    push    %eax
+    mov     %esp, %eax
+    call    sprint
+    pop     %eax
+    pop     %eax
+    ret
+
+
+#------------------------------------------
+# void exit()
+# Exit program and restore resources
+quit:
+    mov     $0, %ebx
+    mov     $1, %eax
+    int     $0x80
+    ret
+
+#------------------------------------------
+# int atoi(Integer number)
+# Ascii to integer function (atoi)
+# Leading blanks are skipped, then an optional sign and digits are read until any other
+# character: a string without digits gives 0 and larger numbers wrap around.
+atoi:
+    push    %ebx             # preserve ebx on the stack to be restored after function runs
+    push    %ecx             # preserve ecx on the stack to be restored after function runs
+    push    %edx             # preserve edx on the stack to be restored after function runs
+    push    %esi             # preserve esi on the stack to be restored after function runs
+    xor     %ebx, %ebx        # initialize forming answer
+    xor     %ecx, %ecx        # initialize sign flag
+    mov     %eax, %esi
+atoi1:
+    lodsb                   # scan off whitespace
+    cmp     $32, %al         # ignore leading blanks
+    je      atoi1
+    cmp     $43, %al             # if + sign proceed
+    je      atoi2
+    cmp     $45, %al             # is it - sign?
+    jne     atoi3               # no, test if numeric
+    dec     %ecx                 # was - sign, set flag for negative result
+atoi2:
+    lodsb                   # get next character
+atoi3:
+    cmp     $48, %al        # is character valid?
+    jb      atoi4          # jump if not '0' to '9'
+    cmp     $57, %al
+    ja      atoi4          # jump if not '0' to '9'
+    and     $0xf, %eax     # isolate lower four bits
+    xchg    %eax, %ebx      # multiply answer x 10
+    mov     $10, %edx
+    mul     %edx
+    add     %eax, %ebx      # add this digit
+    jmp     atoi2         # convert next digit
+atoi4:
+    mov     %ebx, %eax # result into AX
+    jecxz   atoi5 # jump if sign flag clear
+    neg     %eax # make result negative
+atoi5:
+    pop     %esi             # restore esi from the value we pushed onto the stack at the start
+    pop     %edx             # restore edx from the value we pushed onto the stack at the start
+    pop     %ecx             # restore ecx from the value we pushed onto the stack at the start
+    pop     %ebx             # restore ebx from the value we pushed onto the stack at the start
+    ret
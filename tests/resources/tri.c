@@ -0,0 +1,151 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+static int G_tab[10];
+
+static int f_initialiser(void);
+static int f_afficher(int A_n);
+static int f_echanger(int A_i, int A_j);
+static int f_trier(int A_n);
+static int f_main(void);
+
+static void ecrire(int valeur)
+{
+	printf("%d\n", valeur);
+}
+
+static int f_initialiser(void)
+{
+	int retour = 0;
+
+	G_tab[0] = 8;
+	G_tab[1] = 6;
+	G_tab[2] = 9;
+	G_tab[3] = 9;
+	G_tab[4] = 4;
+	G_tab[5] = 2;
+	G_tab[6] = 3;
+	G_tab[7] = 1;
+	G_tab[8] = 4;
+	G_tab[9] = 5;
+	return retour;
+}
+
+static int f_afficher(int A_n)
+{
+	int V_i = 0;
+	int t0, t1, t2;
+	int retour = 0;
+
+	V_i = 0;
+e0:;
+	t0 = 1;
+	if (V_i < A_n)
+		goto e2;
+	t0 = 0;
+e2:;
+	if (t0 == 0)
+		goto e1;
+	t1 = V_i;
+	ecrire(G_tab[t1]);
+	t2 = (int)((unsigned)V_i + (unsigned)1);
+	V_i = t2;
+	goto e0;
+e1:;
+	ecrire(0);
+	return retour;
+}
+
+static int f_echanger(int A_i, int A_j)
+{
+	int V_temp = 0;
+	int t3, t4, t5, t6;
+	int retour = 0;
+
+	t3 = A_j;
+	V_temp = G_tab[t3];
+	t4 = A_j;
+	t5 = A_i;
+	G_tab[t4] = G_tab[t5];
+	t6 = A_i;
+	G_tab[t6] = V_temp;
+	return retour;
+}
+
+static int f_trier(int A_n)
+{
+	int V_echange = 0;
+	int V_j = 0;
+	int V_m = 0;
+	int t7, t8, t9, t10, t11, t12, t13, t15, t16, p0, p1;
+	int retour = 0;
+
+	V_m = A_n;
+	V_echange = 1;
+e3:;
+	t7 = 1;
+	if (V_echange == 1)
+		goto e5;
+	t7 = 0;
+e5:;
+	if (t7 == 0)
+		goto e4;
+	V_echange = 0;
+	V_j = 0;
+e6:;
+	t8 = (int)((unsigned)V_m - (unsigned)1);
+	t9 = 1;
+	if (V_j < t8)
+		goto e8;
+	t9 = 0;
+e8:;
+	if (t9 == 0)
+		goto e7;
+	t10 = (int)((unsigned)V_j + (unsigned)1);
+	t11 = V_j;
+	t12 = 1;
+	if (G_tab[t10] < G_tab[t11])
+		goto e11;
+	t12 = 0;
+e11:;
+	if (t12 == 0)
+		goto e9;
+	p0 = V_j;
+	t13 = (int)((unsigned)V_j + (unsigned)1);
+	p1 = t13;
+	f_echanger(p0, p1);
+	V_echange = 1;
+	goto e10;
+e9:;
+e10:;
+	t15 = (int)((unsigned)V_j + (unsigned)1);
+	V_j = t15;
+	goto e6;
+e7:;
+	t16 = (int)((unsigned)V_m - (unsigned)1);
+	V_m = t16;
+	goto e3;
+e4:;
+	return retour;
+}
+
+static int f_main(void)
+{
+	int p0, p1, p2;
+	int retour = 0;
+
+	f_initialiser();
+	p0 = 10;
+	f_afficher(p0);
+	p1 = 10;
+	f_trier(p1);
+	p2 = 10;
+	f_afficher(p2);
+	return retour;
+}
+
+int main(void)
+{
+	f_main();
+	return 0;
+}
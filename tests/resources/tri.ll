@@ -0,0 +1,379 @@
+@v$tab = internal global [10 x i32] zeroinitializer
+
+; Lit une ligne de l'entrée et la convertit en entier. Au plus 254 caractères sont gardés, les
+; blancs de tête sont sautés puis un signe et des chiffres sont lus jusqu'à tout autre caractère :
+; une ligne sans chiffre donne 0 et les nombres trop grands sont pris modulo 2^32.
+define internal i32 @lire() {
+entree:
+  %ligne = alloca [255 x i8]
+  %i = alloca i64
+  %valeur = alloca i32
+  %debut = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 0
+  store i8 0, i8* %debut
+  store i64 0, i64* %i
+  store i32 0, i32* %valeur
+  %lus = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([9 x i8], [9 x i8]* @format.ligne, i64 0, i64 0), i8* %debut)
+  %lue = icmp eq i32 %lus, 1
+  br i1 %lue, label %reste, label %vide
+
+vide:
+  store i8 0, i8* %debut
+  br label %reste
+
+; La fin d'une ligne trop longue est sautée, puis le saut de ligne.
+reste:
+  %sautes = call i32 (i8*, ...) @scanf(i8* getelementptr inbounds ([7 x i8], [7 x i8]* @format.reste, i64 0, i64 0))
+  %fin = icmp eq i32 %sautes, -1
+  br i1 %fin, label %blancs, label %saut
+
+saut:
+  %saut.ligne = call i32 @getchar()
+  br label %blancs
+
+blancs:
+  %blanc.i = load i64, i64* %i
+  %blanc.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %blanc.i
+  %blanc.c = load i8, i8* %blanc.p
+  %est.blanc = icmp eq i8 %blanc.c, 32
+  br i1 %est.blanc, label %blanc.suivant, label %signe
+
+blanc.suivant:
+  %blanc.j = add i64 %blanc.i, 1
+  store i64 %blanc.j, i64* %i
+  br label %blancs
+
+signe:
+  %moins = icmp eq i8 %blanc.c, 45
+  %plus = icmp eq i8 %blanc.c, 43
+  %signe.present = or i1 %moins, %plus
+  br i1 %signe.present, label %signe.suivant, label %chiffres
+
+signe.suivant:
+  %signe.j = add i64 %blanc.i, 1
+  store i64 %signe.j, i64* %i
+  br label %chiffres
+
+chiffres:
+  %chiffre.i = load i64, i64* %i
+  %chiffre.p = getelementptr inbounds [255 x i8], [255 x i8]* %ligne, i64 0, i64 %chiffre.i
+  %chiffre.c = load i8, i8* %chiffre.p
+  %chiffre = sub i8 %chiffre.c, 48
+  %est.chiffre = icmp ult i8 %chiffre, 10
+  br i1 %est.chiffre, label %chiffre.suivant, label %nombre
+
+chiffre.suivant:
+  %avant = load i32, i32* %valeur
+  %dizaines = mul i32 %avant, 10
+  %unites = zext i8 %chiffre to i32
+  %apres = add i32 %dizaines, %unites
+  store i32 %apres, i32* %valeur
+  %chiffre.j = add i64 %chiffre.i, 1
+  store i64 %chiffre.j, i64* %i
+  br label %chiffres
+
+nombre:
+  %absolu = load i32, i32* %valeur
+  %oppose = sub i32 0, %absolu
+  %resultat = select i1 %moins, i32 %oppose, i32 %absolu
+  ret i32 %resultat
+}
+
+define internal void @ecrire(i32 %valeur) {
+  %ecrits = call i32 (i8*, ...) @printf(i8* getelementptr inbounds ([4 x i8], [4 x i8]* @format.entier, i64 0, i64 0), i32 %valeur)
+  ret void
+}
+
+; La division par zéro arrête le programme, -2147483648 / -1 donne -2147483648.
+define internal i32 @division(i32 %gauche, i32 %droite) {
+  %zero = icmp eq i32 %droite, 0
+  br i1 %zero, label %arret, label %diviseur
+
+arret:
+  call void @llvm.trap()
+  unreachable
+
+diviseur:
+  %moins.un = icmp eq i32 %droite, -1
+  br i1 %moins.un, label %oppose, label %quotient
+
+oppose:
+  %resultat = sub i32 0, %gauche
+  ret i32 %resultat
+
+quotient:
+  %q = sdiv i32 %gauche, %droite
+  ret i32 %q
+}
+
+@format.ligne = private unnamed_addr constant [9 x i8] c"%254[^\0A]\00"
+@format.reste = private unnamed_addr constant [7 x i8] c"%*[^\0A]\00"
+@format.entier = private unnamed_addr constant [4 x i8] c"%d\0A\00"
+
+declare i32 @scanf(i8*, ...)
+declare i32 @printf(i8*, ...)
+declare i32 @getchar()
+declare void @llvm.trap()
+
+define internal i32 @finitialiser() {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %r0 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 0
+  store i32 8, i32* %r0
+  %r1 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 1
+  store i32 6, i32* %r1
+  %r2 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 2
+  store i32 9, i32* %r2
+  %r3 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 3
+  store i32 9, i32* %r3
+  %r4 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 4
+  store i32 4, i32* %r4
+  %r5 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 5
+  store i32 2, i32* %r5
+  %r6 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 6
+  store i32 3, i32* %r6
+  %r7 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 7
+  store i32 1, i32* %r7
+  %r8 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 8
+  store i32 4, i32* %r8
+  %r9 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 9
+  store i32 5, i32* %r9
+  %r10 = load i32, i32* %retour
+  ret i32 %r10
+}
+
+define internal i32 @fafficher(i32 %v$n.param) {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$n.arg = alloca i32
+  store i32 %v$n.param, i32* %v$n.arg
+  %v$i = alloca i32
+  store i32 0, i32* %v$i
+  %t0 = alloca i32
+  %t1 = alloca i32
+  %t2 = alloca i32
+  store i32 0, i32* %v$i
+  br label %e0
+
+e0:
+  store i32 1, i32* %t0
+  %r0 = load i32, i32* %v$i
+  %r1 = load i32, i32* %v$n.arg
+  %r2 = icmp slt i32 %r0, %r1
+  br i1 %r2, label %e2, label %b0
+
+b0:
+  store i32 0, i32* %t0
+  br label %e2
+
+e2:
+  %r3 = load i32, i32* %t0
+  %r4 = icmp eq i32 %r3, 0
+  br i1 %r4, label %e1, label %b1
+
+b1:
+  %r5 = load i32, i32* %v$i
+  store i32 %r5, i32* %t1
+  %r6 = load i32, i32* %t1
+  %r7 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r6
+  %r8 = load i32, i32* %r7
+  call void @ecrire(i32 %r8)
+  %r9 = load i32, i32* %v$i
+  %r10 = add i32 %r9, 1
+  store i32 %r10, i32* %t2
+  %r11 = load i32, i32* %t2
+  store i32 %r11, i32* %v$i
+  br label %e0
+
+e1:
+  call void @ecrire(i32 0)
+  %r12 = load i32, i32* %retour
+  ret i32 %r12
+}
+
+define internal i32 @fechanger(i32 %v$i.param, i32 %v$j.param) {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$i.arg = alloca i32
+  store i32 %v$i.param, i32* %v$i.arg
+  %v$j.arg = alloca i32
+  store i32 %v$j.param, i32* %v$j.arg
+  %v$temp = alloca i32
+  store i32 0, i32* %v$temp
+  %t3 = alloca i32
+  %t4 = alloca i32
+  %t5 = alloca i32
+  %t6 = alloca i32
+  %r0 = load i32, i32* %v$j.arg
+  store i32 %r0, i32* %t3
+  %r1 = load i32, i32* %t3
+  %r2 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r1
+  %r3 = load i32, i32* %r2
+  store i32 %r3, i32* %v$temp
+  %r4 = load i32, i32* %v$j.arg
+  store i32 %r4, i32* %t4
+  %r5 = load i32, i32* %v$i.arg
+  store i32 %r5, i32* %t5
+  %r6 = load i32, i32* %t5
+  %r7 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r6
+  %r8 = load i32, i32* %r7
+  %r9 = load i32, i32* %t4
+  %r10 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r9
+  store i32 %r8, i32* %r10
+  %r11 = load i32, i32* %v$i.arg
+  store i32 %r11, i32* %t6
+  %r12 = load i32, i32* %v$temp
+  %r13 = load i32, i32* %t6
+  %r14 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r13
+  store i32 %r12, i32* %r14
+  %r15 = load i32, i32* %retour
+  ret i32 %r15
+}
+
+define internal i32 @ftrier(i32 %v$n.param) {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %v$n.arg = alloca i32
+  store i32 %v$n.param, i32* %v$n.arg
+  %v$echange = alloca i32
+  store i32 0, i32* %v$echange
+  %v$j = alloca i32
+  store i32 0, i32* %v$j
+  %v$m = alloca i32
+  store i32 0, i32* %v$m
+  %t7 = alloca i32
+  %t8 = alloca i32
+  %t9 = alloca i32
+  %t10 = alloca i32
+  %t11 = alloca i32
+  %t12 = alloca i32
+  %t13 = alloca i32
+  %t14 = alloca i32
+  %t15 = alloca i32
+  %t16 = alloca i32
+  %r0 = load i32, i32* %v$n.arg
+  store i32 %r0, i32* %v$m
+  store i32 1, i32* %v$echange
+  br label %e3
+
+e3:
+  store i32 1, i32* %t7
+  %r1 = load i32, i32* %v$echange
+  %r2 = icmp eq i32 %r1, 1
+  br i1 %r2, label %e5, label %b0
+
+b0:
+  store i32 0, i32* %t7
+  br label %e5
+
+e5:
+  %r3 = load i32, i32* %t7
+  %r4 = icmp eq i32 %r3, 0
+  br i1 %r4, label %e4, label %b1
+
+b1:
+  store i32 0, i32* %v$echange
+  store i32 0, i32* %v$j
+  br label %e6
+
+e6:
+  %r5 = load i32, i32* %v$m
+  %r6 = sub i32 %r5, 1
+  store i32 %r6, i32* %t8
+  store i32 1, i32* %t9
+  %r7 = load i32, i32* %v$j
+  %r8 = load i32, i32* %t8
+  %r9 = icmp slt i32 %r7, %r8
+  br i1 %r9, label %e8, label %b2
+
+b2:
+  store i32 0, i32* %t9
+  br label %e8
+
+e8:
+  %r10 = load i32, i32* %t9
+  %r11 = icmp eq i32 %r10, 0
+  br i1 %r11, label %e7, label %b3
+
+b3:
+  %r12 = load i32, i32* %v$j
+  %r13 = add i32 %r12, 1
+  store i32 %r13, i32* %t10
+  %r14 = load i32, i32* %v$j
+  store i32 %r14, i32* %t11
+  store i32 1, i32* %t12
+  %r15 = load i32, i32* %t10
+  %r16 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r15
+  %r17 = load i32, i32* %r16
+  %r18 = load i32, i32* %t11
+  %r19 = getelementptr inbounds [10 x i32], [10 x i32]* @v$tab, i32 0, i32 %r18
+  %r20 = load i32, i32* %r19
+  %r21 = icmp slt i32 %r17, %r20
+  br i1 %r21, label %e11, label %b4
+
+b4:
+  store i32 0, i32* %t12
+  br label %e11
+
+e11:
+  %r22 = load i32, i32* %t12
+  %r23 = icmp eq i32 %r22, 0
+  br i1 %r23, label %e9, label %b5
+
+b5:
+  %r24 = load i32, i32* %v$j
+  %r25 = load i32, i32* %v$j
+  %r26 = add i32 %r25, 1
+  store i32 %r26, i32* %t13
+  %r27 = load i32, i32* %t13
+  %r28 = call i32 @fechanger(i32 %r24, i32 %r27)
+  store i32 %r28, i32* %t14
+  store i32 1, i32* %v$echange
+  br label %e10
+
+e9:
+  br label %e10
+
+e10:
+  %r29 = load i32, i32* %v$j
+  %r30 = add i32 %r29, 1
+  store i32 %r30, i32* %t15
+  %r31 = load i32, i32* %t15
+  store i32 %r31, i32* %v$j
+  br label %e6
+
+e7:
+  %r32 = load i32, i32* %v$m
+  %r33 = sub i32 %r32, 1
+  store i32 %r33, i32* %t16
+  %r34 = load i32, i32* %t16
+  store i32 %r34, i32* %v$m
+  br label %e3
+
+e4:
+  %r35 = load i32, i32* %retour
+  ret i32 %r35
+}
+
+define internal i32 @fmain() {
+  %retour = alloca i32
+  store i32 0, i32* %retour
+  %t17 = alloca i32
+  %t18 = alloca i32
+  %t19 = alloca i32
+  %t20 = alloca i32
+  %r0 = call i32 @finitialiser()
+  store i32 %r0, i32* %t17
+  %r1 = call i32 @fafficher(i32 10)
+  store i32 %r1, i32* %t18
+  %r2 = call i32 @ftrier(i32 10)
+  store i32 %r2, i32* %t19
+  %r3 = call i32 @fafficher(i32 10)
+  store i32 %r3, i32* %t20
+  %r4 = load i32, i32* %retour
+  ret i32 %r4
+}
+
+define i32 @main() {
+  %retour = call i32 @fmain()
+  ret i32 0
+}